@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.26;
+
+/// @title MockValidationRegistry
+/// @notice Minimal ERC-8004 ValidationRegistry for SDK tests
+/// @dev Stores requests and the validator's latest response without identity
+///      registry checks. Response tags are accepted but not stored, so
+///      getValidationStatus always returns an empty tag.
+contract MockValidationRegistry {
+    struct Validation {
+        address validatorAddress;
+        uint256 agentId;
+        uint8 response;
+        bytes32 responseHash;
+        uint256 lastUpdate;
+    }
+
+    mapping(bytes32 => Validation) private _validations;
+
+    /// @notice Records a request, clearing any earlier response for the same hash
+    function validationRequest(
+        address validatorAddress,
+        uint256 agentId,
+        string calldata, /* requestURI */
+        bytes32 requestHash
+    ) external {
+        _validations[requestHash] = Validation({
+            validatorAddress: validatorAddress,
+            agentId: agentId,
+            response: 0,
+            responseHash: bytes32(0),
+            lastUpdate: 0
+        });
+    }
+
+    /// @notice Records the requested validator's response (0-100)
+    function validationResponse(
+        bytes32 requestHash,
+        uint8 response,
+        string calldata, /* responseURI */
+        bytes32 responseHash,
+        string calldata /* tag */
+    ) external {
+        Validation storage validation = _validations[requestHash];
+        require(msg.sender == validation.validatorAddress);
+        require(response <= 100);
+        validation.response = response;
+        validation.responseHash = responseHash;
+        validation.lastUpdate = block.timestamp;
+    }
+
+    function getValidationStatus(bytes32 requestHash)
+        external
+        view
+        returns (
+            address validatorAddress,
+            uint256 agentId,
+            uint8 response,
+            bytes32 responseHash,
+            string memory tag,
+            uint256 lastUpdate
+        )
+    {
+        Validation storage validation = _validations[requestHash];
+        return (
+            validation.validatorAddress,
+            validation.agentId,
+            validation.response,
+            validation.responseHash,
+            "",
+            validation.lastUpdate
+        );
+    }
+}
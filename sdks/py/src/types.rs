@@ -402,6 +402,7 @@ impl TryFrom<ArbitersAddresses> for alkahest_rs::clients::arbiters::ArbitersAddr
             nonexclusive_unrevocable_confirmation_arbiter: parse_address!(
                 nonexclusive_unrevocable_confirmation_arbiter
             ),
+            custom_arbiters: Default::default(),
         })
     }
 }
@@ -68,6 +68,8 @@ pub struct PyMockAddresses {
     pub erc1155_a: String,
     #[pyo3(get)]
     pub erc1155_b: String,
+    #[pyo3(get)]
+    pub validation_registry: String,
 }
 
 impl From<&MockAddresses> for PyMockAddresses {
@@ -79,6 +81,7 @@ impl From<&MockAddresses> for PyMockAddresses {
             erc721_b: format!("{:?}", m.erc721_b),
             erc1155_a: format!("{:?}", m.erc1155_a),
             erc1155_b: format!("{:?}", m.erc1155_b),
+            validation_registry: format!("{:?}", m.validation_registry),
         }
     }
 }
@@ -0,0 +1,168 @@
+//! Derive macros for the Alkahest Rust SDK
+//!
+//! Use through the `alkahest-rs` re-export rather than depending on this
+//! crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, LitStr, parse_macro_input};
+
+/// Generate an arbiter demand codec for a `sol!` `DemandData` struct.
+///
+/// Emits:
+/// - an `ArbiterDemandData` impl, so the struct decodes into
+///   `DecodedDemand::Extension` through `ArbiterDemandDataCodec`
+/// - `From<T> for Bytes` and `TryFrom<Bytes> for T` via `impl_abi_conversions!`
+/// - `T::decode_demand`, usable with `ArbiterDemandCodecRegistry::register_fn`
+/// - `T::register_into(&mut ArbiterDemandCodecRegistry, arbiter)`
+///
+/// Composing arbiters mark their child fields so nested demands are decoded
+/// through the same registry:
+///
+/// ```rust,ignore
+/// sol! {
+///     #[derive(Debug, ArbiterDemand)]
+///     #[arbiter_demand(children(arbiters = "arbiters", demands = "demands"))]
+///     struct DemandData {
+///         address[] arbiters;
+///         bytes[] demands;
+///         uint256 threshold;
+///     }
+/// }
+/// ```
+///
+/// `#[arbiter_demand(children)]` uses those default field names.
+#[proc_macro_derive(ArbiterDemand, attributes(arbiter_demand))]
+pub fn derive_arbiter_demand(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct Children {
+    arbiters: Ident,
+    demands: Ident,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "ArbiterDemand can only be derived for structs",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "ArbiterDemand cannot be derived for generic structs",
+        ));
+    }
+
+    let children = parse_children(&input)?;
+    if let Some(children) = &children {
+        let Fields::Named(fields) = &data.fields else {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "child demands require named fields",
+            ));
+        };
+        for field in [&children.arbiters, &children.demands] {
+            if !fields.named.iter().any(|f| f.ident.as_ref() == Some(field)) {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    format!("no field named `{field}`"),
+                ));
+            }
+        }
+    }
+
+    let name = &input.ident;
+    let krate = quote!(::alkahest_rs);
+    let arbiters_mod = quote!(#krate::clients::arbiters);
+
+    let children_impl = children.map(|Children { arbiters, demands }| {
+        quote! {
+            fn children(
+                &self,
+            ) -> ::core::option::Option<(
+                &[::alloy::primitives::Address],
+                &[::alloy::primitives::Bytes],
+            )> {
+                ::core::option::Option::Some((self.#arbiters.as_slice(), self.#demands.as_slice()))
+            }
+
+            fn set_child_demands(
+                &mut self,
+                demands: ::std::vec::Vec<::alloy::primitives::Bytes>,
+            ) {
+                self.#demands = demands;
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #arbiters_mod::ArbiterDemandData for #name {
+            #children_impl
+        }
+
+        #krate::impl_abi_conversions!(#name);
+
+        impl #name {
+            /// Decode demand bytes into a `DecodedDemand::Extension`
+            pub fn decode_demand(
+                registry: &#arbiters_mod::ArbiterDemandCodecRegistry,
+                arbiter: ::alloy::primitives::Address,
+                demand: ::alloy::primitives::Bytes,
+            ) -> ::eyre::Result<#arbiters_mod::DecodedDemand> {
+                #arbiters_mod::ArbiterDemandCodec::decode(
+                    &#arbiters_mod::ArbiterDemandDataCodec::<Self>::new(),
+                    registry,
+                    arbiter,
+                    demand,
+                )
+            }
+
+            /// Register this demand's codec for `arbiter`
+            pub fn register_into(
+                registry: &mut #arbiters_mod::ArbiterDemandCodecRegistry,
+                arbiter: ::alloy::primitives::Address,
+            ) {
+                registry.register(arbiter, #arbiters_mod::ArbiterDemandDataCodec::<Self>::new());
+            }
+        }
+    })
+}
+
+fn parse_children(input: &DeriveInput) -> syn::Result<Option<Children>> {
+    let mut children = None;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("arbiter_demand") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("children") {
+                return Err(meta.error("expected `children`"));
+            }
+            let mut arbiters = Ident::new("arbiters", Span::call_site());
+            let mut demands = Ident::new("demands", Span::call_site());
+            if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|field| {
+                    let value: LitStr = field.value()?.parse()?;
+                    if field.path.is_ident("arbiters") {
+                        arbiters = value.parse()?;
+                    } else if field.path.is_ident("demands") {
+                        demands = value.parse()?;
+                    } else {
+                        return Err(field.error("expected `arbiters` or `demands`"));
+                    }
+                    Ok(())
+                })?;
+            }
+            children = Some(Children { arbiters, demands });
+            Ok(())
+        })?;
+    }
+    Ok(children)
+}
@@ -180,6 +180,9 @@ impl DefaultExtensionConfig {
             self.arbiters_addresses
                 .nonexclusive_unrevocable_confirmation_arbiter
         );
+        for (name, address) in &self.arbiters_addresses.custom_arbiters {
+            add_info(&mut index, *address, "arbiters_addresses", name, None);
+        }
 
         add_payment_section!(
             self,
@@ -356,17 +359,121 @@ impl DefaultExtensionConfig {
         index
     }
 
+    /// Whether `name` is the field name of a built-in address slot, like
+    /// `trivial_arbiter` or `escrow_obligation_default`.
+    pub fn is_builtin_name(name: &str) -> bool {
+        // The bundled configs have no custom arbiters, so every field in
+        // their index is built in.
+        crate::addresses::BASE_SEPOLIA_ADDRESSES
+            .address_index()
+            .into_values()
+            .flatten()
+            .any(|info| info.field == name)
+    }
+
     pub fn lookup_address(&self, address: Address) -> Vec<ContractAddressInfo> {
         self.address_index().remove(&address).unwrap_or_default()
     }
+
+    /// Index entries whose field is `name`, or whose `section.field` is
+    /// `name`, sorted by section.
+    pub fn lookup_name(&self, name: &str) -> Vec<ContractAddressInfo> {
+        let mut infos: Vec<ContractAddressInfo> = self
+            .address_index()
+            .into_values()
+            .flatten()
+            .filter(|info| {
+                info.field == name
+                    || name.split_once('.').is_some_and(|(section, field)| {
+                        info.section == section && info.field == field
+                    })
+            })
+            .collect();
+        infos.sort_by(|a, b| (&a.section, &a.field).cmp(&(&b.section, &b.field)));
+        infos
+    }
+
+    /// Resolve a contract name to its address, as [`lookup_name`] matches
+    /// it. Returns `None` if the name is unknown or matches slots holding
+    /// different addresses.
+    ///
+    /// [`lookup_name`]: Self::lookup_name
+    pub fn resolve_name(&self, name: &str) -> Option<Address> {
+        let infos = self.lookup_name(name);
+        let address = infos.first()?.address;
+        infos
+            .iter()
+            .all(|info| info.address == address)
+            .then_some(address)
+    }
+}
+
+/// Human-readable names for known contract addresses, built once from an
+/// address index.
+///
+/// An address used by a single slot is labelled with that slot's field name
+/// (`trusted_oracle_arbiter`). An address shared by differently named slots
+/// lists each as `section.field`, with the `_addresses` suffix dropped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddressLabels {
+    labels: HashMap<Address, String>,
+}
+
+impl AddressLabels {
+    pub fn new(config: &DefaultExtensionConfig) -> Self {
+        let labels = config
+            .address_index()
+            .into_iter()
+            .map(|(address, infos)| (address, label_for(&infos)))
+            .collect();
+        Self { labels }
+    }
+
+    pub fn get(&self, address: &Address) -> Option<&str> {
+        self.labels.get(address).map(String::as_str)
+    }
+
+    /// `address`, followed by its label in parentheses when known.
+    pub fn describe(&self, address: &Address) -> String {
+        match self.get(address) {
+            Some(label) => format!("{address} ({label})"),
+            None => address.to_string(),
+        }
+    }
+}
+
+impl From<&DefaultExtensionConfig> for AddressLabels {
+    fn from(config: &DefaultExtensionConfig) -> Self {
+        Self::new(config)
+    }
+}
+
+fn label_for(infos: &[ContractAddressInfo]) -> String {
+    let mut infos: Vec<&ContractAddressInfo> = infos.iter().collect();
+    infos.sort_by(|a, b| (&a.section, &a.field).cmp(&(&b.section, &b.field)));
+
+    if infos.iter().all(|info| info.field == infos[0].field) {
+        return infos[0].field.clone();
+    }
+    infos
+        .iter()
+        .map(|info| {
+            let section = info
+                .section
+                .strip_suffix("_addresses")
+                .unwrap_or(&info.section);
+            format!("{section}.{}", info.field)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 fn add_info(
     index: &mut HashMap<Address, Vec<ContractAddressInfo>>,
     address: Address,
-    section: &'static str,
-    field: &'static str,
-    escrow_kind: Option<&'static str>,
+    section: &str,
+    field: &str,
+    escrow_kind: Option<&str>,
 ) {
     index.entry(address).or_default().push(ContractAddressInfo {
         address,
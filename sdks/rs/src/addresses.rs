@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use alloy::primitives::{Address, address};
 
 use crate::{
@@ -65,6 +67,7 @@ pub const BASE_SEPOLIA_ADDRESSES: DefaultExtensionConfig = DefaultExtensionConfi
         nonexclusive_unrevocable_confirmation_arbiter: address!(
             "0x9F8AF67d89B674513eFAdf29ed936c4704A64fEb"
         ),
+        custom_arbiters: BTreeMap::new(),
     },
     string_obligation_addresses: StringObligationAddresses {
         eas: address!("0x4200000000000000000000000000000000000021"),
@@ -176,6 +179,7 @@ pub const ETHEREUM_SEPOLIA_ADDRESSES: DefaultExtensionConfig = DefaultExtensionC
         nonexclusive_unrevocable_confirmation_arbiter: address!(
             "0xFeaA2fa295d1453BA382b7eE0e3F66c489A6d9Bb"
         ),
+        custom_arbiters: BTreeMap::new(),
     },
     string_obligation_addresses: StringObligationAddresses {
         eas: address!("0xC2679fBD37d54388Ce493F1DB75320D236e1815e"),
@@ -290,6 +294,7 @@ pub const GENLAYER_BRADBURY_ADDRESSES: DefaultExtensionConfig = DefaultExtension
         nonexclusive_unrevocable_confirmation_arbiter: address!(
             "0x16aeE626D398B547eDD5fa4BdAA638524C92921d"
         ),
+        custom_arbiters: BTreeMap::new(),
     },
     string_obligation_addresses: StringObligationAddresses {
         eas: address!("0xaC18Fa0DE3123215404a0C5f6d02ed9B2D0D0d98"),
@@ -386,6 +391,7 @@ pub const ETHEREUM_ADDRESSES: DefaultExtensionConfig = DefaultExtensionConfig {
         nonexclusive_unrevocable_confirmation_arbiter: address!(
             "0x01666d869918aDDDED1B30eF2d36f3C990F09BDE"
         ),
+        custom_arbiters: BTreeMap::new(),
     },
     string_obligation_addresses: StringObligationAddresses {
         eas: address!("0xA1207F3BBa224E2c9c3c6D5aF63D0eb1582Ce587"),
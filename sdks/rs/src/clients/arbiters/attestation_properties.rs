@@ -2,9 +2,33 @@
 //!
 //! This module contains arbiters that validate specific properties of attestations.
 //! All composing variants have been removed - use AllArbiter with non-composing arbiters instead.
+//!
+//! Each arbiter is reachable through [`AttestationProperties`] and offers typed
+//! demand construction, encode/decode, local evaluation against an
+//! [`IEAS::Attestation`], and an on-chain `check` call.
+
+use alloy::{
+    primitives::{Address, Bytes, FixedBytes},
+    sol_types::SolValue as _,
+};
 
 use crate::{
-    clients::arbiters::ArbitersModule, contracts, impl_abi_conversions, impl_from_attestation,
+    clients::arbiters::ArbitersModule,
+    contracts::{
+        self, IEAS,
+        arbiters::attestation_properties::{
+            AttesterArbiter as AttesterArbiterContract,
+            ExpirationTimeAfterArbiter as ExpirationTimeAfterArbiterContract,
+            ExpirationTimeBeforeArbiter as ExpirationTimeBeforeArbiterContract,
+            ExpirationTimeEqualArbiter as ExpirationTimeEqualArbiterContract,
+            RecipientArbiter as RecipientArbiterContract, RefUidArbiter as RefUidArbiterContract,
+            RevocableArbiter as RevocableArbiterContract, SchemaArbiter as SchemaArbiterContract,
+            TimeAfterArbiter as TimeAfterArbiterContract,
+            TimeBeforeArbiter as TimeBeforeArbiterContract,
+            TimeEqualArbiter as TimeEqualArbiterContract, UidArbiter as UidArbiterContract,
+        },
+    },
+    impl_abi_conversions, impl_from_attestation,
 };
 
 // Implement ABI conversions for all attestation property arbiters
@@ -27,16 +51,319 @@ impl_abi_conversions!(contracts::arbiters::attestation_properties::TimeBeforeArb
 impl_abi_conversions!(contracts::arbiters::attestation_properties::TimeEqualArbiter::DemandData);
 impl_abi_conversions!(contracts::arbiters::attestation_properties::UidArbiter::DemandData);
 
-// Implement From<IEAS::Attestation> for UidArbiter Attestation type
+// Implement From<IEAS::Attestation> for attestation property arbiter Attestation types
+impl_from_attestation!(AttesterArbiterContract::Attestation);
+impl_from_attestation!(ExpirationTimeAfterArbiterContract::Attestation);
+impl_from_attestation!(ExpirationTimeBeforeArbiterContract::Attestation);
+impl_from_attestation!(ExpirationTimeEqualArbiterContract::Attestation);
+impl_from_attestation!(RecipientArbiterContract::Attestation);
+impl_from_attestation!(RefUidArbiterContract::Attestation);
+impl_from_attestation!(RevocableArbiterContract::Attestation);
+impl_from_attestation!(SchemaArbiterContract::Attestation);
+impl_from_attestation!(TimeAfterArbiterContract::Attestation);
+impl_from_attestation!(TimeBeforeArbiterContract::Attestation);
+impl_from_attestation!(TimeEqualArbiterContract::Attestation);
 impl_from_attestation!(contracts::arbiters::attestation_properties::UidArbiter::Attestation);
 
+/// Client-side evaluation of an attestation property demand.
+///
+/// Mirrors the arbiter contract's `check`, so a `false` here means the
+/// on-chain check would revert.
+pub trait AttestationPropertyDemand {
+    fn is_satisfied_by(&self, attestation: &IEAS::Attestation) -> bool;
+}
+
+impl AttestationPropertyDemand for AttesterArbiterContract::DemandData {
+    fn is_satisfied_by(&self, attestation: &IEAS::Attestation) -> bool {
+        attestation.attester == self.attester
+    }
+}
+
+impl AttestationPropertyDemand for ExpirationTimeAfterArbiterContract::DemandData {
+    fn is_satisfied_by(&self, attestation: &IEAS::Attestation) -> bool {
+        // An attestation without expiration (0) never expires, so it is after anything.
+        attestation.expirationTime == 0 || attestation.expirationTime >= self.expirationTime
+    }
+}
+
+impl AttestationPropertyDemand for ExpirationTimeBeforeArbiterContract::DemandData {
+    fn is_satisfied_by(&self, attestation: &IEAS::Attestation) -> bool {
+        attestation.expirationTime != 0 && attestation.expirationTime <= self.expirationTime
+    }
+}
+
+impl AttestationPropertyDemand for ExpirationTimeEqualArbiterContract::DemandData {
+    fn is_satisfied_by(&self, attestation: &IEAS::Attestation) -> bool {
+        attestation.expirationTime == self.expirationTime
+    }
+}
+
+impl AttestationPropertyDemand for RecipientArbiterContract::DemandData {
+    fn is_satisfied_by(&self, attestation: &IEAS::Attestation) -> bool {
+        attestation.recipient == self.recipient
+    }
+}
+
+impl AttestationPropertyDemand for RefUidArbiterContract::DemandData {
+    fn is_satisfied_by(&self, attestation: &IEAS::Attestation) -> bool {
+        attestation.refUID == self.refUID
+    }
+}
+
+impl AttestationPropertyDemand for RevocableArbiterContract::DemandData {
+    fn is_satisfied_by(&self, attestation: &IEAS::Attestation) -> bool {
+        attestation.revocable == self.revocable
+    }
+}
+
+impl AttestationPropertyDemand for SchemaArbiterContract::DemandData {
+    fn is_satisfied_by(&self, attestation: &IEAS::Attestation) -> bool {
+        attestation.schema == self.schema
+    }
+}
+
+impl AttestationPropertyDemand for TimeAfterArbiterContract::DemandData {
+    fn is_satisfied_by(&self, attestation: &IEAS::Attestation) -> bool {
+        // 0 means no constraint
+        self.time == 0 || attestation.time >= self.time
+    }
+}
+
+impl AttestationPropertyDemand for TimeBeforeArbiterContract::DemandData {
+    fn is_satisfied_by(&self, attestation: &IEAS::Attestation) -> bool {
+        // 0 means no constraint
+        self.time == 0 || attestation.time <= self.time
+    }
+}
+
+impl AttestationPropertyDemand for TimeEqualArbiterContract::DemandData {
+    fn is_satisfied_by(&self, attestation: &IEAS::Attestation) -> bool {
+        attestation.time == self.time
+    }
+}
+
+impl AttestationPropertyDemand for UidArbiterContract::DemandData {
+    fn is_satisfied_by(&self, attestation: &IEAS::Attestation) -> bool {
+        attestation.uid == self.uid
+    }
+}
+
 /// Attestation properties arbiters API providing structured access to arbiter functionality
 pub struct AttestationProperties<'a> {
-    _module: &'a ArbitersModule,
+    module: &'a ArbitersModule,
+}
+
+macro_rules! property_arbiter {
+    (
+        $(#[$doc:meta])*
+        $name:ident, $contract:ident, $accessor:ident, $address_field:ident,
+        $param:ident => $field:ident: $field_ty:ty
+    ) => {
+        impl<'a> AttestationProperties<'a> {
+            $(#[$doc])*
+            pub fn $accessor(&self) -> $name<'a> {
+                $name::new(self.module)
+            }
+        }
+
+        $(#[$doc])*
+        pub struct $name<'a> {
+            module: &'a ArbitersModule,
+        }
+
+        impl<'a> $name<'a> {
+            pub fn new(module: &'a ArbitersModule) -> Self {
+                Self { module }
+            }
+
+            /// Address of the deployed arbiter
+            pub fn address(&self) -> Address {
+                self.module.addresses.$address_field
+            }
+
+            /// Build demand data for this arbiter
+            pub fn demand(&self, $param: $field_ty) -> $contract::DemandData {
+                $contract::DemandData { $field: $param }
+            }
+
+            pub fn encode(&self, demand: &$contract::DemandData) -> Bytes {
+                demand.abi_encode().into()
+            }
+
+            pub fn decode(&self, demand: &Bytes) -> eyre::Result<$contract::DemandData> {
+                Ok($contract::DemandData::abi_decode(demand)?)
+            }
+
+            /// Evaluate the demand against `attestation` locally, without an RPC call
+            pub fn evaluate(
+                &self,
+                demand: &$contract::DemandData,
+                attestation: &IEAS::Attestation,
+            ) -> bool {
+                demand.is_satisfied_by(attestation)
+            }
+
+            /// Call the arbiter's `check` on-chain.
+            ///
+            /// The contract reverts when the property does not match; reverts are
+            /// reported as `Ok(false)`, transport errors as `Err`.
+            pub async fn check(
+                &self,
+                attestation: &IEAS::Attestation,
+                demand: &$contract::DemandData,
+            ) -> eyre::Result<bool> {
+                let contract = $contract::new(self.address(), &self.module.public_provider);
+                match contract
+                    .check(
+                        attestation.clone().into(),
+                        self.encode(demand),
+                        FixedBytes::<32>::ZERO,
+                    )
+                    .call()
+                    .await
+                {
+                    Ok(satisfied) => Ok(satisfied),
+                    Err(err) if err.as_revert_data().is_some() => Ok(false),
+                    Err(err) => Err(err.into()),
+                }
+            }
+        }
+    };
 }
 
 impl<'a> AttestationProperties<'a> {
     pub fn new(module: &'a ArbitersModule) -> Self {
-        Self { _module: module }
+        Self { module }
+    }
+}
+
+property_arbiter!(
+    /// Requires the attestation to be made by a specific attester
+    AttesterArbiter, AttesterArbiterContract, attester, attester_arbiter,
+    attester => attester: Address
+);
+property_arbiter!(
+    /// Requires the attestation to expire at or after a timestamp, or never
+    ExpirationTimeAfterArbiter, ExpirationTimeAfterArbiterContract, expiration_time_after,
+    expiration_time_after_arbiter,
+    expiration_time => expirationTime: u64
+);
+property_arbiter!(
+    /// Requires the attestation to expire at or before a timestamp
+    ExpirationTimeBeforeArbiter, ExpirationTimeBeforeArbiterContract, expiration_time_before,
+    expiration_time_before_arbiter,
+    expiration_time => expirationTime: u64
+);
+property_arbiter!(
+    /// Requires the attestation to expire exactly at a timestamp
+    ExpirationTimeEqualArbiter, ExpirationTimeEqualArbiterContract, expiration_time_equal,
+    expiration_time_equal_arbiter,
+    expiration_time => expirationTime: u64
+);
+property_arbiter!(
+    /// Requires the attestation to have a specific recipient
+    RecipientArbiter, RecipientArbiterContract, recipient, recipient_arbiter,
+    recipient => recipient: Address
+);
+property_arbiter!(
+    /// Requires the attestation to reference a specific UID
+    RefUidArbiter, RefUidArbiterContract, ref_uid, ref_uid_arbiter,
+    ref_uid => refUID: FixedBytes<32>
+);
+property_arbiter!(
+    /// Requires the attestation to be (or not be) revocable
+    RevocableArbiter, RevocableArbiterContract, revocable, revocable_arbiter,
+    revocable => revocable: bool
+);
+property_arbiter!(
+    /// Requires the attestation to use a specific schema
+    SchemaArbiter, SchemaArbiterContract, schema, schema_arbiter,
+    schema => schema: FixedBytes<32>
+);
+property_arbiter!(
+    /// Requires the attestation to be made at or after a timestamp (0 = no constraint)
+    TimeAfterArbiter, TimeAfterArbiterContract, time_after, time_after_arbiter,
+    time => time: u64
+);
+property_arbiter!(
+    /// Requires the attestation to be made at or before a timestamp (0 = no constraint)
+    TimeBeforeArbiter, TimeBeforeArbiterContract, time_before, time_before_arbiter,
+    time => time: u64
+);
+property_arbiter!(
+    /// Requires the attestation to be made exactly at a timestamp
+    TimeEqualArbiter, TimeEqualArbiterContract, time_equal, time_equal_arbiter,
+    time => time: u64
+);
+property_arbiter!(
+    /// Requires the attestation to have a specific UID
+    UidArbiter, UidArbiterContract, uid, uid_arbiter,
+    uid => uid: FixedBytes<32>
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attestation() -> IEAS::Attestation {
+        IEAS::Attestation {
+            uid: FixedBytes::repeat_byte(1),
+            schema: FixedBytes::repeat_byte(2),
+            time: 1_000,
+            expirationTime: 2_000,
+            revocationTime: 0,
+            refUID: FixedBytes::repeat_byte(3),
+            recipient: Address::repeat_byte(4),
+            attester: Address::repeat_byte(5),
+            revocable: true,
+            data: Bytes::new(),
+        }
+    }
+
+    #[test]
+    fn evaluates_time_bounds_like_contracts() {
+        let a = attestation();
+
+        assert!(TimeAfterArbiterContract::DemandData { time: 1_000 }.is_satisfied_by(&a));
+        assert!(!TimeAfterArbiterContract::DemandData { time: 1_001 }.is_satisfied_by(&a));
+        assert!(TimeBeforeArbiterContract::DemandData { time: 0 }.is_satisfied_by(&a));
+        assert!(!TimeBeforeArbiterContract::DemandData { time: 999 }.is_satisfied_by(&a));
+
+        let never_expires = IEAS::Attestation {
+            expirationTime: 0,
+            ..attestation()
+        };
+        let after = ExpirationTimeAfterArbiterContract::DemandData {
+            expirationTime: 3_000,
+        };
+        assert!(!after.is_satisfied_by(&a));
+        assert!(after.is_satisfied_by(&never_expires));
+
+        let before = ExpirationTimeBeforeArbiterContract::DemandData {
+            expirationTime: 3_000,
+        };
+        assert!(before.is_satisfied_by(&a));
+        assert!(!before.is_satisfied_by(&never_expires));
+    }
+
+    #[test]
+    fn evaluates_equality_properties() {
+        let a = attestation();
+
+        assert!(
+            RecipientArbiterContract::DemandData {
+                recipient: a.recipient
+            }
+            .is_satisfied_by(&a)
+        );
+        assert!(
+            !AttesterArbiterContract::DemandData {
+                attester: a.recipient
+            }
+            .is_satisfied_by(&a)
+        );
+        assert!(SchemaArbiterContract::DemandData { schema: a.schema }.is_satisfied_by(&a));
+        assert!(!UidArbiterContract::DemandData { uid: a.refUID }.is_satisfied_by(&a));
+        assert!(!RevocableArbiterContract::DemandData { revocable: false }.is_satisfied_by(&a));
     }
 }
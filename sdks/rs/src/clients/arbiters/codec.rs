@@ -1,6 +1,9 @@
-use std::{any::Any, collections::HashMap, fmt, sync::Arc};
+use std::{any::Any, collections::HashMap, fmt, marker::PhantomData, sync::Arc};
 
-use alloy::primitives::{Address, Bytes};
+use alloy::{
+    primitives::{Address, Bytes},
+    sol_types::{SolStruct, SolValue},
+};
 
 use crate::clients::arbiters::DecodedDemand;
 
@@ -15,6 +18,19 @@ pub trait ArbiterDemandCodec: Send + Sync {
         arbiter: Address,
         demand: Bytes,
     ) -> eyre::Result<DecodedDemand>;
+
+    /// Encode an extension demand this codec decoded back to demand bytes.
+    ///
+    /// The default returns the bytes the demand was decoded from. Override it
+    /// when callers may build `DecodedDemand::Extension` values from typed
+    /// data, so the typed data is what gets encoded.
+    fn encode(
+        &self,
+        _registry: &ArbiterDemandCodecRegistry,
+        demand: &DecodedExtensionDemand,
+    ) -> eyre::Result<Bytes> {
+        Ok(demand.raw_data.clone())
+    }
 }
 
 pub type ArbiterDemandDecodeFn =
@@ -41,6 +57,114 @@ impl ArbiterDemandCodec for FnArbiterDemandCodec {
     }
 }
 
+/// A `sol!` demand struct that decodes into [`DecodedDemand::Extension`].
+///
+/// Usually implemented with `#[derive(ArbiterDemand)]`, which also generates
+/// the struct's `register_into` and `decode_demand` functions.
+pub trait ArbiterDemandData: SolStruct + SolValue + Clone + Send + Sync + 'static {
+    /// Arbiters and demand bytes of nested child demands, for arbiters that
+    /// compose others. `None` for leaf demands.
+    fn children(&self) -> Option<(&[Address], &[Bytes])> {
+        None
+    }
+
+    /// Replace the nested child demand bytes, in the order returned by
+    /// [`children`](Self::children).
+    fn set_child_demands(&mut self, _demands: Vec<Bytes>) {}
+}
+
+/// Decoded demand of a composing [`ArbiterDemandData`], with its child
+/// demands decoded through the registry.
+///
+/// Child arbiters are read from `data`; `children` holds one decoded demand
+/// per arbiter, in the same order.
+#[derive(Debug, Clone)]
+pub struct DecodedCompositeDemand<T> {
+    pub data: T,
+    pub children: Vec<DecodedDemand>,
+}
+
+/// Codec for an [`ArbiterDemandData`] type.
+///
+/// Leaf demands decode to an extension holding `T`; composing demands decode
+/// to an extension holding [`DecodedCompositeDemand<T>`].
+pub struct ArbiterDemandDataCodec<T> {
+    _data: PhantomData<fn() -> T>,
+}
+
+impl<T> ArbiterDemandDataCodec<T> {
+    pub fn new() -> Self {
+        Self { _data: PhantomData }
+    }
+}
+
+impl<T> Default for ArbiterDemandDataCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ArbiterDemandData> ArbiterDemandCodec for ArbiterDemandDataCodec<T> {
+    fn decode(
+        &self,
+        registry: &ArbiterDemandCodecRegistry,
+        arbiter: Address,
+        demand: Bytes,
+    ) -> eyre::Result<DecodedDemand> {
+        let data = <T as alloy::sol_types::SolType>::abi_decode(&demand)?;
+        let Some((arbiters, demands)) = data.children() else {
+            return Ok(DecodedDemand::extension(arbiter, demand, data));
+        };
+        if arbiters.len() != demands.len() {
+            return Err(eyre::eyre!(
+                "{} has {} child arbiters but {} child demands",
+                std::any::type_name::<T>(),
+                arbiters.len(),
+                demands.len()
+            ));
+        }
+
+        let children = arbiters
+            .iter()
+            .zip(demands)
+            .map(|(arbiter, demand)| registry.decode(*arbiter, demand))
+            .collect::<eyre::Result<Vec<_>>>()?;
+        Ok(DecodedDemand::extension(
+            arbiter,
+            demand,
+            DecodedCompositeDemand { data, children },
+        ))
+    }
+
+    fn encode(
+        &self,
+        registry: &ArbiterDemandCodecRegistry,
+        demand: &DecodedExtensionDemand,
+    ) -> eyre::Result<Bytes> {
+        if let Some(data) = demand.downcast_ref::<T>() {
+            return Ok(data.abi_encode().into());
+        }
+        let composite = demand
+            .downcast_ref::<DecodedCompositeDemand<T>>()
+            .ok_or_else(|| {
+                eyre::eyre!(
+                    "Expected {} extension data, found {}",
+                    std::any::type_name::<T>(),
+                    demand.type_name
+                )
+            })?;
+
+        let demands = composite
+            .children
+            .iter()
+            .map(|child| child.encode_demand_bytes(registry))
+            .collect::<eyre::Result<Vec<_>>>()?;
+        let mut data = composite.data.clone();
+        data.set_child_demands(demands);
+        Ok(data.abi_encode().into())
+    }
+}
+
 /// Address-keyed registry of arbiter demand codecs.
 #[derive(Clone, Default)]
 pub struct ArbiterDemandCodecRegistry {
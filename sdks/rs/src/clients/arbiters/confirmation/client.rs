@@ -0,0 +1,225 @@
+//! Unified confirmation arbiter client
+//!
+//! The four confirmation arbiters share one ABI for confirming, requesting
+//! and querying confirmations; they differ only in whether a confirmation can
+//! be revoked and whether an escrow accepts more than one fulfillment. This
+//! client covers all of them, selected by [`ConfirmationArbiterType`].
+
+use std::fmt;
+
+use alloy::{
+    primitives::{Address, FixedBytes, Log},
+    rpc::types::{Filter, TransactionReceipt},
+    sol_types::SolEvent as _,
+};
+
+use super::ConfirmationArbiterType;
+use crate::{
+    clients::arbiters::ArbitersModule,
+    contracts::arbiters::confirmation::{
+        ExclusiveRevocableConfirmationArbiter, ExclusiveUnrevocableConfirmationArbiter,
+    },
+};
+
+// Every confirmation arbiter declares these with identical signatures, so the
+// exclusive revocable binding is used to talk to any of them.
+type AnyConfirmationArbiter<P> =
+    ExclusiveRevocableConfirmationArbiter::ExclusiveRevocableConfirmationArbiterInstance<P>;
+
+/// `ConfirmationMade` as emitted by any confirmation arbiter
+pub type ConfirmationMade = ExclusiveRevocableConfirmationArbiter::ConfirmationMade;
+/// `ConfirmationRequested` as emitted by any confirmation arbiter
+pub type ConfirmationRequested = ExclusiveRevocableConfirmationArbiter::ConfirmationRequested;
+
+/// An operation the selected confirmation arbiter does not support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmationCapabilityError {
+    /// `revoke` was called on an unrevocable arbiter.
+    NotRevocable(ConfirmationArbiterType),
+}
+
+impl fmt::Display for ConfirmationCapabilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotRevocable(arbiter_type) => {
+                write!(f, "{arbiter_type:?} confirmations cannot be revoked")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfirmationCapabilityError {}
+
+/// Confirmation arbiter API for any [`ConfirmationArbiterType`]
+pub struct ConfirmationArbiterClient<'a> {
+    module: &'a ArbitersModule,
+    arbiter_type: ConfirmationArbiterType,
+}
+
+impl<'a> ConfirmationArbiterClient<'a> {
+    pub fn new(module: &'a ArbitersModule, arbiter_type: ConfirmationArbiterType) -> Self {
+        Self {
+            module,
+            arbiter_type,
+        }
+    }
+
+    /// The arbiter variant this client talks to
+    pub fn arbiter_type(&self) -> ConfirmationArbiterType {
+        self.arbiter_type
+    }
+
+    /// Get the contract address
+    pub fn address(&self) -> Address {
+        self.module.confirmation_arbiter_address(self.arbiter_type)
+    }
+
+    /// Confirm a fulfillment for an escrow
+    ///
+    /// Only the escrow recipient can confirm.
+    pub async fn confirm(
+        &self,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+    ) -> eyre::Result<TransactionReceipt> {
+        let arbiter = AnyConfirmationArbiter::new(self.address(), &*self.module.wallet_provider);
+
+        let receipt = arbiter
+            .confirm(fulfillment, escrow)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// Revoke a confirmation
+    ///
+    /// Only the escrow recipient can revoke. Fails with
+    /// [`ConfirmationCapabilityError::NotRevocable`] without sending a
+    /// transaction if the arbiter is unrevocable.
+    pub async fn revoke(
+        &self,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+    ) -> eyre::Result<TransactionReceipt> {
+        if !self.arbiter_type.is_revocable() {
+            return Err(ConfirmationCapabilityError::NotRevocable(self.arbiter_type).into());
+        }
+        let arbiter = AnyConfirmationArbiter::new(self.address(), &*self.module.wallet_provider);
+
+        let receipt = arbiter
+            .revoke(fulfillment, escrow)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// Request confirmation for a fulfillment
+    ///
+    /// The fulfillment attester or recipient can request confirmation.
+    pub async fn request_confirmation(
+        &self,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+    ) -> eyre::Result<TransactionReceipt> {
+        let arbiter = AnyConfirmationArbiter::new(self.address(), &*self.module.wallet_provider);
+
+        let receipt = arbiter
+            .requestConfirmation(fulfillment, escrow)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// Check if a fulfillment is confirmed for an escrow
+    pub async fn is_confirmed(
+        &self,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+    ) -> eyre::Result<bool> {
+        let arbiter = AnyConfirmationArbiter::new(self.address(), &*self.module.public_provider);
+
+        let confirmed = arbiter.confirmations(fulfillment, escrow).call().await?;
+        Ok(confirmed)
+    }
+
+    /// Check if an escrow already has a confirmed fulfillment, so confirming
+    /// another would revert. Always `false` for nonexclusive arbiters.
+    pub async fn is_escrow_taken(&self, escrow: FixedBytes<32>) -> eyre::Result<bool> {
+        let address = self.address();
+        let provider = &self.module.public_provider;
+        Ok(match self.arbiter_type {
+            ConfirmationArbiterType::ExclusiveRevocable => {
+                ExclusiveRevocableConfirmationArbiter::new(address, provider)
+                    .escrowToFulfillment(escrow)
+                    .call()
+                    .await?
+                    != FixedBytes::ZERO
+            }
+            ConfirmationArbiterType::ExclusiveUnrevocable => {
+                ExclusiveUnrevocableConfirmationArbiter::new(address, provider)
+                    .escrowConfirmed(escrow)
+                    .call()
+                    .await?
+            }
+            ConfirmationArbiterType::NonexclusiveRevocable
+            | ConfirmationArbiterType::NonexclusiveUnrevocable => false,
+        })
+    }
+
+    /// Wait for a confirmation event
+    pub async fn wait_for_confirmation(
+        &self,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+        from_block: Option<u64>,
+    ) -> eyre::Result<Log<ConfirmationMade>> {
+        let filter = Filter::new()
+            .from_block(from_block.unwrap_or(0))
+            .address(self.address())
+            .event_signature(ConfirmationMade::SIGNATURE_HASH)
+            .topic1(fulfillment)
+            .topic2(escrow);
+
+        let log = crate::utils::wait_for_first_log(
+            &self.module.public_provider,
+            &filter,
+            self.module.poll_interval,
+        )
+        .await?;
+        let decoded = log.log_decode::<ConfirmationMade>()?;
+        Ok(decoded.inner)
+    }
+
+    /// Wait for a confirmation request event
+    pub async fn wait_for_confirmation_request(
+        &self,
+        fulfillment: FixedBytes<32>,
+        confirmer: Address,
+        from_block: Option<u64>,
+    ) -> eyre::Result<Log<ConfirmationRequested>> {
+        let filter = Filter::new()
+            .from_block(from_block.unwrap_or(0))
+            .address(self.address())
+            .event_signature(ConfirmationRequested::SIGNATURE_HASH)
+            .topic1(fulfillment)
+            .topic2(confirmer.into_word());
+
+        let log = crate::utils::wait_for_first_log(
+            &self.module.public_provider,
+            &filter,
+            self.module.poll_interval,
+        )
+        .await?;
+        let decoded = log.log_decode::<ConfirmationRequested>()?;
+        Ok(decoded.inner)
+    }
+}
@@ -4,11 +4,14 @@
 
 use alloy::{
     primitives::{Address, FixedBytes, Log},
-    rpc::types::{Filter, TransactionReceipt},
-    sol_types::SolEvent as _,
+    rpc::types::TransactionReceipt,
 };
 
-use crate::{clients::arbiters::ArbitersModule, contracts};
+use super::{
+    ConfirmationArbiterType,
+    client::{ConfirmationArbiterClient, ConfirmationMade, ConfirmationRequested},
+};
+use crate::clients::arbiters::ArbitersModule;
 
 /// ExclusiveUnrevocableConfirmationArbiter API
 pub struct ExclusiveUnrevocable<'a> {
@@ -20,6 +23,10 @@ impl<'a> ExclusiveUnrevocable<'a> {
         Self { module }
     }
 
+    fn client(&self) -> ConfirmationArbiterClient<'a> {
+        ConfirmationArbiterClient::new(self.module, ConfirmationArbiterType::ExclusiveUnrevocable)
+    }
+
     /// Get the contract address
     pub fn address(&self) -> Address {
         self.module
@@ -35,22 +42,7 @@ impl<'a> ExclusiveUnrevocable<'a> {
         fulfillment: FixedBytes<32>,
         escrow: FixedBytes<32>,
     ) -> eyre::Result<TransactionReceipt> {
-        let arbiter =
-            contracts::arbiters::confirmation::ExclusiveUnrevocableConfirmationArbiter::new(
-                self.module
-                    .addresses
-                    .exclusive_unrevocable_confirmation_arbiter,
-                &*self.module.wallet_provider,
-            );
-
-        let receipt = arbiter
-            .confirm(fulfillment, escrow)
-            .send()
-            .await?
-            .get_receipt()
-            .await?;
-
-        Ok(receipt)
+        self.client().confirm(fulfillment, escrow).await
     }
 
     /// Request confirmation for a fulfillment
@@ -61,22 +53,9 @@ impl<'a> ExclusiveUnrevocable<'a> {
         fulfillment: FixedBytes<32>,
         escrow: FixedBytes<32>,
     ) -> eyre::Result<TransactionReceipt> {
-        let arbiter =
-            contracts::arbiters::confirmation::ExclusiveUnrevocableConfirmationArbiter::new(
-                self.module
-                    .addresses
-                    .exclusive_unrevocable_confirmation_arbiter,
-                &*self.module.wallet_provider,
-            );
-
-        let receipt = arbiter
-            .requestConfirmation(fulfillment, escrow)
-            .send()
-            .await?
-            .get_receipt()
-            .await?;
-
-        Ok(receipt)
+        self.client()
+            .request_confirmation(fulfillment, escrow)
+            .await
     }
 
     /// Wait for a confirmation event
@@ -85,26 +64,10 @@ impl<'a> ExclusiveUnrevocable<'a> {
         fulfillment: FixedBytes<32>,
         escrow: FixedBytes<32>,
         from_block: Option<u64>,
-    ) -> eyre::Result<
-        Log<contracts::arbiters::confirmation::ExclusiveUnrevocableConfirmationArbiter::ConfirmationMade>,
-    >{
-        let filter = Filter::new()
-            .from_block(from_block.unwrap_or(0))
-            .address(self.module.addresses.exclusive_unrevocable_confirmation_arbiter)
-            .event_signature(
-                contracts::arbiters::confirmation::ExclusiveUnrevocableConfirmationArbiter::ConfirmationMade::SIGNATURE_HASH,
-            )
-            .topic1(fulfillment)
-            .topic2(escrow);
-
-        let log = crate::utils::wait_for_first_log(
-            &*self.module.public_provider,
-            &filter,
-            self.module.poll_interval,
-        )
-        .await?;
-        let decoded = log.log_decode::<contracts::arbiters::confirmation::ExclusiveUnrevocableConfirmationArbiter::ConfirmationMade>()?;
-        Ok(decoded.inner)
+    ) -> eyre::Result<Log<ConfirmationMade>> {
+        self.client()
+            .wait_for_confirmation(fulfillment, escrow, from_block)
+            .await
     }
 
     /// Wait for a confirmation request event
@@ -113,26 +76,10 @@ impl<'a> ExclusiveUnrevocable<'a> {
         fulfillment: FixedBytes<32>,
         confirmer: Address,
         from_block: Option<u64>,
-    ) -> eyre::Result<
-        Log<contracts::arbiters::confirmation::ExclusiveUnrevocableConfirmationArbiter::ConfirmationRequested>,
-    >{
-        let filter = Filter::new()
-            .from_block(from_block.unwrap_or(0))
-            .address(self.module.addresses.exclusive_unrevocable_confirmation_arbiter)
-            .event_signature(
-                contracts::arbiters::confirmation::ExclusiveUnrevocableConfirmationArbiter::ConfirmationRequested::SIGNATURE_HASH,
-            )
-            .topic1(fulfillment)
-            .topic2(confirmer.into_word());
-
-        let log = crate::utils::wait_for_first_log(
-            &*self.module.public_provider,
-            &filter,
-            self.module.poll_interval,
-        )
-        .await?;
-        let decoded = log.log_decode::<contracts::arbiters::confirmation::ExclusiveUnrevocableConfirmationArbiter::ConfirmationRequested>()?;
-        Ok(decoded.inner)
+    ) -> eyre::Result<Log<ConfirmationRequested>> {
+        self.client()
+            .wait_for_confirmation_request(fulfillment, confirmer, from_block)
+            .await
     }
 
     /// Check if a fulfillment is confirmed for an escrow
@@ -141,15 +88,6 @@ impl<'a> ExclusiveUnrevocable<'a> {
         fulfillment: FixedBytes<32>,
         escrow: FixedBytes<32>,
     ) -> eyre::Result<bool> {
-        let arbiter =
-            contracts::arbiters::confirmation::ExclusiveUnrevocableConfirmationArbiter::new(
-                self.module
-                    .addresses
-                    .exclusive_unrevocable_confirmation_arbiter,
-                &*self.module.public_provider,
-            );
-
-        let confirmed = arbiter.confirmations(fulfillment, escrow).call().await?;
-        Ok(confirmed)
+        self.client().is_confirmed(fulfillment, escrow).await
     }
 }
@@ -0,0 +1,279 @@
+//! Confirmation inbox for escrow recipients
+//!
+//! Collects `ConfirmationRequested` events addressed to the signer across all
+//! four confirmation arbiters, keeps the ones that are still unconfirmed,
+//! and confirms or rejects them in bulk.
+
+use std::{
+    collections::HashSet,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use alloy::{
+    dyn_abi::SolType,
+    primitives::{Address, B256, FixedBytes},
+    providers::Provider as _,
+    rpc::types::{Filter, Log, TransactionReceipt},
+    sol_types::SolEvent as _,
+};
+use futures::{Stream, StreamExt as _};
+
+use super::{
+    ConfirmationArbiterType,
+    client::{ConfirmationArbiterClient, ConfirmationRequested},
+};
+use crate::{
+    clients::arbiters::{ArbitersModule, SubscriptionHandle, open_log_stream},
+    contracts::IEAS,
+    types::SharedPublicProvider,
+};
+
+/// An unconfirmed confirmation request addressed to the signer.
+#[derive(Debug, Clone)]
+pub struct PendingConfirmation {
+    pub arbiter_type: ConfirmationArbiterType,
+    pub fulfillment_uid: FixedBytes<32>,
+    pub escrow_uid: FixedBytes<32>,
+    /// The fulfillment attestation awaiting confirmation
+    pub fulfillment: IEAS::Attestation,
+    pub block_number: Option<u64>,
+    pub transaction_hash: Option<B256>,
+    /// For exclusive arbiters: another fulfillment is already confirmed for
+    /// this escrow, so confirming this one would revert.
+    pub escrow_taken: bool,
+}
+
+impl PendingConfirmation {
+    /// Decode the fulfillment's obligation data as `ObligationData`.
+    pub fn obligation_data<ObligationData: SolType>(
+        &self,
+    ) -> eyre::Result<ObligationData::RustType> {
+        ObligationData::abi_decode(&self.fulfillment.data).map_err(Into::into)
+    }
+}
+
+/// What happened to one request in [`ConfirmationInbox::respond`].
+#[derive(Debug)]
+pub enum InboxOutcome {
+    Confirmed(TransactionReceipt),
+    /// The request was rejected and an existing confirmation revoked.
+    Revoked(TransactionReceipt),
+    /// The request was rejected; nothing was confirmed, so no transaction
+    /// was needed.
+    Rejected,
+    Failed(eyre::Error),
+}
+
+/// Live stream of new requests returned by [`ConfirmationInbox::stream`].
+///
+/// Unsubscribes from the node when the stream ends or is dropped; use
+/// [`ConfirmationStream::close`] to unsubscribe and wait for it.
+pub struct ConfirmationStream<'a> {
+    requests: Pin<Box<dyn Stream<Item = eyre::Result<PendingConfirmation>> + Send + 'a>>,
+    subscription: Option<SubscriptionHandle>,
+    provider: SharedPublicProvider,
+}
+
+impl ConfirmationStream<'_> {
+    /// Unsubscribe from the node and end the stream.
+    pub async fn close(mut self) -> eyre::Result<()> {
+        match self.subscription.take() {
+            Some(subscription) => subscription.unsubscribe(&self.provider).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Unsubscribe in the background, once.
+    fn release(&mut self) {
+        let Some(subscription) = self.subscription.take() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let provider = self.provider.clone();
+        runtime.spawn(async move {
+            if let Err(err) = subscription.unsubscribe(&provider).await {
+                tracing::warn!("Failed to unsubscribe confirmation stream: {}", err);
+            }
+        });
+    }
+}
+
+impl Stream for ConfirmationStream<'_> {
+    type Item = eyre::Result<PendingConfirmation>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let next = self.requests.as_mut().poll_next(cx);
+        if let Poll::Ready(None) = next {
+            self.release();
+        }
+        next
+    }
+}
+
+impl Drop for ConfirmationStream<'_> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+/// Confirmation inbox API
+pub struct ConfirmationInbox<'a> {
+    module: &'a ArbitersModule,
+}
+
+impl<'a> ConfirmationInbox<'a> {
+    pub fn new(module: &'a ArbitersModule) -> Self {
+        Self { module }
+    }
+
+    /// The confirmer this inbox is for: the signer's address
+    pub fn confirmer(&self) -> Address {
+        self.module.signer.address()
+    }
+
+    fn filter(&self) -> Filter {
+        let addresses: Vec<Address> = ConfirmationArbiterType::ALL
+            .iter()
+            .map(|t| self.module.confirmation_arbiter_address(*t))
+            .collect();
+        Filter::new()
+            .address(addresses)
+            .event_signature(ConfirmationRequested::SIGNATURE_HASH)
+            .topic2(self.confirmer().into_word())
+    }
+
+    /// List unconfirmed requests addressed to the signer, oldest first.
+    ///
+    /// Repeated requests for the same fulfillment and escrow are reported once.
+    pub async fn list_pending(
+        &self,
+        from_block: Option<u64>,
+    ) -> eyre::Result<Vec<PendingConfirmation>> {
+        let filter = self.filter().from_block(from_block.unwrap_or(0));
+        let logs = self.module.public_provider.get_logs(&filter).await?;
+
+        let mut seen = HashSet::new();
+        let mut pending = Vec::new();
+        for log in logs {
+            let key = (
+                log.address(),
+                log.topics().get(1).copied(),
+                log.topics().get(3).copied(),
+            );
+            if !seen.insert(key) {
+                continue;
+            }
+            if let Some(request) = self.to_pending(&log).await? {
+                pending.push(request);
+            }
+        }
+        Ok(pending)
+    }
+
+    /// Stream new unconfirmed requests as they arrive.
+    ///
+    /// The subscription is released when the returned stream ends or is
+    /// dropped.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let mut requests = arbiters.confirmation().inbox().stream().await?;
+    /// while let Some(request) = requests.next().await {
+    ///     let request = request?;
+    ///     // ...
+    /// }
+    /// ```
+    pub async fn stream(&self) -> eyre::Result<ConfirmationStream<'a>> {
+        let module = self.module;
+        let (logs, subscription) = open_log_stream(
+            &self.module.public_provider,
+            &self.filter(),
+            self.module.poll_interval,
+        )
+        .await?;
+
+        let requests = logs
+            .then(move |log| async move { ConfirmationInbox::new(module).to_pending(&log).await })
+            .filter_map(|result| async move { result.transpose() });
+        Ok(ConfirmationStream {
+            requests: Box::pin(requests),
+            subscription: Some(subscription),
+            provider: self.module.public_provider.clone(),
+        })
+    }
+
+    /// Confirm every request in `requests`.
+    pub async fn confirm_all(&self, requests: &[PendingConfirmation]) -> Vec<InboxOutcome> {
+        let decisions: Vec<_> = requests.iter().map(|r| (r, true)).collect();
+        self.respond(&decisions).await
+    }
+
+    /// Confirm or reject each request.
+    ///
+    /// Rejecting revokes the confirmation if one exists, failing with
+    /// [`super::client::ConfirmationCapabilityError`] on unrevocable arbiters;
+    /// otherwise the request is simply left unconfirmed. Requests are
+    /// processed in order and one failure does not stop the rest.
+    pub async fn respond(&self, decisions: &[(&PendingConfirmation, bool)]) -> Vec<InboxOutcome> {
+        let mut outcomes = Vec::with_capacity(decisions.len());
+        for (request, confirm) in decisions {
+            let outcome = match self.respond_one(request, *confirm).await {
+                Ok(outcome) => outcome,
+                Err(err) => InboxOutcome::Failed(err),
+            };
+            outcomes.push(outcome);
+        }
+        outcomes
+    }
+
+    async fn respond_one(
+        &self,
+        request: &PendingConfirmation,
+        confirm: bool,
+    ) -> eyre::Result<InboxOutcome> {
+        let client = ConfirmationArbiterClient::new(self.module, request.arbiter_type);
+        let (fulfillment, escrow) = (request.fulfillment_uid, request.escrow_uid);
+
+        if confirm {
+            return Ok(InboxOutcome::Confirmed(
+                client.confirm(fulfillment, escrow).await?,
+            ));
+        }
+        if !client.is_confirmed(fulfillment, escrow).await? {
+            return Ok(InboxOutcome::Rejected);
+        }
+        Ok(InboxOutcome::Revoked(
+            client.revoke(fulfillment, escrow).await?,
+        ))
+    }
+
+    /// Turn a request log into a pending request, or `None` if it has already
+    /// been confirmed or did not come from a known confirmation arbiter.
+    async fn to_pending(&self, log: &Log) -> eyre::Result<Option<PendingConfirmation>> {
+        let Some(arbiter_type) = self.module.confirmation_arbiter_type(log.address()) else {
+            return Ok(None);
+        };
+        let event = log.log_decode::<ConfirmationRequested>()?.inner.data;
+
+        let client = ConfirmationArbiterClient::new(self.module, arbiter_type);
+        if client.is_confirmed(event.fulfillment, event.escrow).await? {
+            return Ok(None);
+        }
+
+        let eas = IEAS::new(self.module.addresses.eas, &*self.module.public_provider);
+        let fulfillment = eas.getAttestation(event.fulfillment).call().await?;
+
+        Ok(Some(PendingConfirmation {
+            arbiter_type,
+            fulfillment_uid: event.fulfillment,
+            escrow_uid: event.escrow,
+            fulfillment,
+            block_number: log.block_number,
+            transaction_hash: log.transaction_hash,
+            escrow_taken: client.is_escrow_taken(event.escrow).await?,
+        }))
+    }
+}
@@ -10,15 +10,20 @@
 //! - NonexclusiveUnrevocableConfirmationArbiter: Multiple fulfillments per escrow, cannot revoke
 //!
 //! Note: These arbiters do not use DemandData - they use confirmations mapping.
+//!
+//! [`client::ConfirmationArbiterClient`] covers all four variants behind a
+//! single type selected by [`ConfirmationArbiterType`].
 
+pub mod client;
 pub mod exclusive_revocable;
 pub mod exclusive_unrevocable;
+pub mod inbox;
 pub mod nonexclusive_revocable;
 pub mod nonexclusive_unrevocable;
 
 use alloy::primitives::Address;
 
-use crate::clients::arbiters::ArbitersModule;
+use crate::clients::arbiters::{ArbitersModule, DecodedDemand};
 
 /// Confirmation arbiter type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -33,6 +38,34 @@ pub enum ConfirmationArbiterType {
     NonexclusiveUnrevocable,
 }
 
+impl ConfirmationArbiterType {
+    /// All confirmation arbiter types
+    pub const ALL: [ConfirmationArbiterType; 4] = [
+        ConfirmationArbiterType::ExclusiveRevocable,
+        ConfirmationArbiterType::ExclusiveUnrevocable,
+        ConfirmationArbiterType::NonexclusiveRevocable,
+        ConfirmationArbiterType::NonexclusiveUnrevocable,
+    ];
+
+    /// Whether confirmations can be revoked
+    pub fn is_revocable(self) -> bool {
+        matches!(
+            self,
+            ConfirmationArbiterType::ExclusiveRevocable
+                | ConfirmationArbiterType::NonexclusiveRevocable
+        )
+    }
+
+    /// Whether at most one fulfillment can be confirmed per escrow
+    pub fn is_exclusive(self) -> bool {
+        matches!(
+            self,
+            ConfirmationArbiterType::ExclusiveRevocable
+                | ConfirmationArbiterType::ExclusiveUnrevocable
+        )
+    }
+}
+
 /// Confirmation arbiters API
 pub struct Confirmation<'a> {
     module: &'a ArbitersModule,
@@ -64,6 +97,86 @@ impl<'a> Confirmation<'a> {
     ) -> nonexclusive_unrevocable::NonexclusiveUnrevocable<'_> {
         nonexclusive_unrevocable::NonexclusiveUnrevocable::new(self.module)
     }
+
+    /// Access the confirmation arbiter of the given type
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let client = arbiters.confirmation().client(arbiter_type);
+    /// client.confirm(fulfillment, escrow).await?;
+    /// ```
+    pub fn client(
+        &self,
+        arbiter_type: ConfirmationArbiterType,
+    ) -> client::ConfirmationArbiterClient<'a> {
+        client::ConfirmationArbiterClient::new(self.module, arbiter_type)
+    }
+
+    /// Access the confirmation arbiter deployed at `address`
+    ///
+    /// Fails if `address` is not one of the configured confirmation arbiters.
+    pub fn for_address(
+        &self,
+        address: Address,
+    ) -> eyre::Result<client::ConfirmationArbiterClient<'a>> {
+        let arbiter_type = self
+            .module
+            .confirmation_arbiter_type(address)
+            .ok_or_else(|| eyre::eyre!("{address} is not a known confirmation arbiter"))?;
+        Ok(self.client(arbiter_type))
+    }
+
+    /// Access the confirmation arbiter used by a decoded demand
+    ///
+    /// Searches `arbiter` and, through `AnyArbiter` and `AllArbiter`, every
+    /// nested arbiter. Fails if no confirmation arbiter is found or if the
+    /// demand uses more than one kind.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let decoded = arbiters.decode_arbiter_demand(escrow.arbiter, &escrow.demand)?;
+    /// let client = arbiters.confirmation().for_demand(escrow.arbiter, &decoded)?;
+    /// client.confirm(fulfillment, escrow_uid).await?;
+    /// ```
+    pub fn for_demand(
+        &self,
+        arbiter: Address,
+        demand: &DecodedDemand,
+    ) -> eyre::Result<client::ConfirmationArbiterClient<'a>> {
+        let mut arbiters = Vec::new();
+        collect_arbiters(arbiter, demand, &mut arbiters);
+
+        let mut found: Option<ConfirmationArbiterType> = None;
+        for arbiter_type in arbiters
+            .into_iter()
+            .filter_map(|a| self.module.confirmation_arbiter_type(a))
+        {
+            match found {
+                Some(existing) if existing != arbiter_type => {
+                    return Err(eyre::eyre!(
+                        "Demand uses both {existing:?} and {arbiter_type:?} confirmation arbiters"
+                    ));
+                }
+                _ => found = Some(arbiter_type),
+            }
+        }
+        found
+            .map(|arbiter_type| self.client(arbiter_type))
+            .ok_or_else(|| eyre::eyre!("Demand does not use a confirmation arbiter"))
+    }
+
+    /// Access the signer's inbox of pending confirmation requests across all
+    /// confirmation arbiters
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let inbox = arbiters.confirmation().inbox();
+    /// let pending = inbox.list_pending(None).await?;
+    /// let outcomes = inbox.confirm_all(&pending).await;
+    /// ```
+    pub fn inbox(&self) -> inbox::ConfirmationInbox<'a> {
+        inbox::ConfirmationInbox::new(self.module)
+    }
 }
 
 impl ArbitersModule {
@@ -85,6 +198,13 @@ impl ArbitersModule {
         }
     }
 
+    /// Identify which confirmation arbiter is deployed at `address`, if any
+    pub fn confirmation_arbiter_type(&self, address: Address) -> Option<ConfirmationArbiterType> {
+        ConfirmationArbiterType::ALL
+            .into_iter()
+            .find(|t| self.confirmation_arbiter_address(*t) == address)
+    }
+
     /// Access confirmation arbiters API
     ///
     /// # Example
@@ -96,3 +216,46 @@ impl ArbitersModule {
         Confirmation::new(self)
     }
 }
+
+/// Push `arbiter` and every arbiter nested under it, depth first.
+fn collect_arbiters(arbiter: Address, demand: &DecodedDemand, out: &mut Vec<Address>) {
+    out.push(arbiter);
+    let (arbiters, demands) = match demand {
+        DecodedDemand::AnyArbiter(any) => (&any.arbiters, &any.demands),
+        DecodedDemand::AllArbiter(all) => (&all.arbiters, &all.demands),
+        _ => return,
+    };
+    for (arbiter, demand) in arbiters.iter().zip(demands) {
+        collect_arbiters(*arbiter, demand, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::Bytes;
+
+    use super::*;
+    use crate::clients::arbiters::{DecodedAllArbiterDemandData, DecodedAnyArbiterDemandData};
+
+    #[test]
+    fn collect_arbiters_descends_into_logical_demands() {
+        let leaf = |byte| DecodedDemand::Unknown {
+            arbiter: Address::repeat_byte(byte),
+            raw_data: Bytes::new(),
+        };
+        let demand = DecodedDemand::AllArbiter(DecodedAllArbiterDemandData {
+            arbiters: vec![Address::repeat_byte(2), Address::repeat_byte(3)],
+            demands: vec![
+                leaf(2),
+                DecodedDemand::AnyArbiter(DecodedAnyArbiterDemandData {
+                    arbiters: vec![Address::repeat_byte(4)],
+                    demands: vec![leaf(4)],
+                }),
+            ],
+        });
+
+        let mut arbiters = Vec::new();
+        collect_arbiters(Address::repeat_byte(1), &demand, &mut arbiters);
+        assert_eq!(arbiters, [1, 2, 3, 4].map(Address::repeat_byte).to_vec());
+    }
+}
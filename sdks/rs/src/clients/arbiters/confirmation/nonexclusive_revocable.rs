@@ -4,11 +4,14 @@
 
 use alloy::{
     primitives::{Address, FixedBytes, Log},
-    rpc::types::{Filter, TransactionReceipt},
-    sol_types::SolEvent as _,
+    rpc::types::TransactionReceipt,
 };
 
-use crate::{clients::arbiters::ArbitersModule, contracts};
+use super::{
+    ConfirmationArbiterType,
+    client::{ConfirmationArbiterClient, ConfirmationMade, ConfirmationRequested},
+};
+use crate::clients::arbiters::ArbitersModule;
 
 /// NonexclusiveRevocableConfirmationArbiter API
 pub struct NonexclusiveRevocable<'a> {
@@ -20,6 +23,10 @@ impl<'a> NonexclusiveRevocable<'a> {
         Self { module }
     }
 
+    fn client(&self) -> ConfirmationArbiterClient<'a> {
+        ConfirmationArbiterClient::new(self.module, ConfirmationArbiterType::NonexclusiveRevocable)
+    }
+
     /// Get the contract address
     pub fn address(&self) -> Address {
         self.module
@@ -35,22 +42,7 @@ impl<'a> NonexclusiveRevocable<'a> {
         fulfillment: FixedBytes<32>,
         escrow: FixedBytes<32>,
     ) -> eyre::Result<TransactionReceipt> {
-        let arbiter =
-            contracts::arbiters::confirmation::NonexclusiveRevocableConfirmationArbiter::new(
-                self.module
-                    .addresses
-                    .nonexclusive_revocable_confirmation_arbiter,
-                &*self.module.wallet_provider,
-            );
-
-        let receipt = arbiter
-            .confirm(fulfillment, escrow)
-            .send()
-            .await?
-            .get_receipt()
-            .await?;
-
-        Ok(receipt)
+        self.client().confirm(fulfillment, escrow).await
     }
 
     /// Revoke a confirmation
@@ -61,22 +53,7 @@ impl<'a> NonexclusiveRevocable<'a> {
         fulfillment: FixedBytes<32>,
         escrow: FixedBytes<32>,
     ) -> eyre::Result<TransactionReceipt> {
-        let arbiter =
-            contracts::arbiters::confirmation::NonexclusiveRevocableConfirmationArbiter::new(
-                self.module
-                    .addresses
-                    .nonexclusive_revocable_confirmation_arbiter,
-                &*self.module.wallet_provider,
-            );
-
-        let receipt = arbiter
-            .revoke(fulfillment, escrow)
-            .send()
-            .await?
-            .get_receipt()
-            .await?;
-
-        Ok(receipt)
+        self.client().revoke(fulfillment, escrow).await
     }
 
     /// Request confirmation for a fulfillment
@@ -87,22 +64,9 @@ impl<'a> NonexclusiveRevocable<'a> {
         fulfillment: FixedBytes<32>,
         escrow: FixedBytes<32>,
     ) -> eyre::Result<TransactionReceipt> {
-        let arbiter =
-            contracts::arbiters::confirmation::NonexclusiveRevocableConfirmationArbiter::new(
-                self.module
-                    .addresses
-                    .nonexclusive_revocable_confirmation_arbiter,
-                &*self.module.wallet_provider,
-            );
-
-        let receipt = arbiter
-            .requestConfirmation(fulfillment, escrow)
-            .send()
-            .await?
-            .get_receipt()
-            .await?;
-
-        Ok(receipt)
+        self.client()
+            .request_confirmation(fulfillment, escrow)
+            .await
     }
 
     /// Wait for a confirmation event
@@ -111,26 +75,10 @@ impl<'a> NonexclusiveRevocable<'a> {
         fulfillment: FixedBytes<32>,
         escrow: FixedBytes<32>,
         from_block: Option<u64>,
-    ) -> eyre::Result<
-        Log<contracts::arbiters::confirmation::NonexclusiveRevocableConfirmationArbiter::ConfirmationMade>,
-    >{
-        let filter = Filter::new()
-            .from_block(from_block.unwrap_or(0))
-            .address(self.module.addresses.nonexclusive_revocable_confirmation_arbiter)
-            .event_signature(
-                contracts::arbiters::confirmation::NonexclusiveRevocableConfirmationArbiter::ConfirmationMade::SIGNATURE_HASH,
-            )
-            .topic1(fulfillment)
-            .topic2(escrow);
-
-        let log = crate::utils::wait_for_first_log(
-            &*self.module.public_provider,
-            &filter,
-            self.module.poll_interval,
-        )
-        .await?;
-        let decoded = log.log_decode::<contracts::arbiters::confirmation::NonexclusiveRevocableConfirmationArbiter::ConfirmationMade>()?;
-        Ok(decoded.inner)
+    ) -> eyre::Result<Log<ConfirmationMade>> {
+        self.client()
+            .wait_for_confirmation(fulfillment, escrow, from_block)
+            .await
     }
 
     /// Wait for a confirmation request event
@@ -139,26 +87,10 @@ impl<'a> NonexclusiveRevocable<'a> {
         fulfillment: FixedBytes<32>,
         confirmer: Address,
         from_block: Option<u64>,
-    ) -> eyre::Result<
-        Log<contracts::arbiters::confirmation::NonexclusiveRevocableConfirmationArbiter::ConfirmationRequested>,
-    >{
-        let filter = Filter::new()
-            .from_block(from_block.unwrap_or(0))
-            .address(self.module.addresses.nonexclusive_revocable_confirmation_arbiter)
-            .event_signature(
-                contracts::arbiters::confirmation::NonexclusiveRevocableConfirmationArbiter::ConfirmationRequested::SIGNATURE_HASH,
-            )
-            .topic1(fulfillment)
-            .topic2(confirmer.into_word());
-
-        let log = crate::utils::wait_for_first_log(
-            &*self.module.public_provider,
-            &filter,
-            self.module.poll_interval,
-        )
-        .await?;
-        let decoded = log.log_decode::<contracts::arbiters::confirmation::NonexclusiveRevocableConfirmationArbiter::ConfirmationRequested>()?;
-        Ok(decoded.inner)
+    ) -> eyre::Result<Log<ConfirmationRequested>> {
+        self.client()
+            .wait_for_confirmation_request(fulfillment, confirmer, from_block)
+            .await
     }
 
     /// Check if a fulfillment is confirmed for an escrow
@@ -167,15 +99,6 @@ impl<'a> NonexclusiveRevocable<'a> {
         fulfillment: FixedBytes<32>,
         escrow: FixedBytes<32>,
     ) -> eyre::Result<bool> {
-        let arbiter =
-            contracts::arbiters::confirmation::NonexclusiveRevocableConfirmationArbiter::new(
-                self.module
-                    .addresses
-                    .nonexclusive_revocable_confirmation_arbiter,
-                &*self.module.public_provider,
-            );
-
-        let confirmed = arbiter.confirmations(fulfillment, escrow).call().await?;
-        Ok(confirmed)
+        self.client().is_confirmed(fulfillment, escrow).await
     }
 }
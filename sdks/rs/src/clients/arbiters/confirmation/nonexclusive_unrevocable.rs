@@ -4,11 +4,14 @@
 
 use alloy::{
     primitives::{Address, FixedBytes, Log},
-    rpc::types::{Filter, TransactionReceipt},
-    sol_types::SolEvent as _,
+    rpc::types::TransactionReceipt,
 };
 
-use crate::{clients::arbiters::ArbitersModule, contracts};
+use super::{
+    ConfirmationArbiterType,
+    client::{ConfirmationArbiterClient, ConfirmationMade, ConfirmationRequested},
+};
+use crate::clients::arbiters::ArbitersModule;
 
 /// NonexclusiveUnrevocableConfirmationArbiter API
 pub struct NonexclusiveUnrevocable<'a> {
@@ -20,6 +23,13 @@ impl<'a> NonexclusiveUnrevocable<'a> {
         Self { module }
     }
 
+    fn client(&self) -> ConfirmationArbiterClient<'a> {
+        ConfirmationArbiterClient::new(
+            self.module,
+            ConfirmationArbiterType::NonexclusiveUnrevocable,
+        )
+    }
+
     /// Get the contract address
     pub fn address(&self) -> Address {
         self.module
@@ -35,22 +45,7 @@ impl<'a> NonexclusiveUnrevocable<'a> {
         fulfillment: FixedBytes<32>,
         escrow: FixedBytes<32>,
     ) -> eyre::Result<TransactionReceipt> {
-        let arbiter =
-            contracts::arbiters::confirmation::NonexclusiveUnrevocableConfirmationArbiter::new(
-                self.module
-                    .addresses
-                    .nonexclusive_unrevocable_confirmation_arbiter,
-                &*self.module.wallet_provider,
-            );
-
-        let receipt = arbiter
-            .confirm(fulfillment, escrow)
-            .send()
-            .await?
-            .get_receipt()
-            .await?;
-
-        Ok(receipt)
+        self.client().confirm(fulfillment, escrow).await
     }
 
     /// Request confirmation for a fulfillment
@@ -61,22 +56,9 @@ impl<'a> NonexclusiveUnrevocable<'a> {
         fulfillment: FixedBytes<32>,
         escrow: FixedBytes<32>,
     ) -> eyre::Result<TransactionReceipt> {
-        let arbiter =
-            contracts::arbiters::confirmation::NonexclusiveUnrevocableConfirmationArbiter::new(
-                self.module
-                    .addresses
-                    .nonexclusive_unrevocable_confirmation_arbiter,
-                &*self.module.wallet_provider,
-            );
-
-        let receipt = arbiter
-            .requestConfirmation(fulfillment, escrow)
-            .send()
-            .await?
-            .get_receipt()
-            .await?;
-
-        Ok(receipt)
+        self.client()
+            .request_confirmation(fulfillment, escrow)
+            .await
     }
 
     /// Wait for a confirmation event
@@ -85,26 +67,10 @@ impl<'a> NonexclusiveUnrevocable<'a> {
         fulfillment: FixedBytes<32>,
         escrow: FixedBytes<32>,
         from_block: Option<u64>,
-    ) -> eyre::Result<
-        Log<contracts::arbiters::confirmation::NonexclusiveUnrevocableConfirmationArbiter::ConfirmationMade>,
-    >{
-        let filter = Filter::new()
-            .from_block(from_block.unwrap_or(0))
-            .address(self.module.addresses.nonexclusive_unrevocable_confirmation_arbiter)
-            .event_signature(
-                contracts::arbiters::confirmation::NonexclusiveUnrevocableConfirmationArbiter::ConfirmationMade::SIGNATURE_HASH,
-            )
-            .topic1(fulfillment)
-            .topic2(escrow);
-
-        let log = crate::utils::wait_for_first_log(
-            &*self.module.public_provider,
-            &filter,
-            self.module.poll_interval,
-        )
-        .await?;
-        let decoded = log.log_decode::<contracts::arbiters::confirmation::NonexclusiveUnrevocableConfirmationArbiter::ConfirmationMade>()?;
-        Ok(decoded.inner)
+    ) -> eyre::Result<Log<ConfirmationMade>> {
+        self.client()
+            .wait_for_confirmation(fulfillment, escrow, from_block)
+            .await
     }
 
     /// Wait for a confirmation request event
@@ -113,26 +79,10 @@ impl<'a> NonexclusiveUnrevocable<'a> {
         fulfillment: FixedBytes<32>,
         confirmer: Address,
         from_block: Option<u64>,
-    ) -> eyre::Result<
-        Log<contracts::arbiters::confirmation::NonexclusiveUnrevocableConfirmationArbiter::ConfirmationRequested>,
-    >{
-        let filter = Filter::new()
-            .from_block(from_block.unwrap_or(0))
-            .address(self.module.addresses.nonexclusive_unrevocable_confirmation_arbiter)
-            .event_signature(
-                contracts::arbiters::confirmation::NonexclusiveUnrevocableConfirmationArbiter::ConfirmationRequested::SIGNATURE_HASH,
-            )
-            .topic1(fulfillment)
-            .topic2(confirmer.into_word());
-
-        let log = crate::utils::wait_for_first_log(
-            &*self.module.public_provider,
-            &filter,
-            self.module.poll_interval,
-        )
-        .await?;
-        let decoded = log.log_decode::<contracts::arbiters::confirmation::NonexclusiveUnrevocableConfirmationArbiter::ConfirmationRequested>()?;
-        Ok(decoded.inner)
+    ) -> eyre::Result<Log<ConfirmationRequested>> {
+        self.client()
+            .wait_for_confirmation_request(fulfillment, confirmer, from_block)
+            .await
     }
 
     /// Check if a fulfillment is confirmed for an escrow
@@ -141,15 +91,6 @@ impl<'a> NonexclusiveUnrevocable<'a> {
         fulfillment: FixedBytes<32>,
         escrow: FixedBytes<32>,
     ) -> eyre::Result<bool> {
-        let arbiter =
-            contracts::arbiters::confirmation::NonexclusiveUnrevocableConfirmationArbiter::new(
-                self.module
-                    .addresses
-                    .nonexclusive_unrevocable_confirmation_arbiter,
-                &*self.module.public_provider,
-            );
-
-        let confirmed = arbiter.confirmations(fulfillment, escrow).call().await?;
-        Ok(confirmed)
+        self.client().is_confirmed(fulfillment, escrow).await
     }
 }
@@ -0,0 +1,296 @@
+//! Signed audit log for trusted oracle decisions
+//!
+//! Every decision an oracle submits can be captured as a [`DecisionRecord`]
+//! and signed with the oracle key as EIP-712 typed data. The resulting
+//! [`SignedDecisionRecord`]s are exported as JSONL so third parties can check
+//! them later with [`verify_decision_record`], without access to the oracle.
+
+use std::{
+    io::{BufRead, Write},
+    sync::{Arc, Mutex},
+};
+
+use alloy::{
+    dyn_abi::Eip712Domain,
+    primitives::{Address, B256, Bytes, FixedBytes, keccak256},
+    signers::{Signature, Signer, local::PrivateKeySigner},
+    sol,
+    sol_types::SolStruct as _,
+};
+use serde::{Deserialize, Serialize};
+
+use super::trusted_oracle::Decision;
+
+sol! {
+    /// EIP-712 payload signed for each oracle decision. Evidence is committed
+    /// to by hash so arbitrarily large blobs keep a fixed-size signature.
+    struct OracleDecisionRecord {
+        bytes32 fulfillmentUid;
+        bytes32 escrowUid;
+        bytes32 demandHash;
+        bool decision;
+        bytes32 txHash;
+        bytes32 evidenceHash;
+    }
+}
+
+/// EIP-712 domain name used when signing decision records.
+pub const DECISION_RECORD_DOMAIN_NAME: &str = "Alkahest Oracle Decision";
+/// EIP-712 domain version used when signing decision records.
+pub const DECISION_RECORD_DOMAIN_VERSION: &str = "1";
+
+/// A single oracle decision, as recorded for dispute resolution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    /// Chain the decision was submitted on.
+    pub chain_id: u64,
+    /// TrustedOracleArbiter contract the decision was submitted to.
+    pub arbiter: Address,
+    /// Oracle address expected to have signed the record.
+    pub oracle: Address,
+    /// Fulfillment attestation UID that was arbitrated.
+    pub fulfillment_uid: FixedBytes<32>,
+    /// Escrow attestation UID referenced by the fulfillment.
+    pub escrow_uid: FixedBytes<32>,
+    /// `keccak256` of the demand bytes the decision was made against.
+    pub demand_hash: FixedBytes<32>,
+    /// The oracle's decision.
+    pub decision: bool,
+    /// Hash of the `arbitrate` transaction.
+    pub tx_hash: FixedBytes<32>,
+    /// Handler-provided evidence supporting the decision.
+    pub evidence: Bytes,
+}
+
+impl DecisionRecord {
+    /// Build a record from a submitted decision and the handler's evidence.
+    pub fn from_decision(
+        decision: &Decision,
+        chain_id: u64,
+        arbiter: Address,
+        oracle: Address,
+        evidence: Bytes,
+    ) -> Self {
+        Self {
+            chain_id,
+            arbiter,
+            oracle,
+            fulfillment_uid: decision.attestation.uid,
+            escrow_uid: decision.attestation.refUID,
+            demand_hash: keccak256(&decision.demand),
+            decision: decision.decision,
+            tx_hash: decision.receipt.transaction_hash,
+            evidence,
+        }
+    }
+
+    /// EIP-712 domain the record is signed under.
+    pub fn domain(&self) -> eyre::Result<Eip712Domain> {
+        let chain_id = self.chain_id.try_into().map_err(|e| {
+            eyre::eyre!("Invalid chain id {} in decision record: {e}", self.chain_id)
+        })?;
+        Ok(Eip712Domain {
+            name: Some(DECISION_RECORD_DOMAIN_NAME.into()),
+            version: Some(DECISION_RECORD_DOMAIN_VERSION.into()),
+            chain_id: Some(chain_id),
+            verifying_contract: Some(self.arbiter),
+            salt: None,
+        })
+    }
+
+    /// Typed-data struct covered by the signature.
+    pub fn typed_data(&self) -> OracleDecisionRecord {
+        OracleDecisionRecord {
+            fulfillmentUid: self.fulfillment_uid,
+            escrowUid: self.escrow_uid,
+            demandHash: self.demand_hash,
+            decision: self.decision,
+            txHash: self.tx_hash,
+            evidenceHash: keccak256(&self.evidence),
+        }
+    }
+
+    /// EIP-712 signing hash of the record.
+    pub fn signing_hash(&self) -> eyre::Result<B256> {
+        Ok(self.typed_data().eip712_signing_hash(&self.domain()?))
+    }
+}
+
+/// A decision record together with the oracle's 65-byte EIP-712 signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedDecisionRecord {
+    #[serde(flatten)]
+    pub record: DecisionRecord,
+    pub signature: Bytes,
+}
+
+/// Verify that a signed decision record was signed by `record.oracle`.
+///
+/// Returns `Ok(false)` if the signature is well-formed but recovers to a
+/// different address (e.g. the record was tampered with), and an error if
+/// the signature bytes cannot be parsed.
+pub fn verify_decision_record(signed: &SignedDecisionRecord) -> eyre::Result<bool> {
+    let signature = Signature::from_raw(&signed.signature)?;
+    let recovered = signature.recover_address_from_prehash(&signed.record.signing_hash()?)?;
+    Ok(recovered == signed.record.oracle)
+}
+
+/// Parse JSONL produced by [`DecisionAuditLog::write_jsonl`], skipping blank lines.
+pub fn read_decision_records_jsonl<R: BufRead>(
+    reader: R,
+) -> eyre::Result<Vec<SignedDecisionRecord>> {
+    let mut records = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        records.push(serde_json::from_str(&line)?);
+    }
+    Ok(records)
+}
+
+/// In-memory, append-only log of signed oracle decisions.
+///
+/// Cloning the log is cheap and clones share the same records, so a clone can
+/// be moved into `arbitrate_many*` callbacks while the original is used for export.
+#[derive(Clone)]
+pub struct DecisionAuditLog {
+    signer: PrivateKeySigner,
+    chain_id: u64,
+    arbiter: Address,
+    records: Arc<Mutex<Vec<SignedDecisionRecord>>>,
+}
+
+impl DecisionAuditLog {
+    /// Create an empty log signing with `signer` for decisions submitted to
+    /// `arbiter` on `chain_id`.
+    pub fn new(signer: PrivateKeySigner, chain_id: u64, arbiter: Address) -> Self {
+        Self {
+            signer,
+            chain_id,
+            arbiter,
+            records: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Address of the oracle key used to sign records.
+    pub fn oracle(&self) -> Address {
+        self.signer.address()
+    }
+
+    /// Sign and append a record for a submitted decision.
+    pub async fn record(
+        &self,
+        decision: &Decision,
+        evidence: Bytes,
+    ) -> eyre::Result<SignedDecisionRecord> {
+        let record = DecisionRecord::from_decision(
+            decision,
+            self.chain_id,
+            self.arbiter,
+            self.signer.address(),
+            evidence,
+        );
+        let signed = self.sign(record).await?;
+        self.records
+            .lock()
+            .map_err(|_| eyre::eyre!("decision audit log lock poisoned"))?
+            .push(signed.clone());
+        Ok(signed)
+    }
+
+    /// Sign an arbitrary record without appending it to the log.
+    pub async fn sign(&self, record: DecisionRecord) -> eyre::Result<SignedDecisionRecord> {
+        let signature = self
+            .signer
+            .sign_typed_data(&record.typed_data(), &record.domain()?)
+            .await?;
+        Ok(SignedDecisionRecord {
+            record,
+            signature: signature.as_bytes().into(),
+        })
+    }
+
+    /// Snapshot of all records signed so far, in submission order.
+    pub fn records(&self) -> Vec<SignedDecisionRecord> {
+        self.records
+            .lock()
+            .map(|records| records.clone())
+            .unwrap_or_default()
+    }
+
+    /// Write every record as one JSON object per line.
+    pub fn write_jsonl<W: Write>(&self, mut writer: W) -> eyre::Result<()> {
+        for record in self.records() {
+            serde_json::to_writer(&mut writer, &record)?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// Render every record as a JSONL string.
+    pub fn to_jsonl(&self) -> eyre::Result<String> {
+        let mut buf = Vec::new();
+        self.write_jsonl(&mut buf)?;
+        Ok(String::from_utf8(buf)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::address;
+
+    use super::*;
+
+    fn sample_record(oracle: Address) -> DecisionRecord {
+        DecisionRecord {
+            chain_id: 31337,
+            arbiter: address!("0x1111111111111111111111111111111111111111"),
+            oracle,
+            fulfillment_uid: FixedBytes::repeat_byte(0x01),
+            escrow_uid: FixedBytes::repeat_byte(0x02),
+            demand_hash: keccak256([0xab, 0xcd]),
+            decision: true,
+            tx_hash: FixedBytes::repeat_byte(0x03),
+            evidence: Bytes::from_static(b"{\"score\":0.97}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn signed_record_verifies_and_detects_tampering() {
+        let signer = PrivateKeySigner::random();
+        let log = DecisionAuditLog::new(
+            signer.clone(),
+            31337,
+            address!("0x1111111111111111111111111111111111111111"),
+        );
+
+        let signed = log.sign(sample_record(signer.address())).await.unwrap();
+        assert!(verify_decision_record(&signed).unwrap());
+
+        let mut tampered = signed.clone();
+        tampered.record.decision = false;
+        assert!(!verify_decision_record(&tampered).unwrap());
+
+        let mut wrong_evidence = signed;
+        wrong_evidence.record.evidence = Bytes::from_static(b"{}");
+        assert!(!verify_decision_record(&wrong_evidence).unwrap());
+    }
+
+    #[tokio::test]
+    async fn jsonl_roundtrip_preserves_records() {
+        let signer = PrivateKeySigner::random();
+        let log = DecisionAuditLog::new(signer.clone(), 31337, Address::ZERO);
+        let signed = log.sign(sample_record(signer.address())).await.unwrap();
+        log.records.lock().unwrap().push(signed.clone());
+        log.records.lock().unwrap().push(signed.clone());
+
+        let jsonl = log.to_jsonl().unwrap();
+        assert_eq!(jsonl.lines().count(), 2);
+
+        let parsed = read_decision_records_jsonl(jsonl.as_bytes()).unwrap();
+        assert_eq!(parsed, vec![signed.clone(), signed]);
+        assert!(parsed.iter().all(|r| verify_decision_record(r).unwrap()));
+    }
+}
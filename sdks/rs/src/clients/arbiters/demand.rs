@@ -0,0 +1,664 @@
+//! Fluent builder for arbiter demand trees
+//!
+//! [`Demand`] describes an arbiter and its demand data without committing to
+//! contract addresses. Encoding resolves the addresses from an
+//! [`ArbitersAddresses`] set and produces the `ArbiterData` an escrow expects,
+//! in the same format [`ArbiterDemandCodecRegistry`](super::ArbiterDemandCodecRegistry)
+//! decodes.
+
+use alloy::primitives::{Address, Bytes, FixedBytes, U256};
+
+use super::{ArbiterDemandCodecRegistry, ArbitersAddresses, ArbitersModule, DecodedDemand};
+use crate::{
+    contracts::{
+        arbiters::{
+            ERC8004Arbiter, TrustedOracleArbiter,
+            attestation_properties::{
+                AttesterArbiter, ExpirationTimeAfterArbiter, ExpirationTimeBeforeArbiter,
+                ExpirationTimeEqualArbiter, RecipientArbiter, RefUidArbiter, RevocableArbiter,
+                SchemaArbiter, TimeAfterArbiter, TimeBeforeArbiter, TimeEqualArbiter, UidArbiter,
+            },
+            logical::{AllArbiter, AnyArbiter},
+        },
+        obligations::CommitRevealObligation,
+    },
+    types::ArbiterData,
+};
+
+/// An arbiter demand tree, built with the constructor functions below.
+///
+/// # Example
+/// ```rust,ignore
+/// let demand = Demand::all([
+///     Demand::trusted_oracle(oracle, Bytes::new()),
+///     Demand::recipient(bob),
+///     Demand::commit_reveal(commit_reveal_obligation, bond, deadline),
+/// ]);
+/// let item = demand.encode(&arbiters_module.addresses);
+/// ```
+#[derive(Debug, Clone)]
+pub enum Demand {
+    // Core arbiters (no demand data)
+    Trivial,
+    Intrinsics,
+    ReferencesEscrow,
+
+    // Core arbiters (with demand data)
+    TrustedOracle(TrustedOracleArbiter::DemandData),
+    ERC8004(ERC8004Arbiter::DemandData),
+
+    // Logical arbiters
+    Any(Vec<Demand>),
+    All(Vec<Demand>),
+
+    // Attestation property arbiters
+    Attester(Address),
+    ExpirationTimeAfter(u64),
+    ExpirationTimeBefore(u64),
+    ExpirationTimeEqual(u64),
+    Recipient(Address),
+    RefUid(FixedBytes<32>),
+    Revocable(bool),
+    Schema(FixedBytes<32>),
+    TimeAfter(u64),
+    TimeBefore(u64),
+    TimeEqual(u64),
+    Uid(FixedBytes<32>),
+
+    /// `CommitRevealObligation` used as an arbiter. Its address is not part of
+    /// `ArbitersAddresses`, so it is carried explicitly.
+    CommitReveal {
+        obligation: Address,
+        demand: CommitRevealObligation::DemandData,
+    },
+
+    /// Pre-encoded arbiter and demand, for arbiters this builder doesn't cover.
+    Raw(ArbiterData),
+}
+
+impl Demand {
+    pub fn trivial() -> Self {
+        Self::Trivial
+    }
+
+    pub fn intrinsics() -> Self {
+        Self::Intrinsics
+    }
+
+    pub fn references_escrow() -> Self {
+        Self::ReferencesEscrow
+    }
+
+    pub fn trusted_oracle(oracle: Address, data: impl Into<Bytes>) -> Self {
+        Self::TrustedOracle(TrustedOracleArbiter::DemandData {
+            oracle,
+            data: data.into(),
+        })
+    }
+
+    pub fn erc8004(
+        validation_registry: Address,
+        validator_address: Address,
+        min_response: u8,
+        data: impl Into<Bytes>,
+    ) -> Self {
+        Self::ERC8004(ERC8004Arbiter::DemandData {
+            validationRegistry: validation_registry,
+            validatorAddress: validator_address,
+            minResponse: min_response,
+            data: data.into(),
+        })
+    }
+
+    /// Satisfied when any child demand is satisfied.
+    pub fn any(demands: impl IntoIterator<Item = Demand>) -> Self {
+        Self::Any(demands.into_iter().collect())
+    }
+
+    /// Satisfied when every child demand is satisfied.
+    pub fn all(demands: impl IntoIterator<Item = Demand>) -> Self {
+        Self::All(demands.into_iter().collect())
+    }
+
+    pub fn attester(attester: Address) -> Self {
+        Self::Attester(attester)
+    }
+
+    pub fn expiration_time_after(expiration_time: u64) -> Self {
+        Self::ExpirationTimeAfter(expiration_time)
+    }
+
+    pub fn expiration_time_before(expiration_time: u64) -> Self {
+        Self::ExpirationTimeBefore(expiration_time)
+    }
+
+    pub fn expiration_time_equal(expiration_time: u64) -> Self {
+        Self::ExpirationTimeEqual(expiration_time)
+    }
+
+    pub fn recipient(recipient: Address) -> Self {
+        Self::Recipient(recipient)
+    }
+
+    pub fn ref_uid(ref_uid: FixedBytes<32>) -> Self {
+        Self::RefUid(ref_uid)
+    }
+
+    pub fn revocable(revocable: bool) -> Self {
+        Self::Revocable(revocable)
+    }
+
+    pub fn schema(schema: FixedBytes<32>) -> Self {
+        Self::Schema(schema)
+    }
+
+    pub fn time_after(time: u64) -> Self {
+        Self::TimeAfter(time)
+    }
+
+    pub fn time_before(time: u64) -> Self {
+        Self::TimeBefore(time)
+    }
+
+    pub fn time_equal(time: u64) -> Self {
+        Self::TimeEqual(time)
+    }
+
+    pub fn uid(uid: FixedBytes<32>) -> Self {
+        Self::Uid(uid)
+    }
+
+    /// Require a commit-reveal fulfillment from `obligation` (the
+    /// `CommitRevealObligation` address) with the given bond and deadline.
+    pub fn commit_reveal(obligation: Address, bond_amount: U256, commit_deadline: U256) -> Self {
+        Self::CommitReveal {
+            obligation,
+            demand: CommitRevealObligation::DemandData {
+                bondAmount: bond_amount,
+                commitDeadline: commit_deadline,
+            },
+        }
+    }
+
+    pub fn raw(arbiter: Address, demand: impl Into<Bytes>) -> Self {
+        Self::Raw(ArbiterData {
+            arbiter,
+            demand: demand.into(),
+        })
+    }
+
+    /// Encode the demand tree, resolving arbiter addresses from `addresses`.
+    pub fn encode(&self, addresses: &ArbitersAddresses) -> ArbiterData {
+        let (arbiter, demand): (Address, Bytes) = match self {
+            Demand::Trivial => (addresses.trivial_arbiter, Bytes::new()),
+            Demand::Intrinsics => (addresses.intrinsics_arbiter, Bytes::new()),
+            Demand::ReferencesEscrow => (addresses.references_escrow_arbiter, Bytes::new()),
+            Demand::TrustedOracle(demand) => {
+                (addresses.trusted_oracle_arbiter, demand.clone().into())
+            }
+            Demand::ERC8004(demand) => (addresses.erc8004_arbiter, demand.clone().into()),
+            Demand::Any(children) => {
+                let (arbiters, demands) = encode_children(children, addresses);
+                (
+                    addresses.any_arbiter,
+                    AnyArbiter::DemandData { arbiters, demands }.into(),
+                )
+            }
+            Demand::All(children) => {
+                let (arbiters, demands) = encode_children(children, addresses);
+                (
+                    addresses.all_arbiter,
+                    AllArbiter::DemandData { arbiters, demands }.into(),
+                )
+            }
+            Demand::Attester(attester) => (
+                addresses.attester_arbiter,
+                AttesterArbiter::DemandData {
+                    attester: *attester,
+                }
+                .into(),
+            ),
+            Demand::ExpirationTimeAfter(expiration_time) => (
+                addresses.expiration_time_after_arbiter,
+                ExpirationTimeAfterArbiter::DemandData {
+                    expirationTime: *expiration_time,
+                }
+                .into(),
+            ),
+            Demand::ExpirationTimeBefore(expiration_time) => (
+                addresses.expiration_time_before_arbiter,
+                ExpirationTimeBeforeArbiter::DemandData {
+                    expirationTime: *expiration_time,
+                }
+                .into(),
+            ),
+            Demand::ExpirationTimeEqual(expiration_time) => (
+                addresses.expiration_time_equal_arbiter,
+                ExpirationTimeEqualArbiter::DemandData {
+                    expirationTime: *expiration_time,
+                }
+                .into(),
+            ),
+            Demand::Recipient(recipient) => (
+                addresses.recipient_arbiter,
+                RecipientArbiter::DemandData {
+                    recipient: *recipient,
+                }
+                .into(),
+            ),
+            Demand::RefUid(ref_uid) => (
+                addresses.ref_uid_arbiter,
+                RefUidArbiter::DemandData { refUID: *ref_uid }.into(),
+            ),
+            Demand::Revocable(revocable) => (
+                addresses.revocable_arbiter,
+                RevocableArbiter::DemandData {
+                    revocable: *revocable,
+                }
+                .into(),
+            ),
+            Demand::Schema(schema) => (
+                addresses.schema_arbiter,
+                SchemaArbiter::DemandData { schema: *schema }.into(),
+            ),
+            Demand::TimeAfter(time) => (
+                addresses.time_after_arbiter,
+                TimeAfterArbiter::DemandData { time: *time }.into(),
+            ),
+            Demand::TimeBefore(time) => (
+                addresses.time_before_arbiter,
+                TimeBeforeArbiter::DemandData { time: *time }.into(),
+            ),
+            Demand::TimeEqual(time) => (
+                addresses.time_equal_arbiter,
+                TimeEqualArbiter::DemandData { time: *time }.into(),
+            ),
+            Demand::Uid(uid) => (
+                addresses.uid_arbiter,
+                UidArbiter::DemandData { uid: *uid }.into(),
+            ),
+            Demand::CommitReveal { obligation, demand } => (*obligation, demand.clone().into()),
+            Demand::Raw(data) => (data.arbiter, data.demand.clone()),
+        };
+
+        ArbiterData { arbiter, demand }
+    }
+}
+
+impl DecodedDemand {
+    /// Convert back into a [`Demand`] tree.
+    ///
+    /// `Unknown` and `Extension` demands become [`Demand::Raw`] with the bytes
+    /// they were decoded from.
+    pub fn to_demand(&self) -> Demand {
+        self.to_demand_with(&mut |extension| Ok(extension.raw_data.clone()))
+            .expect("raw extension bytes are always available")
+    }
+
+    /// Convert back into a [`Demand`] tree, encoding `Extension` demands
+    /// through their codec's [`encode`](super::ArbiterDemandCodec::encode) hook.
+    pub fn to_demand_with_registry(
+        &self,
+        registry: &ArbiterDemandCodecRegistry,
+    ) -> eyre::Result<Demand> {
+        self.to_demand_with(&mut |extension| match registry.get(&extension.arbiter) {
+            Some(codec) => codec.encode(registry, extension),
+            None => Ok(extension.raw_data.clone()),
+        })
+    }
+
+    /// Encode back to an arbiter/demand pair, resolving core arbiter
+    /// addresses from `addresses`.
+    ///
+    /// Decoding the result with the same registry gives back this demand.
+    pub fn encode(&self, addresses: &ArbitersAddresses) -> ArbiterData {
+        self.to_demand().encode(addresses)
+    }
+
+    /// Like [`DecodedDemand::encode`], but encodes `Extension` demands through
+    /// their registered codec.
+    pub fn encode_with_registry(
+        &self,
+        addresses: &ArbitersAddresses,
+        registry: &ArbiterDemandCodecRegistry,
+    ) -> eyre::Result<ArbiterData> {
+        Ok(self.to_demand_with_registry(registry)?.encode(addresses))
+    }
+
+    /// Encode only this demand's bytes, without its arbiter address.
+    ///
+    /// Unlike [`DecodedDemand::encode`] this needs no address set: logical
+    /// arbiters keep the child arbiters they were decoded with, and
+    /// `Extension` demands encode through their registered codec. Used by
+    /// codecs that re-encode nested child demands.
+    pub fn encode_demand_bytes(
+        &self,
+        registry: &ArbiterDemandCodecRegistry,
+    ) -> eyre::Result<Bytes> {
+        let children = |demands: &[DecodedDemand]| {
+            demands
+                .iter()
+                .map(|d| d.encode_demand_bytes(registry))
+                .collect::<eyre::Result<Vec<_>>>()
+        };
+
+        Ok(match self {
+            DecodedDemand::AnyArbiter(d) => AnyArbiter::DemandData {
+                arbiters: d.arbiters.clone(),
+                demands: children(&d.demands)?,
+            }
+            .into(),
+            DecodedDemand::AllArbiter(d) => AllArbiter::DemandData {
+                arbiters: d.arbiters.clone(),
+                demands: children(&d.demands)?,
+            }
+            .into(),
+            DecodedDemand::Extension(extension) => match registry.get(&extension.arbiter) {
+                Some(codec) => codec.encode(registry, extension)?,
+                None => extension.raw_data.clone(),
+            },
+            // Remaining demands are leaves whose bytes do not depend on
+            // arbiter addresses.
+            leaf => {
+                leaf.to_demand()
+                    .encode(&ArbitersAddresses::default())
+                    .demand
+            }
+        })
+    }
+
+    fn to_demand_with(
+        &self,
+        encode_extension: &mut impl FnMut(&super::DecodedExtensionDemand) -> eyre::Result<Bytes>,
+    ) -> eyre::Result<Demand> {
+        let mut children = |demands: &[DecodedDemand]| {
+            demands
+                .iter()
+                .map(|d| d.to_demand_with(encode_extension))
+                .collect::<eyre::Result<Vec<_>>>()
+        };
+
+        Ok(match self {
+            DecodedDemand::TrivialArbiter => Demand::Trivial,
+            DecodedDemand::IntrinsicsArbiter => Demand::Intrinsics,
+            DecodedDemand::ReferencesEscrowArbiter => Demand::ReferencesEscrow,
+            DecodedDemand::TrustedOracle(d) => Demand::TrustedOracle(d.clone()),
+            DecodedDemand::ERC8004Arbiter(d) => Demand::ERC8004(d.clone()),
+            DecodedDemand::AnyArbiter(d) => Demand::Any(children(&d.demands)?),
+            DecodedDemand::AllArbiter(d) => Demand::All(children(&d.demands)?),
+            DecodedDemand::AttesterArbiter(d) => Demand::Attester(d.attester),
+            DecodedDemand::ExpirationTimeAfterArbiter(d) => {
+                Demand::ExpirationTimeAfter(d.expirationTime)
+            }
+            DecodedDemand::ExpirationTimeBeforeArbiter(d) => {
+                Demand::ExpirationTimeBefore(d.expirationTime)
+            }
+            DecodedDemand::ExpirationTimeEqualArbiter(d) => {
+                Demand::ExpirationTimeEqual(d.expirationTime)
+            }
+            DecodedDemand::RecipientArbiter(d) => Demand::Recipient(d.recipient),
+            DecodedDemand::RefUidArbiter(d) => Demand::RefUid(d.refUID),
+            DecodedDemand::RevocableArbiter(d) => Demand::Revocable(d.revocable),
+            DecodedDemand::SchemaArbiter(d) => Demand::Schema(d.schema),
+            DecodedDemand::TimeAfterArbiter(d) => Demand::TimeAfter(d.time),
+            DecodedDemand::TimeBeforeArbiter(d) => Demand::TimeBefore(d.time),
+            DecodedDemand::TimeEqualArbiter(d) => Demand::TimeEqual(d.time),
+            DecodedDemand::UidArbiter(d) => Demand::Uid(d.uid),
+            DecodedDemand::Unknown { arbiter, raw_data } => Demand::raw(*arbiter, raw_data.clone()),
+            DecodedDemand::Extension(extension) => {
+                Demand::raw(extension.arbiter, encode_extension(extension)?)
+            }
+        })
+    }
+}
+
+impl ArbitersModule {
+    /// Encode a decoded demand tree using this module's addresses and codecs
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let mut decoded = arbiters_module.decode_arbiter_demand(arbiter, &demand)?;
+    /// // ... modify a nested leaf ...
+    /// let item = arbiters_module.encode_decoded_demand(&decoded)?;
+    /// ```
+    pub fn encode_decoded_demand(&self, demand: &DecodedDemand) -> eyre::Result<ArbiterData> {
+        demand.encode_with_registry(&self.addresses, &self.demand_codecs)
+    }
+}
+
+fn encode_children(
+    children: &[Demand],
+    addresses: &ArbitersAddresses,
+) -> (Vec<Address>, Vec<Bytes>) {
+    children
+        .iter()
+        .map(|child| {
+            let ArbiterData { arbiter, demand } = child.encode(addresses);
+            (arbiter, demand)
+        })
+        .unzip()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::address;
+
+    use super::*;
+    use crate::clients::arbiters::{DecodedDemand, default_demand_codecs};
+
+    #[test]
+    fn encoded_tree_decodes_with_default_codecs() {
+        let addresses = ArbitersAddresses::default();
+        let oracle = address!("0x1111111111111111111111111111111111111111");
+        let recipient = address!("0x2222222222222222222222222222222222222222");
+        let commit_reveal = address!("0x3333333333333333333333333333333333333333");
+
+        let demand = Demand::all([
+            Demand::trusted_oracle(oracle, Bytes::from_static(&[0x01])),
+            Demand::any([Demand::recipient(recipient), Demand::time_after(100)]),
+            Demand::commit_reveal(commit_reveal, U256::from(10), U256::from(3600)),
+        ]);
+        let encoded = demand.encode(&addresses);
+        assert_eq!(encoded.arbiter, addresses.all_arbiter);
+
+        let decoded = default_demand_codecs(&addresses)
+            .decode(encoded.arbiter, &encoded.demand)
+            .unwrap();
+        let DecodedDemand::AllArbiter(all) = decoded else {
+            panic!("expected AllArbiter");
+        };
+        assert_eq!(
+            all.arbiters,
+            vec![
+                addresses.trusted_oracle_arbiter,
+                addresses.any_arbiter,
+                commit_reveal
+            ]
+        );
+
+        let DecodedDemand::TrustedOracle(leaf) = &all.demands[0] else {
+            panic!("expected TrustedOracle");
+        };
+        assert_eq!(leaf.oracle, oracle);
+        assert_eq!(leaf.data, Bytes::from_static(&[0x01]));
+
+        let DecodedDemand::AnyArbiter(any) = &all.demands[1] else {
+            panic!("expected AnyArbiter");
+        };
+        assert!(matches!(
+            &any.demands[0],
+            DecodedDemand::RecipientArbiter(d) if d.recipient == recipient
+        ));
+        assert!(matches!(
+            &any.demands[1],
+            DecodedDemand::TimeAfterArbiter(d) if d.time == 100
+        ));
+
+        // Commit-reveal has no default codec, but its bytes survive untouched
+        let DecodedDemand::Unknown { arbiter, raw_data } = &all.demands[2] else {
+            panic!("expected Unknown");
+        };
+        assert_eq!(*arbiter, commit_reveal);
+        let commit_demand = CommitRevealObligation::DemandData::try_from(raw_data).unwrap();
+        assert_eq!(commit_demand.bondAmount, U256::from(10));
+        assert_eq!(commit_demand.commitDeadline, U256::from(3600));
+    }
+
+    #[test]
+    fn leaf_encoding_matches_manual_abi_encoding() {
+        let addresses = ArbitersAddresses::default();
+        let recipient = address!("0x2222222222222222222222222222222222222222");
+
+        let manual = AllArbiter::DemandData {
+            arbiters: vec![addresses.trivial_arbiter, addresses.recipient_arbiter],
+            demands: vec![
+                Bytes::new(),
+                RecipientArbiter::DemandData { recipient }.into(),
+            ],
+        };
+        let built = Demand::all([Demand::trivial(), Demand::recipient(recipient)]);
+
+        assert_eq!(built.encode(&addresses).demand, Bytes::from(manual));
+
+        let raw = Demand::raw(recipient, Bytes::from_static(&[0xaa])).encode(&addresses);
+        assert_eq!(raw.arbiter, recipient);
+        assert_eq!(raw.demand, Bytes::from_static(&[0xaa]));
+    }
+
+    mod roundtrip {
+        use proptest::prelude::*;
+
+        use super::*;
+        use crate::clients::arbiters::ArbiterDemandCodec;
+
+        const EXTENSION_ARBITER: Address = address!("0x00000000000000000000000000000000000e0001");
+        const UNKNOWN_ARBITER: Address = address!("0x00000000000000000000000000000000000e0002");
+
+        /// Extension whose typed data is the reversed demand bytes, so the
+        /// encode hook has to undo the transformation.
+        struct ReversedCodec;
+
+        impl ArbiterDemandCodec for ReversedCodec {
+            fn decode(
+                &self,
+                _registry: &ArbiterDemandCodecRegistry,
+                arbiter: Address,
+                demand: Bytes,
+            ) -> eyre::Result<DecodedDemand> {
+                let reversed: Vec<u8> = demand.iter().rev().copied().collect();
+                Ok(DecodedDemand::extension(arbiter, demand, reversed))
+            }
+
+            fn encode(
+                &self,
+                _registry: &ArbiterDemandCodecRegistry,
+                demand: &crate::clients::arbiters::DecodedExtensionDemand,
+            ) -> eyre::Result<Bytes> {
+                let reversed = demand
+                    .downcast_ref::<Vec<u8>>()
+                    .ok_or_else(|| eyre::eyre!("unexpected extension data"))?;
+                Ok(reversed.iter().rev().copied().collect::<Vec<u8>>().into())
+            }
+        }
+
+        fn registry(addresses: &ArbitersAddresses) -> ArbiterDemandCodecRegistry {
+            default_demand_codecs(addresses).with_codec(EXTENSION_ARBITER, ReversedCodec)
+        }
+
+        fn address() -> impl Strategy<Value = Address> {
+            any::<[u8; 20]>().prop_map(Address::from)
+        }
+
+        fn word() -> impl Strategy<Value = FixedBytes<32>> {
+            any::<[u8; 32]>().prop_map(FixedBytes::from)
+        }
+
+        fn bytes() -> impl Strategy<Value = Bytes> {
+            proptest::collection::vec(any::<u8>(), 0..48).prop_map(Bytes::from)
+        }
+
+        fn leaf() -> impl Strategy<Value = Demand> {
+            prop_oneof![
+                Just(Demand::Trivial),
+                Just(Demand::Intrinsics),
+                Just(Demand::ReferencesEscrow),
+                (address(), bytes()).prop_map(|(o, d)| Demand::trusted_oracle(o, d)),
+                (address(), address(), any::<u8>(), bytes())
+                    .prop_map(|(r, v, m, d)| Demand::erc8004(r, v, m, d)),
+                address().prop_map(Demand::Attester),
+                any::<u64>().prop_map(Demand::ExpirationTimeAfter),
+                any::<u64>().prop_map(Demand::ExpirationTimeBefore),
+                any::<u64>().prop_map(Demand::ExpirationTimeEqual),
+                address().prop_map(Demand::Recipient),
+                word().prop_map(Demand::RefUid),
+                any::<bool>().prop_map(Demand::Revocable),
+                word().prop_map(Demand::Schema),
+                any::<u64>().prop_map(Demand::TimeAfter),
+                any::<u64>().prop_map(Demand::TimeBefore),
+                any::<u64>().prop_map(Demand::TimeEqual),
+                word().prop_map(Demand::Uid),
+                bytes().prop_map(|d| Demand::raw(UNKNOWN_ARBITER, d)),
+                bytes().prop_map(|d| Demand::raw(EXTENSION_ARBITER, d)),
+            ]
+        }
+
+        fn demand() -> impl Strategy<Value = Demand> {
+            leaf().prop_recursive(4, 32, 4, |inner| {
+                prop_oneof![
+                    proptest::collection::vec(inner.clone(), 0..4).prop_map(Demand::Any),
+                    proptest::collection::vec(inner, 0..4).prop_map(Demand::All),
+                ]
+            })
+        }
+
+        proptest! {
+            #[test]
+            fn decode_then_encode_is_identity(demand in demand()) {
+                let addresses = ArbitersAddresses::default();
+                let registry = registry(&addresses);
+                let encoded = demand.encode(&addresses);
+
+                let decoded = registry.decode(encoded.arbiter, &encoded.demand).unwrap();
+
+                let reencoded = decoded.encode_with_registry(&addresses, &registry).unwrap();
+                prop_assert_eq!(reencoded.arbiter, encoded.arbiter);
+                prop_assert_eq!(&reencoded.demand, &encoded.demand);
+
+                let passthrough = decoded.encode(&addresses);
+                prop_assert_eq!(passthrough.arbiter, encoded.arbiter);
+                prop_assert_eq!(&passthrough.demand, &encoded.demand);
+            }
+        }
+
+        #[test]
+        fn modified_leaf_is_reencoded() {
+            let addresses = ArbitersAddresses::default();
+            let registry = registry(&addresses);
+            let old_oracle = address!("0x1111111111111111111111111111111111111111");
+            let new_oracle = address!("0x2222222222222222222222222222222222222222");
+
+            let encoded = Demand::all([
+                Demand::trivial(),
+                Demand::trusted_oracle(old_oracle, Bytes::new()),
+            ])
+            .encode(&addresses);
+            let mut decoded = registry.decode(encoded.arbiter, &encoded.demand).unwrap();
+
+            let DecodedDemand::AllArbiter(all) = &mut decoded else {
+                panic!("expected AllArbiter");
+            };
+            let DecodedDemand::TrustedOracle(leaf) = &mut all.demands[1] else {
+                panic!("expected TrustedOracle");
+            };
+            leaf.oracle = new_oracle;
+
+            let reencoded = decoded.encode(&addresses);
+            let expected = Demand::all([
+                Demand::trivial(),
+                Demand::trusted_oracle(new_oracle, Bytes::new()),
+            ])
+            .encode(&addresses);
+            assert_eq!(reencoded.demand, expected.demand);
+        }
+    }
+}
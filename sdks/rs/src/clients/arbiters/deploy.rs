@@ -0,0 +1,225 @@
+//! Deployment and registration of custom arbiters
+//!
+//! Deploys an arbiter from a compiled Foundry artifact, records its address
+//! under a name in [`ArbitersAddresses::custom_arbiters`], and registers its
+//! demand codec, so the arbiter decodes like a built-in one and shows up by
+//! name in the client's address index.
+
+use std::path::Path;
+
+use alloy::{
+    dyn_abi::{DynSolValue, JsonAbiExt as _},
+    json_abi::{ContractObject, JsonAbi},
+    network::TransactionBuilder as _,
+    primitives::{Address, Bytes},
+    providers::Provider as _,
+    rpc::types::{TransactionReceipt, TransactionRequest},
+};
+
+use super::{ArbiterDemandCodec, ArbitersAddresses, ArbitersModule};
+use crate::DefaultExtensionConfig;
+
+/// A compiled contract: its ABI and creation bytecode.
+#[derive(Debug, Clone)]
+pub struct ContractArtifact {
+    pub abi: JsonAbi,
+    pub bytecode: Bytes,
+}
+
+impl ContractArtifact {
+    /// Parse a Foundry artifact, like those under `src/contracts/`.
+    pub fn from_json(json: &str) -> eyre::Result<Self> {
+        let object: ContractObject = serde_json::from_str(json)?;
+        let abi = object
+            .abi
+            .ok_or_else(|| eyre::eyre!("Artifact has no ABI"))?;
+        let bytecode = object
+            .bytecode
+            .filter(|bytecode| !bytecode.is_empty())
+            .ok_or_else(|| eyre::eyre!("Artifact has no creation bytecode"))?;
+        Ok(Self { abi, bytecode })
+    }
+
+    /// Read and parse a Foundry artifact file.
+    pub fn from_file(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Creation bytecode followed by the ABI-encoded constructor arguments,
+    /// checked against the constructor's inputs.
+    pub fn deploy_code(&self, constructor_args: &[DynSolValue]) -> eyre::Result<Bytes> {
+        let args = match &self.abi.constructor {
+            Some(constructor) => constructor.abi_encode_input(constructor_args)?,
+            None if constructor_args.is_empty() => Vec::new(),
+            None => {
+                return Err(eyre::eyre!(
+                    "Artifact has no constructor but {} arguments were given",
+                    constructor_args.len()
+                ));
+            }
+        };
+        Ok([self.bytecode.as_ref(), &args].concat().into())
+    }
+}
+
+/// A custom arbiter deployed by [`ArbitersModule::deploy_arbiter`].
+#[derive(Debug, Clone)]
+pub struct DeployedArbiter {
+    pub name: String,
+    pub address: Address,
+    pub receipt: TransactionReceipt,
+}
+
+impl ArbitersAddresses {
+    /// Address of the custom arbiter recorded under `name`
+    pub fn custom_arbiter(&self, name: &str) -> Option<Address> {
+        self.custom_arbiters.get(name).copied()
+    }
+}
+
+impl ArbitersModule {
+    /// Deploy a contract from `artifact`, signed by this module's wallet.
+    ///
+    /// The deployed address is the receipt's `contract_address`.
+    pub async fn deploy_artifact(
+        &self,
+        artifact: &ContractArtifact,
+        constructor_args: &[DynSolValue],
+    ) -> eyre::Result<TransactionReceipt> {
+        let tx =
+            TransactionRequest::default().with_deploy_code(artifact.deploy_code(constructor_args)?);
+        let receipt = self
+            .wallet_provider
+            .send_transaction(tx)
+            .await?
+            .get_receipt()
+            .await?;
+
+        if !receipt.status() {
+            return Err(eyre::eyre!(
+                "Deployment transaction {} reverted",
+                receipt.transaction_hash
+            ));
+        }
+        Ok(receipt)
+    }
+
+    /// Record an already deployed arbiter under `name` and register the codec
+    /// for its demands.
+    ///
+    /// Replaces any arbiter previously recorded under `name`.
+    pub fn register_arbiter<C>(
+        &mut self,
+        name: impl Into<String>,
+        address: Address,
+        codec: C,
+    ) -> &mut Self
+    where
+        C: ArbiterDemandCodec + 'static,
+    {
+        self.addresses.custom_arbiters.insert(name.into(), address);
+        self.register_demand_codec(address, codec)
+    }
+
+    /// Deploy a custom arbiter from `artifact` and register it under `name`.
+    ///
+    /// The name then resolves through the client's address index. Fails
+    /// before deploying if `name` is empty or shadows a built-in contract
+    /// name such as `trivial_arbiter`.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let artifact = ContractArtifact::from_file("out/MyArbiter.sol/MyArbiter.json")?;
+    /// let deployed = client
+    ///     .extensions
+    ///     .arbiters
+    ///     .deploy_arbiter(
+    ///         "my_arbiter",
+    ///         &artifact,
+    ///         &[DynSolValue::Address(eas)],
+    ///         ArbiterDemandDataCodec::<MyArbiter::DemandData>::new(),
+    ///     )
+    ///     .await?;
+    ///
+    /// assert_eq!(client.resolve_name("my_arbiter"), Some(deployed.address));
+    /// ```
+    pub async fn deploy_arbiter<C>(
+        &mut self,
+        name: impl Into<String>,
+        artifact: &ContractArtifact,
+        constructor_args: &[DynSolValue],
+        codec: C,
+    ) -> eyre::Result<DeployedArbiter>
+    where
+        C: ArbiterDemandCodec + 'static,
+    {
+        let name = name.into();
+        check_custom_arbiter_name(&name)?;
+
+        let receipt = self.deploy_artifact(artifact, constructor_args).await?;
+        let address = receipt
+            .contract_address
+            .ok_or_else(|| eyre::eyre!("Deployment receipt has no contract address"))?;
+
+        self.register_arbiter(name.clone(), address, codec);
+        Ok(DeployedArbiter {
+            name,
+            address,
+            receipt,
+        })
+    }
+}
+
+/// Fails for names the address index couldn't resolve unambiguously.
+fn check_custom_arbiter_name(name: &str) -> eyre::Result<()> {
+    if name.is_empty() {
+        return Err(eyre::eyre!("Custom arbiter name must not be empty"));
+    }
+    if DefaultExtensionConfig::is_builtin_name(name) {
+        return Err(eyre::eyre!(
+            "Custom arbiter name `{name}` collides with a built-in contract"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::address;
+
+    use super::*;
+
+    const TRIVIAL_ARBITER: &str = include_str!("../../contracts/arbiters/TrivialArbiter.json");
+    const TRUSTED_ORACLE_ARBITER: &str =
+        include_str!("../../contracts/arbiters/TrustedOracleArbiter.json");
+
+    #[test]
+    fn deploy_code_appends_checked_constructor_args() -> eyre::Result<()> {
+        let trivial = ContractArtifact::from_json(TRIVIAL_ARBITER)?;
+        assert_eq!(trivial.deploy_code(&[])?, trivial.bytecode);
+        assert!(trivial.deploy_code(&[DynSolValue::Bool(true)]).is_err());
+
+        let oracle = ContractArtifact::from_json(TRUSTED_ORACLE_ARBITER)?;
+        let eas = address!("0x4200000000000000000000000000000000000021");
+        let code = oracle.deploy_code(&[DynSolValue::Address(eas)])?;
+        assert_eq!(code.len(), oracle.bytecode.len() + 32);
+        assert_eq!(&code[code.len() - 20..], eas.as_slice());
+        assert!(oracle.deploy_code(&[]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn builtin_names_are_rejected() {
+        assert!(check_custom_arbiter_name("trivial_arbiter").is_err());
+        assert!(check_custom_arbiter_name("escrow_obligation_default").is_err());
+        assert!(check_custom_arbiter_name("").is_err());
+        assert!(check_custom_arbiter_name("my_arbiter").is_ok());
+    }
+
+    #[test]
+    fn interface_artifacts_are_rejected() {
+        let err =
+            ContractArtifact::from_json(include_str!("../../contracts/IEAS.json")).unwrap_err();
+        assert!(err.to_string().contains("bytecode"));
+    }
+}
@@ -0,0 +1,359 @@
+//! ERC-8004 arbiter client
+//!
+//! `ERC8004Arbiter` passes when an ERC-8004 ValidationRegistry holds a
+//! response from the expected validator, at or above a minimum score, for the
+//! request hash `keccak256(abi.encode(fulfillmentUid, demand.data))`. This
+//! module reads that status, requests and answers validations, waits for a
+//! passing response, and explains why `check` would fail.
+
+use std::fmt;
+
+use alloy::{
+    primitives::{Address, Bytes, FixedBytes, U256},
+    rpc::types::TransactionReceipt,
+    sol,
+};
+use tokio::time::{Duration, Instant};
+
+use crate::{
+    clients::arbiters::ArbitersModule,
+    contracts::{IEAS, arbiters::ERC8004Arbiter as ERC8004ArbiterContract},
+};
+
+sol! {
+    /// Subset of the ERC-8004 ValidationRegistry used by the arbiter and
+    /// this client.
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IValidationRegistry {
+        function validationRequest(
+            address validatorAddress,
+            uint256 agentId,
+            string calldata requestURI,
+            bytes32 requestHash
+        ) external;
+
+        function validationResponse(
+            bytes32 requestHash,
+            uint8 response,
+            string calldata responseURI,
+            bytes32 responseHash,
+            string calldata tag
+        ) external;
+
+        function getValidationStatus(bytes32 requestHash)
+            external
+            view
+            returns (
+                address validatorAddress,
+                uint256 agentId,
+                uint8 response,
+                bytes32 responseHash,
+                string memory tag,
+                uint256 lastUpdate
+            );
+    }
+}
+
+/// Validation status for one request hash, as reported by the registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationStatus {
+    pub request_hash: FixedBytes<32>,
+    pub validator: Address,
+    pub agent_id: U256,
+    /// Latest response, 0-100. Zero is indistinguishable from pending.
+    pub response: u8,
+    pub response_hash: FixedBytes<32>,
+    pub tag: String,
+    pub last_update: U256,
+}
+
+impl ValidationStatus {
+    /// Whether the registry knows about this request at all.
+    pub fn exists(&self) -> bool {
+        self.validator != Address::ZERO
+    }
+}
+
+/// Reason `ERC8004Arbiter.check` would reject a fulfillment, in the order
+/// the contract checks them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Erc8004CheckFailure {
+    FulfillmentMustReferenceEscrow {
+        escrow_uid: FixedBytes<32>,
+        ref_uid: FixedBytes<32>,
+    },
+    InvalidMinResponse(u8),
+    ValidationNotFound,
+    ValidatorMismatch {
+        expected: Address,
+        actual: Address,
+    },
+    ResponseBelowMinimum {
+        response: u8,
+        min_response: u8,
+    },
+}
+
+impl fmt::Display for Erc8004CheckFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FulfillmentMustReferenceEscrow {
+                escrow_uid,
+                ref_uid,
+            } => write!(
+                f,
+                "fulfillment refUID {ref_uid} does not reference escrow {escrow_uid}"
+            ),
+            Self::InvalidMinResponse(min) => {
+                write!(f, "minResponse {min} is outside the supported 1-100 range")
+            }
+            Self::ValidationNotFound => write!(f, "registry has no validation for the request"),
+            Self::ValidatorMismatch { expected, actual } => write!(
+                f,
+                "validation came from {actual}, but demand requires {expected}"
+            ),
+            Self::ResponseBelowMinimum {
+                response,
+                min_response,
+            } => write!(f, "response {response} is below the minimum {min_response}"),
+        }
+    }
+}
+
+/// ERC-8004 arbiter API
+pub struct Erc8004<'a> {
+    module: &'a ArbitersModule,
+}
+
+impl<'a> Erc8004<'a> {
+    pub fn new(module: &'a ArbitersModule) -> Self {
+        Self { module }
+    }
+
+    /// Address of the deployed ERC8004Arbiter
+    pub fn address(&self) -> Address {
+        self.module.addresses.erc8004_arbiter
+    }
+
+    /// Build demand data, rejecting a `min_response` the arbiter would refuse.
+    pub fn demand(
+        &self,
+        validation_registry: Address,
+        validator: Address,
+        min_response: u8,
+        data: Bytes,
+    ) -> eyre::Result<ERC8004ArbiterContract::DemandData> {
+        if !(1..=100).contains(&min_response) {
+            return Err(eyre::eyre!(
+                "minResponse must be between 1 and 100, got {min_response}"
+            ));
+        }
+        Ok(ERC8004ArbiterContract::DemandData {
+            validationRegistry: validation_registry,
+            validatorAddress: validator,
+            minResponse: min_response,
+            data,
+        })
+    }
+
+    /// Request hash the arbiter looks up for `fulfillment_uid` under `demand`.
+    pub fn request_hash(
+        &self,
+        fulfillment_uid: FixedBytes<32>,
+        demand: &ERC8004ArbiterContract::DemandData,
+    ) -> FixedBytes<32> {
+        ArbitersModule::erc8004_request_hash_for(fulfillment_uid, &demand.data)
+    }
+
+    /// Ask the demand's validator to validate `fulfillment_uid` on behalf of
+    /// `agent_id`. Must be sent by the agent's owner on a real registry.
+    pub async fn request_validation(
+        &self,
+        demand: &ERC8004ArbiterContract::DemandData,
+        fulfillment_uid: FixedBytes<32>,
+        agent_id: U256,
+        request_uri: String,
+    ) -> eyre::Result<TransactionReceipt> {
+        let registry =
+            IValidationRegistry::new(demand.validationRegistry, &*self.module.wallet_provider);
+        let receipt = registry
+            .validationRequest(
+                demand.validatorAddress,
+                agent_id,
+                request_uri,
+                self.request_hash(fulfillment_uid, demand),
+            )
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+        Ok(receipt)
+    }
+
+    /// Submit a validator response for `request_hash`. Must be sent by the
+    /// validator named in the request.
+    pub async fn respond(
+        &self,
+        validation_registry: Address,
+        request_hash: FixedBytes<32>,
+        response: u8,
+        response_uri: String,
+        response_hash: FixedBytes<32>,
+        tag: String,
+    ) -> eyre::Result<TransactionReceipt> {
+        let registry = IValidationRegistry::new(validation_registry, &*self.module.wallet_provider);
+        let receipt = registry
+            .validationResponse(request_hash, response, response_uri, response_hash, tag)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+        Ok(receipt)
+    }
+
+    /// Read the registry's validation status for `fulfillment_uid`.
+    pub async fn validation_status(
+        &self,
+        demand: &ERC8004ArbiterContract::DemandData,
+        fulfillment_uid: FixedBytes<32>,
+    ) -> eyre::Result<ValidationStatus> {
+        let request_hash = self.request_hash(fulfillment_uid, demand);
+        let registry =
+            IValidationRegistry::new(demand.validationRegistry, &*self.module.public_provider);
+        let status = registry.getValidationStatus(request_hash).call().await?;
+        Ok(ValidationStatus {
+            request_hash,
+            validator: status.validatorAddress,
+            agent_id: status.agentId,
+            response: status.response,
+            response_hash: status.responseHash,
+            tag: status.tag,
+            last_update: status.lastUpdate,
+        })
+    }
+
+    /// Poll the registry until the demand's validator has responded with at
+    /// least the demand's `minResponse`.
+    ///
+    /// Polls at the client's configured interval. Returns an error if
+    /// `timeout` elapses first.
+    pub async fn wait_for_response(
+        &self,
+        demand: &ERC8004ArbiterContract::DemandData,
+        fulfillment_uid: FixedBytes<32>,
+        timeout: Option<Duration>,
+    ) -> eyre::Result<ValidationStatus> {
+        let min_response = demand.minResponse;
+        let deadline = timeout.map(|t| Instant::now() + t);
+        loop {
+            let status = self.validation_status(demand, fulfillment_uid).await?;
+            if status.validator == demand.validatorAddress && status.response >= min_response {
+                return Ok(status);
+            }
+            if deadline.is_some_and(|d| Instant::now() >= d) {
+                return Err(eyre::eyre!(
+                    "Timed out waiting for validation response >= {min_response} (last: {})",
+                    status.response
+                ));
+            }
+            tokio::time::sleep(self.module.poll_interval).await;
+        }
+    }
+
+    /// Explain why `ERC8004Arbiter.check` would reject `fulfillment`, or
+    /// return `None` if it would pass.
+    pub async fn explain_check(
+        &self,
+        fulfillment: &IEAS::Attestation,
+        demand: &ERC8004ArbiterContract::DemandData,
+        escrow_uid: FixedBytes<32>,
+    ) -> eyre::Result<Option<Erc8004CheckFailure>> {
+        if fulfillment.refUID != escrow_uid {
+            return Ok(Some(Erc8004CheckFailure::FulfillmentMustReferenceEscrow {
+                escrow_uid,
+                ref_uid: fulfillment.refUID,
+            }));
+        }
+        if !(1..=100).contains(&demand.minResponse) {
+            return Ok(Some(Erc8004CheckFailure::InvalidMinResponse(
+                demand.minResponse,
+            )));
+        }
+
+        let status = self.validation_status(demand, fulfillment.uid).await?;
+        Ok(check_status(demand, &status))
+    }
+}
+
+fn check_status(
+    demand: &ERC8004ArbiterContract::DemandData,
+    status: &ValidationStatus,
+) -> Option<Erc8004CheckFailure> {
+    if !status.exists() {
+        Some(Erc8004CheckFailure::ValidationNotFound)
+    } else if status.validator != demand.validatorAddress {
+        Some(Erc8004CheckFailure::ValidatorMismatch {
+            expected: demand.validatorAddress,
+            actual: status.validator,
+        })
+    } else if status.response < demand.minResponse {
+        Some(Erc8004CheckFailure::ResponseBelowMinimum {
+            response: status.response,
+            min_response: demand.minResponse,
+        })
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn demand(min_response: u8) -> ERC8004ArbiterContract::DemandData {
+        ERC8004ArbiterContract::DemandData {
+            validationRegistry: Address::repeat_byte(1),
+            validatorAddress: Address::repeat_byte(2),
+            minResponse: min_response,
+            data: Bytes::new(),
+        }
+    }
+
+    fn status(validator: Address, response: u8) -> ValidationStatus {
+        ValidationStatus {
+            request_hash: FixedBytes::ZERO,
+            validator,
+            agent_id: U256::from(1),
+            response,
+            response_hash: FixedBytes::ZERO,
+            tag: String::new(),
+            last_update: U256::ZERO,
+        }
+    }
+
+    #[test]
+    fn check_status_follows_contract_order() {
+        let d = demand(50);
+
+        assert_eq!(
+            check_status(&d, &status(Address::ZERO, 90)),
+            Some(Erc8004CheckFailure::ValidationNotFound)
+        );
+        assert_eq!(
+            check_status(&d, &status(Address::repeat_byte(3), 90)),
+            Some(Erc8004CheckFailure::ValidatorMismatch {
+                expected: Address::repeat_byte(2),
+                actual: Address::repeat_byte(3),
+            })
+        );
+        assert_eq!(
+            check_status(&d, &status(Address::repeat_byte(2), 49)),
+            Some(Erc8004CheckFailure::ResponseBelowMinimum {
+                response: 49,
+                min_response: 50,
+            })
+        );
+        assert_eq!(check_status(&d, &status(Address::repeat_byte(2), 50)), None);
+    }
+}
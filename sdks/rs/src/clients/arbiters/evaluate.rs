@@ -0,0 +1,503 @@
+//! Off-chain prediction of arbiter outcomes
+//!
+//! Pure arbiters (attestation properties, trivial, intrinsics, references
+//! escrow, and `Any`/`All` composition over them) depend only on the
+//! fulfillment attestation, the escrow UID and the block timestamp, so they
+//! are evaluated locally. Stateful leaves (trusted oracle, ERC-8004,
+//! confirmation and unknown arbiters) are deferred and, through
+//! [`ArbitersModule::evaluate_demand`], resolved with an `eth_call` of the
+//! arbiter's `check`.
+
+use alloy::{
+    eips::BlockNumberOrTag,
+    primitives::{Address, FixedBytes},
+    providers::Provider as _,
+};
+use futures::future::join_all;
+use serde::Serialize;
+
+use super::{ArbitersModule, AttestationPropertyDemand, DecodedDemand};
+use crate::{contracts, impl_from_attestation};
+
+impl_from_attestation!(contracts::BaseArbiter::Attestation);
+
+/// Predicted result of a demand node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Pass,
+    Fail,
+    /// Could not be determined, e.g. a stateful leaf evaluated offline or an
+    /// RPC error.
+    Unknown,
+}
+
+/// How a node's verdict was obtained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvaluationSource {
+    /// Computed from attestation fields, or from the node's children.
+    Local,
+    /// Result of an `eth_call` to the arbiter's `check`.
+    OnChain,
+    /// Depends on contract state and was not evaluated.
+    Deferred,
+}
+
+/// Per-node evaluation trace, mirroring the demand tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DemandTrace {
+    pub arbiter: Address,
+    /// Demand type, using the same names as the JSON `type` tag of
+    /// [`super::DemandNode`].
+    pub kind: &'static str,
+    pub verdict: Verdict,
+    pub source: EvaluationSource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<DemandTrace>,
+}
+
+impl DemandTrace {
+    fn leaf(arbiter: Address, kind: &'static str, verdict: Verdict, reason: Option<&str>) -> Self {
+        Self {
+            arbiter,
+            kind,
+            verdict,
+            source: EvaluationSource::Local,
+            reason: reason.map(str::to_string),
+            children: Vec::new(),
+        }
+    }
+
+    fn check(arbiter: Address, kind: &'static str, passed: bool, failure: &str) -> Self {
+        if passed {
+            Self::leaf(arbiter, kind, Verdict::Pass, None)
+        } else {
+            Self::leaf(arbiter, kind, Verdict::Fail, Some(failure))
+        }
+    }
+
+    fn deferred(arbiter: Address, kind: &'static str) -> Self {
+        Self {
+            source: EvaluationSource::Deferred,
+            ..Self::leaf(
+                arbiter,
+                kind,
+                Verdict::Unknown,
+                Some("depends on contract state"),
+            )
+        }
+    }
+
+    /// Iterate over the deferred leaves, depth-first.
+    pub fn deferred_leaves(&self) -> Vec<&DemandTrace> {
+        let mut out = Vec::new();
+        self.collect_deferred(&mut out);
+        out
+    }
+
+    fn collect_deferred<'t>(&'t self, out: &mut Vec<&'t DemandTrace>) {
+        if self.source == EvaluationSource::Deferred {
+            out.push(self);
+        }
+        for child in &self.children {
+            child.collect_deferred(out);
+        }
+    }
+
+    /// Fill deferred leaves, in depth-first order, and recompute the
+    /// verdicts of the logical nodes above them.
+    fn resolve(&mut self, results: &mut impl Iterator<Item = DemandTrace>) {
+        if self.source == EvaluationSource::Deferred {
+            if let Some(resolved) = results.next() {
+                *self = resolved;
+            }
+            return;
+        }
+        if self.children.is_empty() {
+            return;
+        }
+        for child in &mut self.children {
+            child.resolve(results);
+        }
+        self.verdict = combine(self.kind, &self.children);
+    }
+}
+
+fn combine(kind: &str, children: &[DemandTrace]) -> Verdict {
+    let any = |v: Verdict| children.iter().any(|c| c.verdict == v);
+    match kind {
+        // AnyArbiter passes on the first passing child.
+        "any" if any(Verdict::Pass) => Verdict::Pass,
+        "any" if any(Verdict::Unknown) => Verdict::Unknown,
+        "any" => Verdict::Fail,
+        // AllArbiter fails on the first failing child.
+        _ if any(Verdict::Fail) => Verdict::Fail,
+        _ if any(Verdict::Unknown) => Verdict::Unknown,
+        _ => Verdict::Pass,
+    }
+}
+
+impl DecodedDemand {
+    /// Evaluate this demand against `fulfillment` without any RPC calls.
+    ///
+    /// `now` is the block timestamp used for the intrinsics expiry check.
+    /// Stateful leaves are returned as [`Verdict::Unknown`] with
+    /// [`EvaluationSource::Deferred`].
+    pub fn evaluate_locally(
+        &self,
+        arbiter: Address,
+        fulfillment: &contracts::IEAS::Attestation,
+        escrow_uid: FixedBytes<32>,
+        now: u64,
+    ) -> DemandTrace {
+        let f = fulfillment;
+        match self {
+            DecodedDemand::TrivialArbiter => {
+                DemandTrace::leaf(arbiter, "trivial", Verdict::Pass, None)
+            }
+            DecodedDemand::IntrinsicsArbiter => {
+                let failure = if f.uid == FixedBytes::ZERO {
+                    Some("attestation uid is zero")
+                } else if f.expirationTime != 0 && f.expirationTime <= now {
+                    Some("attestation has expired")
+                } else if f.revocationTime != 0 {
+                    Some("attestation has been revoked")
+                } else {
+                    None
+                };
+                match failure {
+                    Some(reason) => {
+                        DemandTrace::leaf(arbiter, "intrinsics", Verdict::Fail, Some(reason))
+                    }
+                    None => DemandTrace::leaf(arbiter, "intrinsics", Verdict::Pass, None),
+                }
+            }
+            DecodedDemand::ReferencesEscrowArbiter => DemandTrace::check(
+                arbiter,
+                "references_escrow",
+                f.refUID == escrow_uid,
+                "fulfillment does not reference the escrow",
+            ),
+            DecodedDemand::TrustedOracle(_) => DemandTrace::deferred(arbiter, "trusted_oracle"),
+            DecodedDemand::ERC8004Arbiter(_) => DemandTrace::deferred(arbiter, "erc8004"),
+            DecodedDemand::Unknown { .. } => DemandTrace::deferred(arbiter, "unknown"),
+            DecodedDemand::Extension(_) => DemandTrace::deferred(arbiter, "extension"),
+            DecodedDemand::AnyArbiter(d) => {
+                Self::evaluate_logical(arbiter, "any", &d.arbiters, &d.demands, f, escrow_uid, now)
+            }
+            DecodedDemand::AllArbiter(d) => {
+                Self::evaluate_logical(arbiter, "all", &d.arbiters, &d.demands, f, escrow_uid, now)
+            }
+            DecodedDemand::AttesterArbiter(d) => DemandTrace::check(
+                arbiter,
+                "attester",
+                d.is_satisfied_by(f),
+                "attester mismatched",
+            ),
+            DecodedDemand::ExpirationTimeAfterArbiter(d) => DemandTrace::check(
+                arbiter,
+                "expiration_time_after",
+                d.is_satisfied_by(f),
+                "expiration time not after",
+            ),
+            DecodedDemand::ExpirationTimeBeforeArbiter(d) => DemandTrace::check(
+                arbiter,
+                "expiration_time_before",
+                d.is_satisfied_by(f),
+                "expiration time not before",
+            ),
+            DecodedDemand::ExpirationTimeEqualArbiter(d) => DemandTrace::check(
+                arbiter,
+                "expiration_time_equal",
+                d.is_satisfied_by(f),
+                "expiration time not equal",
+            ),
+            DecodedDemand::RecipientArbiter(d) => DemandTrace::check(
+                arbiter,
+                "recipient",
+                d.is_satisfied_by(f),
+                "recipient mismatched",
+            ),
+            DecodedDemand::RefUidArbiter(d) => DemandTrace::check(
+                arbiter,
+                "ref_uid",
+                d.is_satisfied_by(f),
+                "ref uid mismatched",
+            ),
+            DecodedDemand::RevocableArbiter(d) => DemandTrace::check(
+                arbiter,
+                "revocable",
+                d.is_satisfied_by(f),
+                "revocability mismatched",
+            ),
+            DecodedDemand::SchemaArbiter(d) => {
+                DemandTrace::check(arbiter, "schema", d.is_satisfied_by(f), "schema mismatched")
+            }
+            DecodedDemand::TimeAfterArbiter(d) => DemandTrace::check(
+                arbiter,
+                "time_after",
+                d.is_satisfied_by(f),
+                "time not after",
+            ),
+            DecodedDemand::TimeBeforeArbiter(d) => DemandTrace::check(
+                arbiter,
+                "time_before",
+                d.is_satisfied_by(f),
+                "time not before",
+            ),
+            DecodedDemand::TimeEqualArbiter(d) => DemandTrace::check(
+                arbiter,
+                "time_equal",
+                d.is_satisfied_by(f),
+                "time not equal",
+            ),
+            DecodedDemand::UidArbiter(d) => {
+                DemandTrace::check(arbiter, "uid", d.is_satisfied_by(f), "uid mismatched")
+            }
+        }
+    }
+
+    fn evaluate_logical(
+        arbiter: Address,
+        kind: &'static str,
+        arbiters: &[Address],
+        demands: &[DecodedDemand],
+        fulfillment: &contracts::IEAS::Attestation,
+        escrow_uid: FixedBytes<32>,
+        now: u64,
+    ) -> DemandTrace {
+        if arbiters.len() != demands.len() {
+            return DemandTrace::leaf(
+                arbiter,
+                kind,
+                Verdict::Fail,
+                Some("mismatched arbiters and demands"),
+            );
+        }
+        let children: Vec<_> = arbiters
+            .iter()
+            .zip(demands)
+            .map(|(child_arbiter, child)| {
+                child.evaluate_locally(*child_arbiter, fulfillment, escrow_uid, now)
+            })
+            .collect();
+        DemandTrace {
+            verdict: combine(kind, &children),
+            children,
+            ..DemandTrace::leaf(arbiter, kind, Verdict::Unknown, None)
+        }
+    }
+
+    /// Collect deferred leaves with their arbiter addresses, in the same
+    /// depth-first order as [`DemandTrace::deferred_leaves`].
+    fn deferred_leaves(&self, arbiter: Address) -> Vec<(Address, &DecodedDemand)> {
+        match self {
+            DecodedDemand::TrustedOracle(_)
+            | DecodedDemand::ERC8004Arbiter(_)
+            | DecodedDemand::Unknown { .. }
+            | DecodedDemand::Extension(_) => vec![(arbiter, self)],
+            DecodedDemand::AnyArbiter(d) if d.arbiters.len() == d.demands.len() => d
+                .arbiters
+                .iter()
+                .zip(&d.demands)
+                .flat_map(|(a, child)| child.deferred_leaves(*a))
+                .collect(),
+            DecodedDemand::AllArbiter(d) if d.arbiters.len() == d.demands.len() => d
+                .arbiters
+                .iter()
+                .zip(&d.demands)
+                .flat_map(|(a, child)| child.deferred_leaves(*a))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl ArbitersModule {
+    /// Predict whether `fulfillment` satisfies `demand` for `escrow_uid`.
+    ///
+    /// Pure leaves are evaluated locally against the latest block timestamp;
+    /// stateful leaves are checked with an `eth_call` of the arbiter's
+    /// `check`. A revert counts as [`Verdict::Fail`]; an RPC error leaves the
+    /// leaf [`Verdict::Unknown`].
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let demand = arbiters_module.decode_arbiter_demand(arbiter, &demand_bytes)?;
+    /// let trace = arbiters_module.evaluate_demand(arbiter, &demand, &fulfillment, escrow_uid).await?;
+    /// if trace.verdict == Verdict::Fail { /* don't submit */ }
+    /// ```
+    pub async fn evaluate_demand(
+        &self,
+        arbiter: Address,
+        demand: &DecodedDemand,
+        fulfillment: &contracts::IEAS::Attestation,
+        escrow_uid: FixedBytes<32>,
+    ) -> eyre::Result<DemandTrace> {
+        let now = self
+            .public_provider
+            .get_block_by_number(BlockNumberOrTag::Latest)
+            .await?
+            .ok_or_else(|| eyre::eyre!("Latest block not found"))?
+            .header
+            .timestamp;
+
+        let mut trace = demand.evaluate_locally(arbiter, fulfillment, escrow_uid, now);
+        let leaves = demand.deferred_leaves(arbiter);
+        if leaves.is_empty() {
+            return Ok(trace);
+        }
+
+        let checks = leaves.into_iter().zip(trace.deferred_leaves()).map(
+            |((leaf_arbiter, leaf), deferred)| {
+                let kind = deferred.kind;
+                async move {
+                    let demand_bytes = leaf
+                        .encode_with_registry(&self.addresses, &self.demand_codecs)?
+                        .demand;
+                    let contract = contracts::BaseArbiter::new(leaf_arbiter, &self.public_provider);
+                    let result = contract
+                        .check(fulfillment.clone().into(), demand_bytes, escrow_uid)
+                        .call()
+                        .await;
+                    let (verdict, reason) = match result {
+                        Ok(true) => (Verdict::Pass, None),
+                        Ok(false) => (Verdict::Fail, Some("check returned false".to_string())),
+                        Err(err) if err.as_revert_data().is_some() => {
+                            (Verdict::Fail, Some(format!("check reverted: {err}")))
+                        }
+                        Err(err) => (Verdict::Unknown, Some(format!("eth_call failed: {err}"))),
+                    };
+                    Ok::<_, eyre::Error>(DemandTrace {
+                        arbiter: leaf_arbiter,
+                        kind,
+                        verdict,
+                        source: EvaluationSource::OnChain,
+                        reason,
+                        children: Vec::new(),
+                    })
+                }
+            },
+        );
+        let resolved = join_all(checks)
+            .await
+            .into_iter()
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        trace.resolve(&mut resolved.into_iter());
+        Ok(trace)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::Bytes;
+
+    use super::*;
+    use crate::clients::arbiters::DecodedAllArbiterDemandData;
+    use crate::clients::arbiters::DecodedAnyArbiterDemandData;
+    use crate::contracts::arbiters::{
+        TrustedOracleArbiter, attestation_properties::RecipientArbiter,
+    };
+
+    fn fulfillment() -> contracts::IEAS::Attestation {
+        contracts::IEAS::Attestation {
+            uid: FixedBytes::repeat_byte(1),
+            schema: FixedBytes::ZERO,
+            time: 100,
+            expirationTime: 0,
+            revocationTime: 0,
+            refUID: FixedBytes::repeat_byte(9),
+            recipient: Address::repeat_byte(4),
+            attester: Address::ZERO,
+            revocable: true,
+            data: Bytes::new(),
+        }
+    }
+
+    fn recipient(byte: u8) -> DecodedDemand {
+        DecodedDemand::RecipientArbiter(RecipientArbiter::DemandData {
+            recipient: Address::repeat_byte(byte),
+        })
+    }
+
+    fn oracle() -> DecodedDemand {
+        DecodedDemand::TrustedOracle(TrustedOracleArbiter::DemandData {
+            oracle: Address::repeat_byte(7),
+            data: Bytes::new(),
+        })
+    }
+
+    #[test]
+    fn evaluates_pure_tree_locally() {
+        let demand = DecodedDemand::AllArbiter(DecodedAllArbiterDemandData {
+            arbiters: vec![Address::repeat_byte(0x10), Address::repeat_byte(0x11)],
+            demands: vec![DecodedDemand::ReferencesEscrowArbiter, recipient(4)],
+        });
+
+        let trace = demand.evaluate_locally(
+            Address::repeat_byte(0x20),
+            &fulfillment(),
+            FixedBytes::repeat_byte(9),
+            1_000,
+        );
+        assert_eq!(trace.verdict, Verdict::Pass);
+        assert_eq!(trace.children.len(), 2);
+
+        let trace = demand.evaluate_locally(
+            Address::repeat_byte(0x20),
+            &fulfillment(),
+            FixedBytes::repeat_byte(8),
+            1_000,
+        );
+        assert_eq!(trace.verdict, Verdict::Fail);
+        assert_eq!(trace.children[0].verdict, Verdict::Fail);
+    }
+
+    #[test]
+    fn defers_stateful_leaves_and_resolves_them() {
+        let demand = DecodedDemand::AnyArbiter(DecodedAnyArbiterDemandData {
+            arbiters: vec![Address::repeat_byte(0x10), Address::repeat_byte(0x11)],
+            demands: vec![recipient(5), oracle()],
+        });
+
+        let mut trace = demand.evaluate_locally(
+            Address::repeat_byte(0x20),
+            &fulfillment(),
+            FixedBytes::ZERO,
+            1_000,
+        );
+        assert_eq!(trace.verdict, Verdict::Unknown);
+        let deferred = trace.deferred_leaves();
+        assert_eq!(deferred.len(), 1);
+        assert_eq!(deferred[0].kind, "trusted_oracle");
+        assert_eq!(demand.deferred_leaves(Address::repeat_byte(0x20)).len(), 1);
+
+        let resolved = DemandTrace {
+            verdict: Verdict::Pass,
+            source: EvaluationSource::OnChain,
+            reason: None,
+            ..deferred[0].clone()
+        };
+        trace.resolve(&mut std::iter::once(resolved));
+        assert_eq!(trace.verdict, Verdict::Pass);
+        assert_eq!(trace.children[1].source, EvaluationSource::OnChain);
+    }
+
+    #[test]
+    fn intrinsics_checks_expiry_against_now() {
+        let expiring = contracts::IEAS::Attestation {
+            expirationTime: 500,
+            ..fulfillment()
+        };
+        let intrinsics = DecodedDemand::IntrinsicsArbiter;
+
+        let before = intrinsics.evaluate_locally(Address::ZERO, &expiring, FixedBytes::ZERO, 499);
+        let after = intrinsics.evaluate_locally(Address::ZERO, &expiring, FixedBytes::ZERO, 500);
+        assert_eq!(before.verdict, Verdict::Pass);
+        assert_eq!(after.verdict, Verdict::Fail);
+    }
+}
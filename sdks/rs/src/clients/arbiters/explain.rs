@@ -0,0 +1,531 @@
+//! Human-readable explanations and a stable JSON form for decoded demands
+//!
+//! [`DecodedDemand::explain`] renders a demand tree as indented text for
+//! review, e.g.
+//!
+//! ```text
+//! ALL of:
+//! - oracle 0xabc… approves
+//! - recipient is 0xdef…
+//! - attestation expires after 2026-11-01 00:00:00 UTC
+//! ```
+//!
+//! [`DemandNode`] is the JSON representation. Every node carries its arbiter
+//! address and a `type` tag; leaves that the SDK cannot interpret keep their
+//! raw demand bytes so the tree can always be converted back.
+
+use alloy::primitives::{Address, Bytes, FixedBytes};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ArbiterDemandCodecRegistry, DecodedAllArbiterDemandData, DecodedAnyArbiterDemandData,
+    DecodedDemand,
+};
+use crate::{
+    address_index::AddressLabels,
+    contracts::arbiters::{
+        ERC8004Arbiter, TrustedOracleArbiter,
+        attestation_properties::{
+            AttesterArbiter, ExpirationTimeAfterArbiter, ExpirationTimeBeforeArbiter,
+            ExpirationTimeEqualArbiter, RecipientArbiter, RefUidArbiter, RevocableArbiter,
+            SchemaArbiter, TimeAfterArbiter, TimeBeforeArbiter, TimeEqualArbiter, UidArbiter,
+        },
+    },
+};
+
+/// One node of a demand tree in its JSON form.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DemandNode {
+    pub arbiter: Address,
+    /// Label of `arbiter` from the address index, when known. Informational
+    /// only; ignored when converting back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arbiter_name: Option<String>,
+    #[serde(flatten)]
+    pub kind: DemandKind,
+}
+
+/// Arbiter-specific content of a [`DemandNode`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DemandKind {
+    Trivial,
+    Intrinsics,
+    ReferencesEscrow,
+    TrustedOracle {
+        oracle: Address,
+        data: Bytes,
+    },
+    Erc8004 {
+        validation_registry: Address,
+        validator: Address,
+        min_response: u8,
+        data: Bytes,
+    },
+    Any {
+        demands: Vec<DemandNode>,
+    },
+    All {
+        demands: Vec<DemandNode>,
+    },
+    Attester {
+        attester: Address,
+    },
+    ExpirationTimeAfter {
+        expiration_time: u64,
+    },
+    ExpirationTimeBefore {
+        expiration_time: u64,
+    },
+    ExpirationTimeEqual {
+        expiration_time: u64,
+    },
+    Recipient {
+        recipient: Address,
+    },
+    RefUid {
+        ref_uid: FixedBytes<32>,
+    },
+    Revocable {
+        revocable: bool,
+    },
+    Schema {
+        schema: FixedBytes<32>,
+    },
+    TimeAfter {
+        time: u64,
+    },
+    TimeBefore {
+        time: u64,
+    },
+    TimeEqual {
+        time: u64,
+    },
+    Uid {
+        uid: FixedBytes<32>,
+    },
+    /// Arbiter decoded by an extension codec. Only the raw bytes are kept.
+    Extension {
+        type_name: String,
+        data: Bytes,
+    },
+    /// Arbiter the SDK does not know how to decode.
+    Unknown {
+        data: Bytes,
+    },
+}
+
+impl DecodedDemand {
+    /// Convert to the JSON node form. `arbiter` is the address this demand
+    /// was decoded for; nested addresses come from the logical arbiters'
+    /// `arbiters` lists.
+    pub fn to_node(&self, arbiter: Address, labels: Option<&AddressLabels>) -> DemandNode {
+        let children = |arbiters: &[Address], demands: &[DecodedDemand]| {
+            arbiters
+                .iter()
+                .zip(demands)
+                .map(|(arbiter, demand)| demand.to_node(*arbiter, labels))
+                .collect()
+        };
+
+        let kind = match self {
+            DecodedDemand::TrivialArbiter => DemandKind::Trivial,
+            DecodedDemand::IntrinsicsArbiter => DemandKind::Intrinsics,
+            DecodedDemand::ReferencesEscrowArbiter => DemandKind::ReferencesEscrow,
+            DecodedDemand::TrustedOracle(d) => DemandKind::TrustedOracle {
+                oracle: d.oracle,
+                data: d.data.clone(),
+            },
+            DecodedDemand::ERC8004Arbiter(d) => DemandKind::Erc8004 {
+                validation_registry: d.validationRegistry,
+                validator: d.validatorAddress,
+                min_response: d.minResponse,
+                data: d.data.clone(),
+            },
+            DecodedDemand::AnyArbiter(d) => DemandKind::Any {
+                demands: children(&d.arbiters, &d.demands),
+            },
+            DecodedDemand::AllArbiter(d) => DemandKind::All {
+                demands: children(&d.arbiters, &d.demands),
+            },
+            DecodedDemand::AttesterArbiter(d) => DemandKind::Attester {
+                attester: d.attester,
+            },
+            DecodedDemand::ExpirationTimeAfterArbiter(d) => DemandKind::ExpirationTimeAfter {
+                expiration_time: d.expirationTime,
+            },
+            DecodedDemand::ExpirationTimeBeforeArbiter(d) => DemandKind::ExpirationTimeBefore {
+                expiration_time: d.expirationTime,
+            },
+            DecodedDemand::ExpirationTimeEqualArbiter(d) => DemandKind::ExpirationTimeEqual {
+                expiration_time: d.expirationTime,
+            },
+            DecodedDemand::RecipientArbiter(d) => DemandKind::Recipient {
+                recipient: d.recipient,
+            },
+            DecodedDemand::RefUidArbiter(d) => DemandKind::RefUid { ref_uid: d.refUID },
+            DecodedDemand::RevocableArbiter(d) => DemandKind::Revocable {
+                revocable: d.revocable,
+            },
+            DecodedDemand::SchemaArbiter(d) => DemandKind::Schema { schema: d.schema },
+            DecodedDemand::TimeAfterArbiter(d) => DemandKind::TimeAfter { time: d.time },
+            DecodedDemand::TimeBeforeArbiter(d) => DemandKind::TimeBefore { time: d.time },
+            DecodedDemand::TimeEqualArbiter(d) => DemandKind::TimeEqual { time: d.time },
+            DecodedDemand::UidArbiter(d) => DemandKind::Uid { uid: d.uid },
+            DecodedDemand::Unknown { raw_data, .. } => DemandKind::Unknown {
+                data: raw_data.clone(),
+            },
+            DecodedDemand::Extension(extension) => DemandKind::Extension {
+                type_name: extension.type_name.to_string(),
+                data: extension.raw_data.clone(),
+            },
+        };
+
+        DemandNode {
+            arbiter,
+            arbiter_name: labels.and_then(|l| l.get(&arbiter)).map(str::to_string),
+            kind,
+        }
+    }
+
+    /// Render this demand as an indented, human-readable requirement tree.
+    ///
+    /// Known addresses are annotated with their names from `labels`.
+    pub fn explain(&self, arbiter: Address, labels: Option<&AddressLabels>) -> String {
+        self.to_node(arbiter, labels).explain(labels)
+    }
+}
+
+impl DemandNode {
+    /// Convert back to a [`DecodedDemand`].
+    ///
+    /// `Unknown` and `Extension` leaves are re-decoded from their raw bytes
+    /// through `registry`, so extension demands come back typed when their
+    /// codec is registered.
+    pub fn to_decoded(&self, registry: &ArbiterDemandCodecRegistry) -> eyre::Result<DecodedDemand> {
+        let children =
+            |demands: &[DemandNode]| -> eyre::Result<(Vec<Address>, Vec<DecodedDemand>)> {
+                let decoded = demands
+                    .iter()
+                    .map(|node| node.to_decoded(registry))
+                    .collect::<eyre::Result<Vec<_>>>()?;
+                Ok((demands.iter().map(|node| node.arbiter).collect(), decoded))
+            };
+
+        Ok(match &self.kind {
+            DemandKind::Trivial => DecodedDemand::TrivialArbiter,
+            DemandKind::Intrinsics => DecodedDemand::IntrinsicsArbiter,
+            DemandKind::ReferencesEscrow => DecodedDemand::ReferencesEscrowArbiter,
+            DemandKind::TrustedOracle { oracle, data } => {
+                DecodedDemand::TrustedOracle(TrustedOracleArbiter::DemandData {
+                    oracle: *oracle,
+                    data: data.clone(),
+                })
+            }
+            DemandKind::Erc8004 {
+                validation_registry,
+                validator,
+                min_response,
+                data,
+            } => DecodedDemand::ERC8004Arbiter(ERC8004Arbiter::DemandData {
+                validationRegistry: *validation_registry,
+                validatorAddress: *validator,
+                minResponse: *min_response,
+                data: data.clone(),
+            }),
+            DemandKind::Any { demands } => {
+                let (arbiters, demands) = children(demands)?;
+                DecodedDemand::AnyArbiter(DecodedAnyArbiterDemandData { arbiters, demands })
+            }
+            DemandKind::All { demands } => {
+                let (arbiters, demands) = children(demands)?;
+                DecodedDemand::AllArbiter(DecodedAllArbiterDemandData { arbiters, demands })
+            }
+            DemandKind::Attester { attester } => {
+                DecodedDemand::AttesterArbiter(AttesterArbiter::DemandData {
+                    attester: *attester,
+                })
+            }
+            DemandKind::ExpirationTimeAfter { expiration_time } => {
+                DecodedDemand::ExpirationTimeAfterArbiter(ExpirationTimeAfterArbiter::DemandData {
+                    expirationTime: *expiration_time,
+                })
+            }
+            DemandKind::ExpirationTimeBefore { expiration_time } => {
+                DecodedDemand::ExpirationTimeBeforeArbiter(
+                    ExpirationTimeBeforeArbiter::DemandData {
+                        expirationTime: *expiration_time,
+                    },
+                )
+            }
+            DemandKind::ExpirationTimeEqual { expiration_time } => {
+                DecodedDemand::ExpirationTimeEqualArbiter(ExpirationTimeEqualArbiter::DemandData {
+                    expirationTime: *expiration_time,
+                })
+            }
+            DemandKind::Recipient { recipient } => {
+                DecodedDemand::RecipientArbiter(RecipientArbiter::DemandData {
+                    recipient: *recipient,
+                })
+            }
+            DemandKind::RefUid { ref_uid } => {
+                DecodedDemand::RefUidArbiter(RefUidArbiter::DemandData { refUID: *ref_uid })
+            }
+            DemandKind::Revocable { revocable } => {
+                DecodedDemand::RevocableArbiter(RevocableArbiter::DemandData {
+                    revocable: *revocable,
+                })
+            }
+            DemandKind::Schema { schema } => {
+                DecodedDemand::SchemaArbiter(SchemaArbiter::DemandData { schema: *schema })
+            }
+            DemandKind::TimeAfter { time } => {
+                DecodedDemand::TimeAfterArbiter(TimeAfterArbiter::DemandData { time: *time })
+            }
+            DemandKind::TimeBefore { time } => {
+                DecodedDemand::TimeBeforeArbiter(TimeBeforeArbiter::DemandData { time: *time })
+            }
+            DemandKind::TimeEqual { time } => {
+                DecodedDemand::TimeEqualArbiter(TimeEqualArbiter::DemandData { time: *time })
+            }
+            DemandKind::Uid { uid } => {
+                DecodedDemand::UidArbiter(UidArbiter::DemandData { uid: *uid })
+            }
+            DemandKind::Extension { data, .. } | DemandKind::Unknown { data } => {
+                registry.decode(self.arbiter, data)?
+            }
+        })
+    }
+
+    /// Render this node as an indented, human-readable requirement tree.
+    pub fn explain(&self, labels: Option<&AddressLabels>) -> String {
+        let mut out = String::new();
+        self.write_explanation(labels, 0, &mut out);
+        out
+    }
+
+    fn write_explanation(&self, labels: Option<&AddressLabels>, depth: usize, out: &mut String) {
+        let indent = "  ".repeat(depth.saturating_sub(1));
+        let bullet = if depth == 0 { "" } else { "- " };
+        let name = |address: &Address| match labels {
+            Some(labels) => labels.describe(address),
+            None => address.to_string(),
+        };
+
+        let (line, children) = match &self.kind {
+            DemandKind::Any { demands } if demands.is_empty() => {
+                ("ANY of: (nothing; never satisfied)".to_string(), None)
+            }
+            DemandKind::All { demands } if demands.is_empty() => {
+                ("ALL of: (nothing; always satisfied)".to_string(), None)
+            }
+            DemandKind::Any { demands } => ("ANY of:".to_string(), Some(demands)),
+            DemandKind::All { demands } => ("ALL of:".to_string(), Some(demands)),
+            DemandKind::Trivial => ("always satisfied".to_string(), None),
+            DemandKind::Intrinsics => (
+                "fulfillment is a valid, unexpired, unrevoked attestation".to_string(),
+                None,
+            ),
+            DemandKind::ReferencesEscrow => ("fulfillment references the escrow".to_string(), None),
+            DemandKind::TrustedOracle { oracle, data } if data.is_empty() => {
+                (format!("oracle {} approves", name(oracle)), None)
+            }
+            DemandKind::TrustedOracle { oracle, data } => (
+                format!("oracle {} approves (context {data})", name(oracle)),
+                None,
+            ),
+            DemandKind::Erc8004 {
+                validation_registry,
+                validator,
+                min_response,
+                ..
+            } => (
+                format!(
+                    "validator {} responds at least {min_response}/100 in registry {}",
+                    name(validator),
+                    name(validation_registry)
+                ),
+                None,
+            ),
+            DemandKind::Attester { attester } => (format!("attester is {}", name(attester)), None),
+            DemandKind::ExpirationTimeAfter { expiration_time } => (
+                format!(
+                    "attestation expires after {}",
+                    format_timestamp(*expiration_time)
+                ),
+                None,
+            ),
+            DemandKind::ExpirationTimeBefore { expiration_time } => (
+                format!(
+                    "attestation expires before {}",
+                    format_timestamp(*expiration_time)
+                ),
+                None,
+            ),
+            DemandKind::ExpirationTimeEqual { expiration_time } => (
+                format!(
+                    "attestation expires at {}",
+                    format_timestamp(*expiration_time)
+                ),
+                None,
+            ),
+            DemandKind::Recipient { recipient } => {
+                (format!("recipient is {}", name(recipient)), None)
+            }
+            DemandKind::RefUid { ref_uid } => (format!("attestation references {ref_uid}"), None),
+            DemandKind::Revocable { revocable: true } => {
+                ("attestation is revocable".to_string(), None)
+            }
+            DemandKind::Revocable { revocable: false } => {
+                ("attestation is not revocable".to_string(), None)
+            }
+            DemandKind::Schema { schema } => (format!("attestation schema is {schema}"), None),
+            DemandKind::TimeAfter { time } => (
+                format!("attestation made after {}", format_timestamp(*time)),
+                None,
+            ),
+            DemandKind::TimeBefore { time } => (
+                format!("attestation made before {}", format_timestamp(*time)),
+                None,
+            ),
+            DemandKind::TimeEqual { time } => (
+                format!("attestation made at {}", format_timestamp(*time)),
+                None,
+            ),
+            DemandKind::Uid { uid } => (format!("attestation uid is {uid}"), None),
+            DemandKind::Extension { type_name, data } => (
+                format!(
+                    "arbiter {} ({type_name}) accepts demand {data}",
+                    name(&self.arbiter)
+                ),
+                None,
+            ),
+            DemandKind::Unknown { data } => (
+                format!(
+                    "unknown arbiter {} accepts demand {data}",
+                    name(&self.arbiter)
+                ),
+                None,
+            ),
+        };
+
+        out.push_str(&indent);
+        out.push_str(bullet);
+        out.push_str(&line);
+        out.push('\n');
+        for child in children.into_iter().flatten() {
+            child.write_explanation(labels, depth + 1, out);
+        }
+    }
+}
+
+/// Format a unix timestamp as `YYYY-MM-DD HH:MM:SS UTC`.
+fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let secs = timestamp % 86_400;
+
+    // Civil-from-days, after Howard Hinnant's date algorithms.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        secs / 3_600,
+        secs % 3_600 / 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DefaultExtensionConfig;
+
+    fn sample() -> (Address, DecodedDemand) {
+        let addresses = DefaultExtensionConfig::default().arbiters_addresses;
+        let demand = DecodedDemand::AllArbiter(DecodedAllArbiterDemandData {
+            arbiters: vec![
+                addresses.trusted_oracle_arbiter,
+                addresses.recipient_arbiter,
+                addresses.expiration_time_after_arbiter,
+            ],
+            demands: vec![
+                DecodedDemand::TrustedOracle(TrustedOracleArbiter::DemandData {
+                    oracle: Address::repeat_byte(0xab),
+                    data: Bytes::new(),
+                }),
+                DecodedDemand::RecipientArbiter(RecipientArbiter::DemandData {
+                    recipient: Address::repeat_byte(0xde),
+                }),
+                DecodedDemand::ExpirationTimeAfterArbiter(ExpirationTimeAfterArbiter::DemandData {
+                    expirationTime: 1_793_491_200,
+                }),
+            ],
+        });
+        (addresses.all_arbiter, demand)
+    }
+
+    #[test]
+    fn explains_nested_demand() {
+        let (arbiter, demand) = sample();
+        let text = demand.explain(arbiter, None);
+
+        assert_eq!(
+            text,
+            format!(
+                "ALL of:\n- oracle {} approves\n- recipient is {}\n- attestation expires after 2026-11-01 00:00:00 UTC\n",
+                Address::repeat_byte(0xab),
+                Address::repeat_byte(0xde),
+            )
+        );
+    }
+
+    #[test]
+    fn labels_known_addresses() {
+        let config = DefaultExtensionConfig::default();
+        let labels = AddressLabels::new(&config);
+        let (arbiter, demand) = sample();
+        let node = demand.to_node(arbiter, Some(&labels));
+
+        assert_eq!(node.arbiter_name.as_deref(), Some("all_arbiter"));
+        let DemandKind::All { demands } = &node.kind else {
+            panic!("expected All node");
+        };
+        assert_eq!(
+            demands[0].arbiter_name.as_deref(),
+            Some("trusted_oracle_arbiter")
+        );
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let (arbiter, demand) = sample();
+        let node = demand.to_node(arbiter, None);
+        let json = serde_json::to_value(&node).unwrap();
+
+        assert_eq!(json["type"], "all");
+        assert_eq!(json["demands"][1]["type"], "recipient");
+        assert_eq!(json["demands"][2]["expiration_time"], 1_793_491_200u64);
+
+        let parsed: DemandNode = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, node);
+        let decoded = parsed
+            .to_decoded(&ArbiterDemandCodecRegistry::new())
+            .unwrap();
+        assert_eq!(decoded.to_node(arbiter, None), node);
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00:00 UTC");
+        assert_eq!(format_timestamp(1_793_534_461), "2026-11-01 12:01:01 UTC");
+    }
+}
@@ -15,6 +15,10 @@ use crate::{
     impl_abi_conversions, impl_from_attestation,
 };
 
+/// Maximum number of child arbiters `AllArbiter` and `AnyArbiter` accept in
+/// one demand, mirroring the contracts' `MAX_ARBITERS`.
+pub const MAX_ARBITERS: usize = 50;
+
 // Implement ABI conversions for logical arbiters
 impl_abi_conversions!(AllArbiterContract::DemandData);
 impl_abi_conversions!(AnyArbiterContract::DemandData);
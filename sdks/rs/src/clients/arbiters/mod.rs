@@ -3,7 +3,7 @@ use crate::{
     contracts,
     extensions::{AlkahestExtension, ContractModule},
     impl_abi_conversions, impl_from_attestation,
-    types::{SharedPublicProvider, SharedWalletProvider},
+    types::{ArbiterData, SharedPublicProvider, SharedWalletProvider},
 };
 
 // Implement ABI conversions for core arbiter DemandData types
@@ -15,34 +15,85 @@ impl_from_attestation!(contracts::arbiters::TrivialArbiter::Attestation);
 impl_from_attestation!(contracts::arbiters::ReferencesEscrowArbiter::Attestation);
 impl_from_attestation!(contracts::arbiters::TrustedOracleArbiter::Attestation);
 impl_from_attestation!(contracts::arbiters::IntrinsicsArbiter::Attestation);
+impl_from_attestation!(contracts::arbiters::ERC8004Arbiter::Attestation);
 use alloy::{
     primitives::{Address, Bytes, FixedBytes, keccak256},
     signers::local::PrivateKeySigner,
     sol_types::SolValue as _,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 mod attestation_properties;
 mod codec;
 mod confirmation;
+mod decision_audit;
+mod demand;
+mod deploy;
+mod erc8004;
+mod evaluate;
+mod explain;
 mod logical;
+mod oracle_metrics;
+mod quorum;
 mod trusted_oracle;
 
-pub use codec::{ArbiterDemandCodec, ArbiterDemandCodecRegistry, DecodedExtensionDemand};
+pub use alkahest_rs_derive::ArbiterDemand;
+pub use attestation_properties::{
+    AttestationProperties, AttestationPropertyDemand, AttesterArbiter, ExpirationTimeAfterArbiter,
+    ExpirationTimeBeforeArbiter, ExpirationTimeEqualArbiter, RecipientArbiter, RefUidArbiter,
+    RevocableArbiter, SchemaArbiter, TimeAfterArbiter, TimeBeforeArbiter, TimeEqualArbiter,
+    UidArbiter,
+};
+pub use codec::{
+    ArbiterDemandCodec, ArbiterDemandCodecRegistry, ArbiterDemandData, ArbiterDemandDataCodec,
+    DecodedCompositeDemand, DecodedExtensionDemand,
+};
+
+pub use demand::Demand;
+pub use deploy::{ContractArtifact, DeployedArbiter};
+pub use erc8004::{Erc8004, Erc8004CheckFailure, IValidationRegistry, ValidationStatus};
+pub use evaluate::{DemandTrace, EvaluationSource, Verdict};
+pub use explain::{DemandKind, DemandNode};
+
+pub use decision_audit::{
+    DECISION_RECORD_DOMAIN_NAME, DECISION_RECORD_DOMAIN_VERSION, DecisionAuditLog, DecisionRecord,
+    OracleDecisionRecord, SignedDecisionRecord, read_decision_records_jsonl,
+    verify_decision_record,
+};
 
 // Re-export confirmation types
-pub use confirmation::ConfirmationArbiterType;
+pub use confirmation::{
+    ConfirmationArbiterType,
+    client::{
+        ConfirmationArbiterClient, ConfirmationCapabilityError, ConfirmationMade,
+        ConfirmationRequested,
+    },
+    inbox::{ConfirmationInbox, ConfirmationStream, InboxOutcome, PendingConfirmation},
+};
 
 // Re-export logical APIs
 pub use logical::{
     AllArbiter, AnyArbiter, DecodedAllArbiterDemandData, DecodedAnyArbiterDemandData, Logical,
+    MAX_ARBITERS,
+};
+
+// Re-export oracle runtime metrics
+pub use oracle_metrics::{
+    MetricsServer, NoopOracleMetrics, OracleMetrics, PrometheusOracleMetrics,
 };
 
+// Re-export oracle quorum helpers
+pub use quorum::{OracleQuorum, Quorum, QuorumOutcome, QuorumProgress};
+
 // Re-export trusted oracle module (with backwards-compatible aliases)
 pub use trusted_oracle::{
-    ArbitrateManyResult, ArbitrationMode, AttestationWithDemand, Decision, OracleAddresses,
-    OracleModule, TrustedOracle, TrustedOracleAddresses, TrustedOracleModule,
+    ArbitrateManyResult, ArbitrationMode, ArbitrationRequest, AttestationWithDemand, Decision,
+    DecisionRevision, FulfillmentWithArbitration, OracleAddresses, OracleModule, PastDecision,
+    RevisionMode, RevisionReport, SubscriptionHandle, TrustedOracle, TrustedOracleAddresses,
+    TrustedOracleModule, decision_key,
 };
+pub(crate) use trusted_oracle::{BoxedLogStream, open_log_stream};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArbitersAddresses {
@@ -73,6 +124,11 @@ pub struct ArbitersAddresses {
     pub exclusive_unrevocable_confirmation_arbiter: Address,
     pub nonexclusive_revocable_confirmation_arbiter: Address,
     pub nonexclusive_unrevocable_confirmation_arbiter: Address,
+    /// Custom arbiters by name, recorded by
+    /// [`ArbitersModule::register_arbiter`] and
+    /// [`ArbitersModule::deploy_arbiter`]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom_arbiters: BTreeMap<String, Address>,
 }
 
 #[derive(Clone)]
@@ -373,6 +429,42 @@ impl ArbitersModule {
         trusted_oracle::TrustedOracle::new(self)
     }
 
+    /// Encode a [`Demand`] tree using this module's arbiter addresses
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let item = arbiters_module.encode_demand(&Demand::all([
+    ///     Demand::trusted_oracle(oracle, Bytes::new()),
+    ///     Demand::recipient(bob),
+    /// ]));
+    /// ```
+    pub fn encode_demand(&self, demand: &Demand) -> ArbiterData {
+        demand.encode(&self.addresses)
+    }
+
+    /// Access k-of-n oracle quorum helpers
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let quorum = OracleQuorum::new([oracle_a, oracle_b, oracle_c], 2)?;
+    /// let demand = arbiters_module.quorum().demand(&quorum)?;
+    /// let progress = arbiters_module.quorum().wait_for_quorum(&quorum, fulfillment, None, |_| {}).await?;
+    /// ```
+    pub fn quorum(&self) -> quorum::Quorum<'_> {
+        quorum::Quorum::new(self)
+    }
+
+    /// Access ERC-8004 validation registry helpers
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let demand = arbiters_module.erc8004().demand(registry, validator, 80, Bytes::new())?;
+    /// let status = arbiters_module.erc8004().wait_for_response(&demand, fulfillment_uid, None).await?;
+    /// ```
+    pub fn erc8004(&self) -> erc8004::Erc8004<'_> {
+        erc8004::Erc8004::new(self)
+    }
+
     pub fn encode_erc8004_demand(
         demand: &contracts::arbiters::ERC8004Arbiter::DemandData,
     ) -> Bytes {
@@ -446,6 +538,27 @@ impl DecodedDemand {
     {
         Self::Extension(DecodedExtensionDemand::new(arbiter, raw_data, data))
     }
+
+    /// Collect every `TrustedOracleArbiter` leaf in this demand tree, in
+    /// depth-first order, descending through `AnyArbiter` and `AllArbiter`.
+    pub fn trusted_oracle_leaves(
+        &self,
+    ) -> Vec<&contracts::arbiters::TrustedOracleArbiter::DemandData> {
+        match self {
+            DecodedDemand::TrustedOracle(demand) => vec![demand],
+            DecodedDemand::AnyArbiter(any) => any
+                .demands
+                .iter()
+                .flat_map(DecodedDemand::trusted_oracle_leaves)
+                .collect(),
+            DecodedDemand::AllArbiter(all) => all
+                .demands
+                .iter()
+                .flat_map(DecodedDemand::trusted_oracle_leaves)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 impl ArbitersModule {
@@ -469,13 +582,15 @@ impl ArbitersModule {
         logical::Logical::new(self)
     }
 
-    /// Access attestation properties arbiters API for structured decode functionality
+    /// Access attestation properties arbiters API for demand construction,
+    /// decoding, local evaluation and on-chain checks
     ///
     /// # Example
     /// ```rust,ignore
-    /// let decoded_attester = arbiters_module.attestation_properties().attester().decode(attester_demand_data)?;
-    /// let decoded_recipient = arbiters_module.attestation_properties().recipient().decode(recipient_demand_data)?;
-    /// let decoded_schema = arbiters_module.attestation_properties().schema().decode(schema_demand_data)?;
+    /// let decoded_attester = arbiters_module.attestation_properties().attester().decode(&attester_demand_data)?;
+    /// let demand = arbiters_module.attestation_properties().recipient().demand(bob);
+    /// let ok = arbiters_module.attestation_properties().recipient().evaluate(&demand, &attestation);
+    /// let ok_on_chain = arbiters_module.attestation_properties().recipient().check(&attestation, &demand).await?;
     /// ```
     pub fn attestation_properties(&self) -> attestation_properties::AttestationProperties<'_> {
         attestation_properties::AttestationProperties::new(self)
@@ -541,6 +656,51 @@ mod tests {
         assert_ne!(hash, FixedBytes::<32>::default());
     }
 
+    #[test]
+    fn trusted_oracle_leaves_walks_logical_arbiters() {
+        let addresses = ArbitersAddresses::default();
+        let oracle_a = address!("0x1111111111111111111111111111111111111111");
+        let oracle_b = address!("0x2222222222222222222222222222222222222222");
+        let leaf = |oracle, data: &'static [u8]| -> Bytes {
+            contracts::arbiters::TrustedOracleArbiter::DemandData {
+                oracle,
+                data: Bytes::from_static(data),
+            }
+            .abi_encode()
+            .into()
+        };
+        let any_demand = contracts::arbiters::logical::AnyArbiter::DemandData {
+            arbiters: vec![addresses.trusted_oracle_arbiter, addresses.trivial_arbiter],
+            demands: vec![leaf(oracle_b, &[0x02]), Bytes::new()],
+        };
+        let all_demand = contracts::arbiters::logical::AllArbiter::DemandData {
+            arbiters: vec![addresses.trusted_oracle_arbiter, addresses.any_arbiter],
+            demands: vec![leaf(oracle_a, &[0x01]), any_demand.abi_encode().into()],
+        };
+
+        let decoded = default_demand_codecs(&addresses)
+            .decode(addresses.all_arbiter, &all_demand.abi_encode().into())
+            .unwrap();
+        let leaves: Vec<_> = decoded
+            .trusted_oracle_leaves()
+            .into_iter()
+            .map(|leaf| (leaf.oracle, leaf.data.clone()))
+            .collect();
+
+        assert_eq!(
+            leaves,
+            vec![
+                (oracle_a, Bytes::from_static(&[0x01])),
+                (oracle_b, Bytes::from_static(&[0x02])),
+            ]
+        );
+        assert!(
+            DecodedDemand::TrivialArbiter
+                .trusted_oracle_leaves()
+                .is_empty()
+        );
+    }
+
     #[test]
     fn demand_codec_registry_decodes_extensions_inside_logical_arbiters() {
         let addresses = ArbitersAddresses::default();
@@ -0,0 +1,372 @@
+//! Trusted oracle runtime metrics
+//!
+//! The oracle runtime reports what it does through the [`OracleMetrics`]
+//! trait. [`PrometheusOracleMetrics`] is a built-in implementation that keeps
+//! the values in atomics, renders them in the Prometheus text exposition
+//! format, and can serve them over a local HTTP listener.
+
+use std::{
+    net::SocketAddr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use alloy::primitives::U256;
+use tokio::{
+    io::{AsyncReadExt as _, AsyncWriteExt as _},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+use tokio_util::sync::CancellationToken;
+
+/// Sink for trusted oracle runtime events.
+///
+/// All methods default to no-ops so implementations only override what they
+/// record. Methods are called inline on the arbitration path and should not
+/// block.
+pub trait OracleMetrics: Send + Sync {
+    /// An `ArbitrationRequested` event addressed to this oracle was picked up.
+    fn request_seen(&self) {}
+    /// A request entered the processing queue.
+    fn request_queued(&self) {}
+    /// A request left the processing queue, whatever its outcome.
+    fn request_dequeued(&self) {}
+    /// A decision was submitted and mined.
+    fn decision_made(&self, _decision: bool) {}
+    /// Submitting a decision failed.
+    fn arbitration_failed(&self) {}
+    /// The runtime processed a log from `block`.
+    fn block_processed(&self, _block: u64) {}
+    /// Latest observed balance of the oracle wallet, in wei.
+    fn wallet_balance(&self, _balance: U256) {}
+}
+
+/// Metrics sink that discards everything. Used when no sink is configured.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopOracleMetrics;
+
+impl OracleMetrics for NoopOracleMetrics {}
+
+/// Marks one request as queued for as long as the guard is alive.
+pub(crate) struct QueuedRequest<'a> {
+    metrics: &'a dyn OracleMetrics,
+}
+
+impl<'a> QueuedRequest<'a> {
+    pub(crate) fn new(metrics: &'a dyn OracleMetrics) -> Self {
+        metrics.request_queued();
+        Self { metrics }
+    }
+}
+
+impl Drop for QueuedRequest<'_> {
+    fn drop(&mut self) {
+        self.metrics.request_dequeued();
+    }
+}
+
+/// Built-in [`OracleMetrics`] implementation with a Prometheus text exporter.
+///
+/// # Example
+/// ```rust,ignore
+/// let metrics = Arc::new(PrometheusOracleMetrics::new());
+/// let oracle = client.oracle().clone().with_metrics(metrics.clone());
+/// let server = metrics.serve("127.0.0.1:9464".parse()?).await?;
+/// // curl http://127.0.0.1:9464/metrics
+/// ```
+#[derive(Debug, Default)]
+pub struct PrometheusOracleMetrics {
+    requests_seen: AtomicU64,
+    decisions_true: AtomicU64,
+    decisions_false: AtomicU64,
+    failures: AtomicU64,
+    queue_depth: AtomicU64,
+    last_block: AtomicU64,
+    wallet_balance: Mutex<U256>,
+}
+
+impl PrometheusOracleMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn requests_seen(&self) -> u64 {
+        self.requests_seen.load(Ordering::Relaxed)
+    }
+
+    pub fn decisions(&self, decision: bool) -> u64 {
+        if decision {
+            self.decisions_true.load(Ordering::Relaxed)
+        } else {
+            self.decisions_false.load(Ordering::Relaxed)
+        }
+    }
+
+    pub fn failures(&self) -> u64 {
+        self.failures.load(Ordering::Relaxed)
+    }
+
+    pub fn queue_depth(&self) -> u64 {
+        self.queue_depth.load(Ordering::Relaxed)
+    }
+
+    pub fn last_block(&self) -> u64 {
+        self.last_block.load(Ordering::Relaxed)
+    }
+
+    pub fn balance(&self) -> U256 {
+        *self.wallet_balance.lock().unwrap()
+    }
+
+    /// Render all metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(&str, String)]| {
+            out.push_str(&format!("# HELP {name} {help}\n# TYPE {name} {kind}\n"));
+            for (labels, value) in samples {
+                out.push_str(&format!("{name}{labels} {value}\n"));
+            }
+        };
+
+        metric(
+            "alkahest_oracle_requests_seen_total",
+            "counter",
+            "Arbitration requests picked up by the oracle.",
+            &[("", self.requests_seen().to_string())],
+        );
+        metric(
+            "alkahest_oracle_decisions_total",
+            "counter",
+            "Decisions submitted by the oracle.",
+            &[
+                ("{decision=\"true\"}", self.decisions(true).to_string()),
+                ("{decision=\"false\"}", self.decisions(false).to_string()),
+            ],
+        );
+        metric(
+            "alkahest_oracle_failures_total",
+            "counter",
+            "Decisions that failed to submit.",
+            &[("", self.failures().to_string())],
+        );
+        metric(
+            "alkahest_oracle_queue_depth",
+            "gauge",
+            "Arbitration requests currently being processed.",
+            &[("", self.queue_depth().to_string())],
+        );
+        metric(
+            "alkahest_oracle_last_block",
+            "gauge",
+            "Block number of the last processed log.",
+            &[("", self.last_block().to_string())],
+        );
+        metric(
+            "alkahest_oracle_wallet_balance_wei",
+            "gauge",
+            "Oracle wallet balance in wei.",
+            &[("", self.balance().to_string())],
+        );
+
+        out
+    }
+
+    /// Serve `/metrics` (Prometheus text) and `/health` on `addr`.
+    ///
+    /// Bind to port 0 to pick a free port; the bound address is available
+    /// from [`MetricsServer::local_addr`].
+    pub async fn serve(self: Arc<Self>, addr: SocketAddr) -> eyre::Result<MetricsServer> {
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        let cancel = CancellationToken::new();
+
+        let task_cancel = cancel.clone();
+        let handle = tokio::spawn(async move {
+            loop {
+                let stream = tokio::select! {
+                    _ = task_cancel.cancelled() => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(err) => {
+                            tracing::error!("Metrics listener accept failed: {}", err);
+                            continue;
+                        }
+                    },
+                };
+                let metrics = self.clone();
+                tokio::spawn(async move {
+                    if let Err(err) = serve_connection(stream, &metrics).await {
+                        tracing::debug!("Metrics connection error: {}", err);
+                    }
+                });
+            }
+        });
+
+        Ok(MetricsServer {
+            local_addr,
+            cancel,
+            handle,
+        })
+    }
+}
+
+impl OracleMetrics for PrometheusOracleMetrics {
+    fn request_seen(&self) {
+        self.requests_seen.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn request_queued(&self) {
+        self.queue_depth.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn request_dequeued(&self) {
+        self.queue_depth.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    fn decision_made(&self, decision: bool) {
+        let counter = if decision {
+            &self.decisions_true
+        } else {
+            &self.decisions_false
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn arbitration_failed(&self) {
+        self.failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn block_processed(&self, block: u64) {
+        self.last_block.fetch_max(block, Ordering::Relaxed);
+    }
+
+    fn wallet_balance(&self, balance: U256) {
+        *self.wallet_balance.lock().unwrap() = balance;
+    }
+}
+
+async fn serve_connection(
+    mut stream: TcpStream,
+    metrics: &PrometheusOracleMetrics,
+) -> eyre::Result<()> {
+    // Only the request line matters; read until the end of the headers.
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < 8192 {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut parts = request.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => (
+            "200 OK",
+            "text/plain; version=0.0.4; charset=utf-8",
+            metrics.render(),
+        ),
+        (Some("GET"), Some("/health")) => ("200 OK", "text/plain", "ok\n".to_string()),
+        _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Handle to a running metrics HTTP listener.
+pub struct MetricsServer {
+    local_addr: SocketAddr,
+    cancel: CancellationToken,
+    handle: JoinHandle<()>,
+}
+
+impl MetricsServer {
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Stop accepting connections and wait for the listener to exit.
+    pub async fn shutdown(self) -> eyre::Result<()> {
+        self.cancel.cancel();
+        self.handle.await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn get(addr: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[test]
+    fn records_and_renders_metrics() {
+        let metrics = PrometheusOracleMetrics::new();
+        metrics.request_seen();
+        metrics.request_seen();
+        metrics.decision_made(true);
+        metrics.decision_made(false);
+        metrics.decision_made(true);
+        metrics.arbitration_failed();
+        metrics.block_processed(42);
+        metrics.block_processed(7);
+        metrics.wallet_balance(U256::from(1_000_000_000_000_000_000u128));
+        {
+            let _queued = QueuedRequest::new(&metrics);
+            assert_eq!(metrics.queue_depth(), 1);
+        }
+        assert_eq!(metrics.queue_depth(), 0);
+
+        let text = metrics.render();
+        assert!(text.contains("alkahest_oracle_requests_seen_total 2\n"));
+        assert!(text.contains("alkahest_oracle_decisions_total{decision=\"true\"} 2\n"));
+        assert!(text.contains("alkahest_oracle_decisions_total{decision=\"false\"} 1\n"));
+        assert!(text.contains("alkahest_oracle_failures_total 1\n"));
+        assert!(text.contains("alkahest_oracle_last_block 42\n"));
+        assert!(text.contains("alkahest_oracle_wallet_balance_wei 1000000000000000000\n"));
+        assert!(text.contains("# TYPE alkahest_oracle_queue_depth gauge\n"));
+    }
+
+    #[tokio::test]
+    async fn serves_metrics_over_http() {
+        let metrics = Arc::new(PrometheusOracleMetrics::new());
+        metrics.request_seen();
+        let server = metrics
+            .clone()
+            .serve("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+
+        let response = get(server.local_addr(), "/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("alkahest_oracle_requests_seen_total 1\n"));
+
+        assert!(get(server.local_addr(), "/health").await.ends_with("ok\n"));
+        assert!(
+            get(server.local_addr(), "/nope")
+                .await
+                .starts_with("HTTP/1.1 404")
+        );
+
+        server.shutdown().await.unwrap();
+    }
+}
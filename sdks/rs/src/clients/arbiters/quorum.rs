@@ -0,0 +1,443 @@
+//! Multi-oracle quorum helpers
+//!
+//! A k-of-n oracle quorum is composed from existing arbiters: each oracle is a
+//! `TrustedOracleArbiter` leaf, and the leaves are combined with `AllArbiter`
+//! and `AnyArbiter`. There is no on-chain threshold arbiter, so a strict
+//! k-of-n quorum (1 < k < n) is encoded as an `AnyArbiter` over one
+//! `AllArbiter` per k-sized subset of the oracles.
+
+use std::collections::HashSet;
+
+use alloy::{
+    primitives::{Address, Bytes, FixedBytes},
+    providers::Provider as _,
+    rpc::types::Filter,
+    sol_types::SolEvent as _,
+};
+use futures::StreamExt as _;
+use itertools::Itertools as _;
+
+use super::{
+    ArbitersAddresses, ArbitersModule, Demand, MAX_ARBITERS, open_log_stream,
+    trusted_oracle::decision_key,
+};
+use crate::{contracts::arbiters::TrustedOracleArbiter, types::ArbiterData};
+
+/// Builder for a k-of-n trusted oracle quorum demand.
+#[derive(Debug, Clone)]
+pub struct OracleQuorum {
+    oracles: Vec<Address>,
+    threshold: usize,
+    data: Bytes,
+}
+
+impl OracleQuorum {
+    /// Create a quorum requiring `threshold` of `oracles` to approve.
+    ///
+    /// Fails if the oracle list is empty, contains duplicates, or the
+    /// threshold is outside `1..=oracles.len()`.
+    pub fn new(oracles: impl IntoIterator<Item = Address>, threshold: usize) -> eyre::Result<Self> {
+        let oracles: Vec<Address> = oracles.into_iter().collect();
+        if oracles.is_empty() {
+            return Err(eyre::eyre!("Oracle quorum requires at least one oracle"));
+        }
+        let mut seen = HashSet::new();
+        if let Some(dup) = oracles.iter().find(|o| !seen.insert(**o)) {
+            return Err(eyre::eyre!("Duplicate oracle in quorum: {}", dup));
+        }
+        if threshold == 0 || threshold > oracles.len() {
+            return Err(eyre::eyre!(
+                "Quorum threshold must be between 1 and {}, got {}",
+                oracles.len(),
+                threshold
+            ));
+        }
+
+        Ok(Self {
+            oracles,
+            threshold,
+            data: Bytes::new(),
+        })
+    }
+
+    /// Set the demand data passed to every oracle leaf.
+    pub fn with_data(mut self, data: Bytes) -> Self {
+        self.data = data;
+        self
+    }
+
+    pub fn oracles(&self) -> &[Address] {
+        &self.oracles
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn data(&self) -> &Bytes {
+        &self.data
+    }
+
+    /// Decision key each oracle's `ArbitrationMade` event carries for `fulfillment_uid`.
+    pub fn decision_key(&self, fulfillment_uid: FixedBytes<32>) -> FixedBytes<32> {
+        decision_key(fulfillment_uid, &self.data)
+    }
+
+    /// Express the quorum as a [`Demand`] tree.
+    ///
+    /// - n-of-n becomes an `AllArbiter` over every oracle leaf
+    /// - 1-of-n becomes an `AnyArbiter` over every oracle leaf
+    /// - k-of-n becomes an `AnyArbiter` over one `AllArbiter` per k-subset
+    ///
+    /// A single-oracle quorum is just the `TrustedOracleArbiter` leaf.
+    ///
+    /// Fails if any logical arbiter in the tree would get more than
+    /// [`MAX_ARBITERS`] children, which the contracts reject.
+    pub fn to_demand(&self) -> eyre::Result<Demand> {
+        let leaf = |oracle: Address| Demand::trusted_oracle(oracle, self.data.clone());
+
+        let n = self.oracles.len();
+        if n > MAX_ARBITERS {
+            return Err(eyre::eyre!(
+                "Quorum has {} oracles (max {})",
+                n,
+                MAX_ARBITERS
+            ));
+        }
+        if n == 1 {
+            return Ok(leaf(self.oracles[0]));
+        }
+        if self.threshold == n {
+            return Ok(Demand::all(self.oracles.iter().copied().map(leaf)));
+        }
+
+        let combinations = binomial(n, self.threshold);
+        if combinations > MAX_ARBITERS {
+            return Err(eyre::eyre!(
+                "{}-of-{} quorum expands to {} oracle subsets (max {})",
+                self.threshold,
+                n,
+                combinations,
+                MAX_ARBITERS
+            ));
+        }
+
+        if self.threshold == 1 {
+            Ok(Demand::any(self.oracles.iter().copied().map(leaf)))
+        } else {
+            Ok(Demand::any(
+                self.oracles
+                    .iter()
+                    .copied()
+                    .combinations(self.threshold)
+                    .map(|subset| Demand::all(subset.into_iter().map(leaf))),
+            ))
+        }
+    }
+
+    /// Encode the quorum as an arbiter/demand pair, see [`OracleQuorum::to_demand`].
+    pub fn build(&self, addresses: &ArbitersAddresses) -> eyre::Result<ArbiterData> {
+        Ok(self.to_demand()?.encode(addresses))
+    }
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    let k = k.min(n - k);
+    let mut result: usize = 1;
+    for i in 0..k {
+        result = result.saturating_mul(n - i) / (i + 1);
+    }
+    result
+}
+
+/// Final outcome of a quorum once it can no longer change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuorumOutcome {
+    /// At least `threshold` oracles approved the fulfillment.
+    Approved,
+    /// Too many oracles rejected for the threshold to be reachable.
+    Rejected,
+}
+
+/// Latest decision seen from each oracle in a quorum.
+#[derive(Debug, Clone)]
+pub struct QuorumProgress {
+    pub threshold: usize,
+    /// Oracles in quorum order, with `None` until the oracle has decided.
+    pub decisions: Vec<(Address, Option<bool>)>,
+}
+
+impl QuorumProgress {
+    pub fn new(quorum: &OracleQuorum) -> Self {
+        Self {
+            threshold: quorum.threshold,
+            decisions: quorum.oracles.iter().map(|o| (*o, None)).collect(),
+        }
+    }
+
+    /// Record a decision from `oracle`, replacing any earlier one.
+    ///
+    /// Returns `true` if the oracle is part of the quorum and its decision changed.
+    pub fn record(&mut self, oracle: Address, decision: bool) -> bool {
+        match self.decisions.iter_mut().find(|(o, _)| *o == oracle) {
+            Some((_, slot)) if *slot != Some(decision) => {
+                *slot = Some(decision);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn approvals(&self) -> usize {
+        self.decisions
+            .iter()
+            .filter(|(_, d)| *d == Some(true))
+            .count()
+    }
+
+    pub fn rejections(&self) -> usize {
+        self.decisions
+            .iter()
+            .filter(|(_, d)| *d == Some(false))
+            .count()
+    }
+
+    pub fn pending(&self) -> usize {
+        self.decisions.iter().filter(|(_, d)| d.is_none()).count()
+    }
+
+    /// The quorum outcome, or `None` while it is still undecided.
+    pub fn outcome(&self) -> Option<QuorumOutcome> {
+        if self.approvals() >= self.threshold {
+            Some(QuorumOutcome::Approved)
+        } else if self.approvals() + self.pending() < self.threshold {
+            Some(QuorumOutcome::Rejected)
+        } else {
+            None
+        }
+    }
+}
+
+/// Oracle quorum API accessor (accessed via `arbiters.quorum()`)
+pub struct Quorum<'a> {
+    module: &'a ArbitersModule,
+}
+
+impl<'a> Quorum<'a> {
+    pub fn new(module: &'a ArbitersModule) -> Self {
+        Self { module }
+    }
+
+    /// Encode `quorum` using this module's arbiter addresses.
+    pub fn demand(&self, quorum: &OracleQuorum) -> eyre::Result<ArbiterData> {
+        quorum.build(&self.module.addresses)
+    }
+
+    /// Wait until the quorum for `fulfillment_uid` is approved or rejected.
+    ///
+    /// Tracks `ArbitrationMade` events from the quorum's oracles, calling
+    /// `on_progress` every time an oracle's decision changes. Decisions made
+    /// before the call (from `from_block`, default genesis) are included.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let progress = arbiters.quorum().wait_for_quorum(&quorum, fulfillment_uid, None, |p| {
+    ///     println!("{}/{} approvals", p.approvals(), p.threshold);
+    /// }).await?;
+    /// assert_eq!(progress.outcome(), Some(QuorumOutcome::Approved));
+    /// ```
+    pub async fn wait_for_quorum(
+        &self,
+        quorum: &OracleQuorum,
+        fulfillment_uid: FixedBytes<32>,
+        from_block: Option<u64>,
+        mut on_progress: impl FnMut(&QuorumProgress),
+    ) -> eyre::Result<QuorumProgress> {
+        // ArbitrationMade event: (bytes32 indexed decisionKey, bytes32 indexed fulfillmentUid, address indexed oracle, bool decision)
+        // topic1 = decisionKey, topic2 = fulfillmentUid, topic3 = oracle
+        let filter = Filter::new()
+            .from_block(from_block.unwrap_or(0))
+            .address(self.module.addresses.trusted_oracle_arbiter)
+            .event_signature(TrustedOracleArbiter::ArbitrationMade::SIGNATURE_HASH)
+            .topic1(quorum.decision_key(fulfillment_uid))
+            .topic2(fulfillment_uid)
+            .topic3(
+                quorum
+                    .oracles
+                    .iter()
+                    .map(|o| o.into_word())
+                    .collect::<Vec<_>>(),
+            );
+
+        // Subscribe before reading history so no decision falls in between.
+        let (mut stream, subscription) = open_log_stream(
+            &self.module.public_provider,
+            &filter,
+            self.module.poll_interval,
+        )
+        .await?;
+
+        let result = async {
+            let mut progress = QuorumProgress::new(quorum);
+            for log in self.module.public_provider.get_logs(&filter).await? {
+                let event = log.log_decode::<TrustedOracleArbiter::ArbitrationMade>()?;
+                if progress.record(event.inner.oracle, event.inner.decision) {
+                    on_progress(&progress);
+                }
+            }
+            if progress.outcome().is_some() {
+                return Ok(progress);
+            }
+
+            while let Some(log) = stream.next().await {
+                let event = log.log_decode::<TrustedOracleArbiter::ArbitrationMade>()?;
+                if progress.record(event.inner.oracle, event.inner.decision) {
+                    on_progress(&progress);
+                    if progress.outcome().is_some() {
+                        return Ok(progress);
+                    }
+                }
+            }
+
+            Err(eyre::eyre!(
+                "Stream ended before the oracle quorum was decided"
+            ))
+        }
+        .await;
+
+        subscription
+            .unsubscribe(&self.module.public_provider)
+            .await?;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::address;
+
+    use super::*;
+    use crate::clients::arbiters::{DecodedDemand, default_demand_codecs};
+
+    const A: Address = address!("0x1111111111111111111111111111111111111111");
+    const B: Address = address!("0x2222222222222222222222222222222222222222");
+    const C: Address = address!("0x3333333333333333333333333333333333333333");
+
+    fn addresses() -> ArbitersAddresses {
+        ArbitersAddresses {
+            trusted_oracle_arbiter: address!("0x00000000000000000000000000000000000000a1"),
+            any_arbiter: address!("0x00000000000000000000000000000000000000a2"),
+            all_arbiter: address!("0x00000000000000000000000000000000000000a3"),
+            ..Default::default()
+        }
+    }
+
+    fn leaf_oracles(demand: &DecodedDemand) -> Vec<Address> {
+        match demand {
+            DecodedDemand::TrustedOracle(d) => vec![d.oracle],
+            DecodedDemand::AllArbiter(d) => d.demands.iter().flat_map(leaf_oracles).collect(),
+            other => panic!("unexpected demand {other:?}"),
+        }
+    }
+
+    #[test]
+    fn two_of_three_expands_to_subsets() {
+        let addresses = addresses();
+        let quorum = OracleQuorum::new([A, B, C], 2).unwrap();
+        let data = quorum.build(&addresses).unwrap();
+        assert_eq!(data.arbiter, addresses.any_arbiter);
+
+        let decoded = default_demand_codecs(&addresses)
+            .decode(data.arbiter, &data.demand)
+            .unwrap();
+        let DecodedDemand::AnyArbiter(any) = decoded else {
+            panic!("expected AnyArbiter");
+        };
+        let subsets: Vec<Vec<Address>> = any.demands.iter().map(leaf_oracles).collect();
+        assert_eq!(subsets, vec![vec![A, B], vec![A, C], vec![B, C]]);
+    }
+
+    #[test]
+    fn unanimous_and_single_quorums() {
+        let addresses = addresses();
+
+        let all = OracleQuorum::new([A, B], 2)
+            .unwrap()
+            .build(&addresses)
+            .unwrap();
+        assert_eq!(all.arbiter, addresses.all_arbiter);
+
+        let any = OracleQuorum::new([A, B], 1)
+            .unwrap()
+            .build(&addresses)
+            .unwrap();
+        assert_eq!(any.arbiter, addresses.any_arbiter);
+
+        let single = OracleQuorum::new([A], 1)
+            .unwrap()
+            .build(&addresses)
+            .unwrap();
+        assert_eq!(single.arbiter, addresses.trusted_oracle_arbiter);
+    }
+
+    #[test]
+    fn rejects_invalid_quorums() {
+        assert!(OracleQuorum::new([], 1).is_err());
+        assert!(OracleQuorum::new([A, B], 0).is_err());
+        assert!(OracleQuorum::new([A, B], 3).is_err());
+        assert!(OracleQuorum::new([A, A], 1).is_err());
+
+        let many = (1u8..=20).map(Address::repeat_byte);
+        let quorum = OracleQuorum::new(many, 10).unwrap();
+        assert!(quorum.build(&addresses()).is_err());
+    }
+
+    #[test]
+    fn rejects_quorums_over_max_arbiters() {
+        // 3-of-8 expands to 56 AllArbiter subsets under one AnyArbiter
+        let eight = OracleQuorum::new((1u8..=8).map(Address::repeat_byte), 3).unwrap();
+        assert!(eight.to_demand().is_err());
+
+        let oracles: Vec<Address> = (1u8..=51).map(Address::repeat_byte).collect();
+        assert!(
+            OracleQuorum::new(oracles.clone(), 1)
+                .unwrap()
+                .to_demand()
+                .is_err()
+        );
+        assert!(
+            OracleQuorum::new(oracles.clone(), 51)
+                .unwrap()
+                .to_demand()
+                .is_err()
+        );
+        assert!(
+            OracleQuorum::new(oracles[..50].to_vec(), 1)
+                .unwrap()
+                .to_demand()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn progress_tracks_outcome() {
+        let quorum = OracleQuorum::new([A, B, C], 2).unwrap();
+        let mut progress = QuorumProgress::new(&quorum);
+        assert_eq!(progress.outcome(), None);
+
+        assert!(progress.record(A, false));
+        assert!(!progress.record(A, false));
+        assert_eq!(progress.outcome(), None);
+
+        assert!(progress.record(B, false));
+        assert_eq!(progress.outcome(), Some(QuorumOutcome::Rejected));
+
+        // Oracles may revise their decisions
+        assert!(progress.record(A, true));
+        assert!(progress.record(C, true));
+        assert_eq!(progress.outcome(), Some(QuorumOutcome::Approved));
+        assert_eq!((progress.approvals(), progress.rejections()), (2, 1));
+
+        assert!(!progress.record(Address::ZERO, true));
+    }
+}
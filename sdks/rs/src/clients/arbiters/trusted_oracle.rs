@@ -1,5 +1,7 @@
 use std::{
+    collections::HashMap,
     pin::Pin,
+    sync::{Arc, Mutex},
     time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -9,6 +11,7 @@ use alloy::{
     primitives::{Address, Bytes, FixedBytes},
     providers::Provider,
     rpc::types::{Filter, Log, TransactionReceipt},
+    signers::local::PrivateKeySigner,
     sol,
     sol_types::SolEvent,
 };
@@ -17,6 +20,10 @@ use tokio::time::Duration;
 use tokio_util::sync::CancellationToken;
 use tracing;
 
+use super::{
+    decision_audit::DecisionAuditLog,
+    oracle_metrics::{NoopOracleMetrics, OracleMetrics, QueuedRequest},
+};
 use crate::{
     addresses::BASE_SEPOLIA_ADDRESSES,
     contracts::{
@@ -33,7 +40,7 @@ use crate::{
 /// Used internally to unify the WS (`SubscriptionStream<Log>`) and HTTP
 /// (`PollerStream<Vec<Log>>` flattened) code paths inside the trusted oracle
 /// listener.
-type BoxedLogStream = Pin<Box<dyn Stream<Item = alloy::rpc::types::Log> + Send>>;
+pub(crate) type BoxedLogStream = Pin<Box<dyn Stream<Item = alloy::rpc::types::Log> + Send>>;
 
 /// Transport-agnostic handle to a long-running event subscription opened by
 /// `arbitrate_many*` methods.
@@ -70,6 +77,15 @@ impl SubscriptionHandle {
     }
 }
 
+/// Key under which `TrustedOracleArbiter` stores a decision:
+/// `keccak256(abi.encodePacked(fulfillmentUid, demand))`.
+pub fn decision_key(fulfillment_uid: FixedBytes<32>, demand: &Bytes) -> FixedBytes<32> {
+    let mut packed = Vec::with_capacity(32 + demand.len());
+    packed.extend_from_slice(fulfillment_uid.as_slice());
+    packed.extend_from_slice(demand);
+    alloy::primitives::keccak256(&packed)
+}
+
 #[derive(Debug, Clone)]
 pub struct TrustedOracleAddresses {
     pub eas: Address,
@@ -85,6 +101,9 @@ pub struct TrustedOracleModule {
     /// for the polling fallback inside ``wait_for_first_log``; ws transports
     /// ignore it.
     poll_interval: std::time::Duration,
+    /// Sink for runtime counters and gauges; a no-op unless set via
+    /// [`TrustedOracleModule::with_metrics`].
+    metrics: Arc<dyn OracleMetrics>,
 
     pub addresses: TrustedOracleAddresses,
 }
@@ -142,10 +161,69 @@ impl AlkahestExtension for TrustedOracleModule {
 
 pub struct Decision {
     pub attestation: IEAS::Attestation,
+    /// Demand bytes the decision was made against (from `ArbitrationRequested`)
+    pub demand: Bytes,
     pub decision: bool,
     pub receipt: TransactionReceipt,
 }
 
+/// Arbitration request sent for one trusted oracle leaf of an escrow demand
+pub struct ArbitrationRequest {
+    pub oracle: Address,
+    /// Oracle-specific demand data from the `TrustedOracleArbiter` leaf
+    pub demand: Bytes,
+    pub receipt: TransactionReceipt,
+}
+
+/// Result from `AlkahestClient::fulfill_and_request_arbitration`
+pub struct FulfillmentWithArbitration {
+    pub fulfillment_uid: FixedBytes<32>,
+    pub fulfillment_receipt: TransactionReceipt,
+    /// One request per distinct (oracle, demand) leaf, in demand-tree order
+    pub requests: Vec<ArbitrationRequest>,
+}
+
+/// A decision this oracle has recorded on-chain, from its latest `ArbitrationMade` log
+#[derive(Debug, Clone)]
+pub struct PastDecision {
+    pub fulfillment_uid: FixedBytes<32>,
+    /// `keccak256(fulfillmentUid ++ demand)`, see [`decision_key`]
+    pub decision_key: FixedBytes<32>,
+    pub decision: bool,
+    pub block_number: Option<u64>,
+    pub transaction_hash: Option<FixedBytes<32>>,
+}
+
+/// Whether `revise_decisions*` submits corrections or only reports them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevisionMode {
+    /// Compute the report without sending any transaction
+    DryRun,
+    /// Submit an `arbitrate` call for every changed decision
+    Apply,
+}
+
+/// A past decision whose re-evaluated outcome differs from the recorded one
+#[derive(Debug, Clone)]
+pub struct DecisionRevision {
+    pub attestation: Attestation,
+    pub demand: Bytes,
+    pub previous: bool,
+    pub revised: bool,
+}
+
+/// Result from `revise_decisions` / `revise_decisions_async`
+pub struct RevisionReport {
+    /// Decisions that changed on re-evaluation
+    pub revisions: Vec<DecisionRevision>,
+    /// Number of past decisions that re-evaluated to the same outcome
+    pub unchanged: usize,
+    /// Number of past decisions the handler declined to re-evaluate (returned `None`)
+    pub skipped: usize,
+    /// Corrections submitted on-chain (always empty for `RevisionMode::DryRun`)
+    pub submitted: Vec<Decision>,
+}
+
 /// Result from `arbitrate_many`
 pub struct ArbitrateManyResult {
     /// Decisions made for past attestations (empty for `Future` mode)
@@ -171,10 +249,38 @@ impl TrustedOracleModule {
             wallet_provider,
             signer_address,
             poll_interval,
+            metrics: Arc::new(NoopOracleMetrics),
             addresses: addresses.unwrap_or_default(),
         })
     }
 
+    /// Report runtime metrics for every arbitration this module performs.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let metrics = Arc::new(PrometheusOracleMetrics::new());
+    /// let oracle = client.oracle().clone().with_metrics(metrics.clone());
+    /// oracle.arbitrate_many_async(arbitrate, on_decision, ArbitrationMode::All).await?;
+    /// ```
+    pub fn with_metrics(mut self, metrics: Arc<dyn OracleMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn metrics(&self) -> &Arc<dyn OracleMetrics> {
+        &self.metrics
+    }
+
+    /// Fetch the oracle wallet balance and report it to the metrics sink.
+    pub async fn refresh_wallet_balance(&self) -> eyre::Result<alloy::primitives::U256> {
+        let balance = self
+            .public_provider
+            .get_balance(self.signer_address)
+            .await?;
+        self.metrics.wallet_balance(balance);
+        Ok(balance)
+    }
+
     pub async fn wait_for_arbitration(
         &self,
         fulfillment_uid: FixedBytes<32>,
@@ -192,12 +298,7 @@ impl TrustedOracleModule {
 
         // If demand is provided, compute decisionKey and filter by it
         if let Some(demand) = demand {
-            // decisionKey = keccak256(abi.encodePacked(fulfillmentUid, demand))
-            let mut packed = Vec::with_capacity(32 + demand.len());
-            packed.extend_from_slice(fulfillment_uid.as_slice());
-            packed.extend_from_slice(&demand);
-            let decision_key = alloy::primitives::keccak256(&packed);
-            filter = filter.topic1(decision_key);
+            filter = filter.topic1(decision_key(fulfillment_uid, &demand));
         }
 
         // If oracle is provided, filter by it
@@ -288,49 +389,6 @@ impl TrustedOracleModule {
         }
     }
 
-    /// Open a transport-agnostic log stream, returning the stream alongside a
-    /// [`SubscriptionHandle`] used to release the underlying resource.
-    ///
-    /// Internally this picks pubsub (`subscribe_logs` -> `SubscriptionStream`)
-    /// or polling (`watch_logs` with `poll_interval` -> `PollerStream` flattened)
-    /// based on the provider's pubsub capability.
-    async fn open_log_stream(
-        &self,
-        filter: &Filter,
-        poll_interval: Duration,
-    ) -> eyre::Result<(BoxedLogStream, SubscriptionHandle)> {
-        if provider_supports_pubsub(&*self.public_provider) {
-            let sub = self.public_provider.subscribe_logs(filter).await?;
-            let local_id = *sub.local_id();
-            let stream: BoxedLogStream = Box::pin(sub.into_stream());
-            Ok((
-                stream,
-                SubscriptionHandle {
-                    inner: SubscriptionHandleInner::Pubsub { local_id },
-                },
-            ))
-        } else {
-            let cancel = CancellationToken::new();
-            let poller = self
-                .public_provider
-                .watch_logs(filter)
-                .await?
-                .with_poll_interval(poll_interval);
-            let stream = poller.into_stream().flat_map(futures::stream::iter);
-            // Wrap the stream so it terminates when `cancel` is fired.
-            let cancel_clone = cancel.clone();
-            let stream: BoxedLogStream = Box::pin(stream.take_until(async move {
-                cancel_clone.cancelled().await;
-            }));
-            Ok((
-                stream,
-                SubscriptionHandle {
-                    inner: SubscriptionHandleInner::Polling { cancel },
-                },
-            ))
-        }
-    }
-
     pub async fn request_arbitration(
         &self,
         obligation_uid: FixedBytes<32>,
@@ -437,6 +495,10 @@ impl TrustedOracleModule {
             .map(|log| log.log_decode::<TrustedOracleArbiter::ArbitrationRequested>())
             .collect::<Result<Vec<_>, _>>()?;
 
+        if let Some(block) = logs.iter().filter_map(|log| log.block_number).max() {
+            self.metrics.block_processed(block);
+        }
+
         // Fetch attestations and pair with their demand data
         let attestation_futures = logs.into_iter().map(|log| {
             let eas = IEAS::new(self.addresses.eas, &*self.wallet_provider);
@@ -484,6 +546,14 @@ impl TrustedOracleModule {
     ) -> eyre::Result<Vec<Decision>> {
         use itertools::izip;
 
+        let _queued: Vec<_> = attestations_with_demand
+            .iter()
+            .map(|_| {
+                self.metrics.request_seen();
+                QueuedRequest::new(&*self.metrics)
+            })
+            .collect();
+
         let arbitration_futs = attestations_with_demand
             .iter()
             .zip(decisions.iter())
@@ -509,24 +579,37 @@ impl TrustedOracleModule {
 
         let mut pending_txs = Vec::new();
         for fut in arbitration_futs {
-            pending_txs.push(fut.await?);
+            pending_txs.push(
+                fut.await
+                    .inspect_err(|_| self.metrics.arbitration_failed())?,
+            );
         }
 
         let receipt_futs = pending_txs
             .into_iter()
             .map(|tx| async move { tx.get_receipt().await });
 
-        let receipts = try_join_all(receipt_futs).await?;
+        let receipts = try_join_all(receipt_futs)
+            .await
+            .inspect_err(|_| self.metrics.arbitration_failed())?;
 
         let result = izip!(attestations_with_demand, decisions, receipts)
             .filter(|(_, d, _)| d.is_some())
             .map(|(awd, decision, receipt)| Decision {
                 attestation: awd.attestation,
+                demand: awd.demand,
                 decision: decision.unwrap(),
                 receipt,
             })
             .collect::<Vec<Decision>>();
 
+        for decision in &result {
+            self.metrics.decision_made(decision.decision);
+        }
+        if !result.is_empty() {
+            report_wallet_balance(&self.public_provider, self.signer_address, &*self.metrics).await;
+        }
+
         Ok(result)
     }
 
@@ -569,7 +652,8 @@ impl TrustedOracleModule {
         // Set up future listener if needed
         let subscription = if include_future {
             let filter = self.make_arbitration_requested_filter();
-            let (stream, handle) = self.open_log_stream(&filter, self.poll_interval).await?;
+            let (stream, handle) =
+                open_log_stream(&self.public_provider, &filter, self.poll_interval).await?;
 
             self.spawn_stream_handler(stream, arbitrate, on_decision, skip_arbitrated);
 
@@ -624,7 +708,8 @@ impl TrustedOracleModule {
         // Set up future listener if needed
         let subscription = if include_future {
             let filter = self.make_arbitration_requested_filter();
-            let (stream, handle) = self.open_log_stream(&filter, self.poll_interval).await?;
+            let (stream, handle) =
+                open_log_stream(&self.public_provider, &filter, self.poll_interval).await?;
 
             self.spawn_stream_handler_async(stream, arbitrate, on_decision, skip_arbitrated);
 
@@ -639,6 +724,263 @@ impl TrustedOracleModule {
         })
     }
 
+    /// List this oracle's current on-chain decisions.
+    ///
+    /// Reads every `ArbitrationMade` log emitted for this oracle. A decision
+    /// that was overwritten is reported once, with its latest value.
+    pub async fn list_decisions(&self) -> eyre::Result<Vec<PastDecision>> {
+        let filter = self.make_arbitration_made_filter(None);
+        let logs = self.public_provider.get_logs(&filter).await?;
+
+        let mut decisions: Vec<PastDecision> = Vec::new();
+        let mut index: HashMap<FixedBytes<32>, usize> = HashMap::new();
+        for log in logs {
+            let decoded = log.log_decode::<TrustedOracleArbiter::ArbitrationMade>()?;
+            let decision = PastDecision {
+                fulfillment_uid: decoded.inner.fulfillmentUid,
+                decision_key: decoded.inner.decisionKey,
+                decision: decoded.inner.decision,
+                block_number: decoded.block_number,
+                transaction_hash: decoded.transaction_hash,
+            };
+            match index.get(&decision.decision_key) {
+                Some(&i) => decisions[i] = decision,
+                None => {
+                    index.insert(decision.decision_key, decisions.len());
+                    decisions.push(decision);
+                }
+            }
+        }
+
+        Ok(decisions)
+    }
+
+    /// Past arbitration requests this oracle has already decided, paired
+    /// with the recorded decision.
+    async fn get_decided_attestations(&self) -> eyre::Result<Vec<(AttestationWithDemand, bool)>> {
+        let decisions: HashMap<FixedBytes<32>, bool> = self
+            .list_decisions()
+            .await?
+            .into_iter()
+            .map(|d| (d.decision_key, d.decision))
+            .collect();
+
+        let mut seen = std::collections::HashSet::new();
+        Ok(self
+            .get_past_attestations(false)
+            .await?
+            .into_iter()
+            .filter_map(|awd| {
+                let key = decision_key(awd.attestation.uid, &awd.demand);
+                // Repeated requests for the same (fulfillment, demand) share one decision
+                if !seen.insert(key) {
+                    return None;
+                }
+                decisions.get(&key).map(|previous| (awd, *previous))
+            })
+            .collect())
+    }
+
+    async fn apply_revisions(
+        &self,
+        decided: Vec<(AttestationWithDemand, bool)>,
+        revised: Vec<Option<bool>>,
+        mode: RevisionMode,
+    ) -> eyre::Result<RevisionReport> {
+        let mut revisions = Vec::new();
+        let mut unchanged = 0;
+        let mut skipped = 0;
+        for ((awd, previous), revised) in decided.into_iter().zip(revised) {
+            match revised {
+                None => skipped += 1,
+                Some(revised) if revised == previous => unchanged += 1,
+                Some(revised) => revisions.push(DecisionRevision {
+                    attestation: awd.attestation,
+                    demand: awd.demand,
+                    previous,
+                    revised,
+                }),
+            }
+        }
+
+        let submitted = match mode {
+            RevisionMode::DryRun => Vec::new(),
+            RevisionMode::Apply => {
+                let (decisions, attestations) = revisions
+                    .iter()
+                    .map(|r| {
+                        (
+                            Some(r.revised),
+                            AttestationWithDemand {
+                                attestation: r.attestation.clone(),
+                                demand: r.demand.clone(),
+                            },
+                        )
+                    })
+                    .unzip();
+                self.submit_arbitrations(decisions, attestations).await?
+            }
+        };
+
+        Ok(RevisionReport {
+            revisions,
+            unchanged,
+            skipped,
+            submitted,
+        })
+    }
+
+    /// Re-run `arbitrate` over this oracle's past decisions and correct the ones that changed
+    ///
+    /// Only requests that already have an on-chain decision are considered;
+    /// use `arbitrate_many_*` with `PastUnarbitrated` for the rest. Running
+    /// this twice with the same handler submits nothing the second time.
+    ///
+    /// # Arguments
+    /// * `arbitrate` - Sync callback that returns `Some(true/false)` to re-evaluate, `None` to skip
+    /// * `mode` - `DryRun` to only report changes, `Apply` to submit corrections
+    pub async fn revise_decisions<Arbitrate: Fn(&AttestationWithDemand) -> Option<bool>>(
+        &self,
+        arbitrate: Arbitrate,
+        mode: RevisionMode,
+    ) -> eyre::Result<RevisionReport> {
+        let decided = self.get_decided_attestations().await?;
+        let revised = decided.iter().map(|(awd, _)| arbitrate(awd)).collect();
+        self.apply_revisions(decided, revised, mode).await
+    }
+
+    /// Re-run `arbitrate` over this oracle's past decisions (async callback version)
+    ///
+    /// See [`TrustedOracleModule::revise_decisions`].
+    pub async fn revise_decisions_async<
+        ArbitrateFut: std::future::Future<Output = Option<bool>>,
+        Arbitrate: Fn(&AttestationWithDemand) -> ArbitrateFut,
+    >(
+        &self,
+        arbitrate: Arbitrate,
+        mode: RevisionMode,
+    ) -> eyre::Result<RevisionReport> {
+        let decided = self.get_decided_attestations().await?;
+        let revised =
+            futures::future::join_all(decided.iter().map(|(awd, _)| arbitrate(awd))).await;
+        self.apply_revisions(decided, revised, mode).await
+    }
+
+    /// Create an empty [`DecisionAuditLog`] for this oracle.
+    ///
+    /// `signer` must be the oracle key this module submits decisions with; the
+    /// chain id is read from the provider so records are bound to this network.
+    pub async fn decision_audit_log(
+        &self,
+        signer: PrivateKeySigner,
+    ) -> eyre::Result<DecisionAuditLog> {
+        if signer.address() != self.signer_address {
+            return Err(eyre::eyre!(
+                "Audit signer {} does not match oracle address {}",
+                signer.address(),
+                self.signer_address
+            ));
+        }
+        let chain_id = self.public_provider.get_chain_id().await?;
+        Ok(DecisionAuditLog::new(
+            signer,
+            chain_id,
+            self.addresses.trusted_oracle_arbiter,
+        ))
+    }
+
+    /// Arbitrate multiple attestations, recording a signed audit entry per decision
+    ///
+    /// Behaves like `arbitrate_many_async`, but the handler also returns an
+    /// evidence blob alongside its decision. After each decision is submitted,
+    /// a record with the evidence is signed and appended to `audit_log`.
+    ///
+    /// # Arguments
+    /// * `arbitrate` - Async callback returning `Some((decision, evidence))` to arbitrate, `None` to skip
+    /// * `on_decision` - Callback invoked after each successful arbitration (for modes with listeners)
+    /// * `audit_log` - Log that receives a signed record for every submitted decision
+    /// * `mode` - Which attestations to process (see `ArbitrationMode`)
+    pub async fn arbitrate_many_audited<
+        ArbitrateFut: std::future::Future<Output = Option<(bool, Bytes)>> + Send + 'static,
+        Arbitrate: Fn(&AttestationWithDemand) -> ArbitrateFut + Clone + Send + Sync + 'static,
+        OnDecisionFut: std::future::Future<Output = ()> + Send + 'static,
+        OnDecision: Fn(&Decision) -> OnDecisionFut + Clone + Send + Sync + 'static,
+    >(
+        &self,
+        arbitrate: Arbitrate,
+        on_decision: OnDecision,
+        audit_log: &DecisionAuditLog,
+        mode: ArbitrationMode,
+    ) -> eyre::Result<ArbitrateManyResult> {
+        // Evidence is handed from the handler to the post-submission hook via
+        // this map, keyed by the same decision key the arbiter contract uses.
+        let evidence: Arc<Mutex<HashMap<FixedBytes<32>, Bytes>>> = Default::default();
+
+        let arbitrate_with_evidence = {
+            let evidence = evidence.clone();
+            move |awd: &AttestationWithDemand| {
+                let fut = arbitrate(awd);
+                let evidence = evidence.clone();
+                let key = decision_key(awd.attestation.uid, &awd.demand);
+                async move {
+                    let (decision, blob) = fut.await?;
+                    if let Ok(mut evidence) = evidence.lock() {
+                        evidence.insert(key, blob);
+                    }
+                    Some(decision)
+                }
+            }
+        };
+
+        let take_evidence = {
+            let evidence = evidence.clone();
+            move |decision: &Decision| {
+                let key = decision_key(decision.attestation.uid, &decision.demand);
+                evidence
+                    .lock()
+                    .ok()
+                    .and_then(|mut evidence| evidence.remove(&key))
+                    .unwrap_or_default()
+            }
+        };
+
+        let on_decision_with_audit = {
+            let audit_log = audit_log.clone();
+            let take_evidence = take_evidence.clone();
+            move |decision: &Decision| {
+                let audit_log = audit_log.clone();
+                let evidence = take_evidence(decision);
+                let decision = Decision {
+                    attestation: decision.attestation.clone(),
+                    demand: decision.demand.clone(),
+                    decision: decision.decision,
+                    receipt: decision.receipt.clone(),
+                };
+                let user_fut = on_decision(&decision);
+                async move {
+                    if let Err(err) = audit_log.record(&decision, evidence).await {
+                        tracing::error!(
+                            "Failed to record audit entry for {}: {}",
+                            decision.attestation.uid,
+                            err
+                        );
+                    }
+                    user_fut.await;
+                }
+            }
+        };
+
+        let result = self
+            .arbitrate_many_async(arbitrate_with_evidence, on_decision_with_audit, mode)
+            .await?;
+
+        for decision in &result.past_decisions {
+            audit_log.record(decision, take_evidence(decision)).await?;
+        }
+
+        Ok(result)
+    }
+
     /// Arbitrate multiple attestations in blocking mode (sync callback version)
     ///
     /// Unlike `arbitrate_many_sync`, this function does NOT spawn a background task.
@@ -681,7 +1023,8 @@ impl TrustedOracleModule {
         // Process future events in blocking mode if needed
         let subscription = if include_future {
             let filter = self.make_arbitration_requested_filter();
-            let (stream, handle) = self.open_log_stream(&filter, self.poll_interval).await?;
+            let (stream, handle) =
+                open_log_stream(&self.public_provider, &filter, self.poll_interval).await?;
 
             self.handle_stream_blocking_sync(
                 stream,
@@ -747,7 +1090,8 @@ impl TrustedOracleModule {
         // Process future events in blocking mode if needed
         let subscription = if include_future {
             let filter = self.make_arbitration_requested_filter();
-            let (stream, handle) = self.open_log_stream(&filter, self.poll_interval).await?;
+            let (stream, handle) =
+                open_log_stream(&self.public_provider, &filter, self.poll_interval).await?;
 
             self.handle_stream_blocking_async(
                 stream,
@@ -811,6 +1155,12 @@ impl TrustedOracleModule {
                 continue;
             };
 
+            self.metrics.request_seen();
+            if let Some(block) = log.block_number {
+                self.metrics.block_processed(block);
+            }
+            let _queued = QueuedRequest::new(&*self.metrics);
+
             let Ok(attestation) = eas
                 .getAttestation(arbitration_log.inner.fulfillmentUid)
                 .call()
@@ -857,22 +1207,39 @@ impl TrustedOracleModule {
             };
 
             match arbiter
-                .arbitrate(attestation.uid, demand, decision_value)
+                .arbitrate(attestation.uid, demand.clone(), decision_value)
                 .nonce(nonce)
                 .send()
                 .await
             {
-                Ok(tx) => {
-                    if let Ok(receipt) = tx.get_receipt().await {
+                Ok(tx) => match tx.get_receipt().await {
+                    Ok(receipt) => {
+                        self.metrics.decision_made(decision_value);
+                        report_wallet_balance(
+                            &self.public_provider,
+                            self.signer_address,
+                            &*self.metrics,
+                        )
+                        .await;
                         let decision = Decision {
                             attestation,
+                            demand,
                             decision: decision_value,
                             receipt,
                         };
                         on_decision(&decision).await;
                     }
-                }
+                    Err(err) => {
+                        self.metrics.arbitration_failed();
+                        tracing::error!(
+                            "Arbitration receipt failed for {}: {}",
+                            attestation.uid,
+                            err
+                        );
+                    }
+                },
                 Err(err) => {
+                    self.metrics.arbitration_failed();
                     tracing::error!("Arbitration failed for {}: {}", attestation.uid, err);
                 }
             }
@@ -922,6 +1289,12 @@ impl TrustedOracleModule {
                 continue;
             };
 
+            self.metrics.request_seen();
+            if let Some(block) = log.block_number {
+                self.metrics.block_processed(block);
+            }
+            let _queued = QueuedRequest::new(&*self.metrics);
+
             let Ok(attestation) = eas
                 .getAttestation(arbitration_log.inner.fulfillmentUid)
                 .call()
@@ -968,22 +1341,39 @@ impl TrustedOracleModule {
             };
 
             match arbiter
-                .arbitrate(attestation.uid, demand, decision_value)
+                .arbitrate(attestation.uid, demand.clone(), decision_value)
                 .nonce(nonce)
                 .send()
                 .await
             {
-                Ok(tx) => {
-                    if let Ok(receipt) = tx.get_receipt().await {
+                Ok(tx) => match tx.get_receipt().await {
+                    Ok(receipt) => {
+                        self.metrics.decision_made(decision_value);
+                        report_wallet_balance(
+                            &self.public_provider,
+                            self.signer_address,
+                            &*self.metrics,
+                        )
+                        .await;
                         let decision = Decision {
                             attestation,
+                            demand,
                             decision: decision_value,
                             receipt,
                         };
                         on_decision(&decision).await;
                     }
-                }
+                    Err(err) => {
+                        self.metrics.arbitration_failed();
+                        tracing::error!(
+                            "Arbitration receipt failed for {}: {}",
+                            attestation.uid,
+                            err
+                        );
+                    }
+                },
                 Err(err) => {
+                    self.metrics.arbitration_failed();
                     tracing::error!("Arbitration failed for {}: {}", attestation.uid, err);
                 }
             }
@@ -1006,6 +1396,7 @@ impl TrustedOracleModule {
         let arbiter_address = self.addresses.trusted_oracle_arbiter;
         let signer_address = self.signer_address;
         let public_provider = self.public_provider.clone();
+        let metrics = self.metrics.clone();
 
         tokio::spawn(async move {
             let eas = IEAS::new(eas_address, &wallet_provider);
@@ -1019,6 +1410,12 @@ impl TrustedOracleModule {
                     continue;
                 };
 
+                metrics.request_seen();
+                if let Some(block) = log.block_number {
+                    metrics.block_processed(block);
+                }
+                let _queued = QueuedRequest::new(&*metrics);
+
                 let Ok(attestation) = eas
                     .getAttestation(arbitration_log.inner.fulfillmentUid)
                     .call()
@@ -1067,22 +1464,35 @@ impl TrustedOracleModule {
                 };
 
                 match arbiter
-                    .arbitrate(attestation.uid, demand, decision_value)
+                    .arbitrate(attestation.uid, demand.clone(), decision_value)
                     .nonce(nonce)
                     .send()
                     .await
                 {
-                    Ok(tx) => {
-                        if let Ok(receipt) = tx.get_receipt().await {
+                    Ok(tx) => match tx.get_receipt().await {
+                        Ok(receipt) => {
+                            metrics.decision_made(decision_value);
+                            report_wallet_balance(&public_provider, signer_address, &*metrics)
+                                .await;
                             let decision = Decision {
                                 attestation,
+                                demand,
                                 decision: decision_value,
                                 receipt,
                             };
                             tokio::spawn(on_decision(&decision));
                         }
-                    }
+                        Err(err) => {
+                            metrics.arbitration_failed();
+                            tracing::error!(
+                                "Arbitration receipt failed for {}: {}",
+                                attestation.uid,
+                                err
+                            );
+                        }
+                    },
                     Err(err) => {
+                        metrics.arbitration_failed();
                         tracing::error!("Arbitration failed for {}: {}", attestation.uid, err);
                     }
                 }
@@ -1107,6 +1517,7 @@ impl TrustedOracleModule {
         let arbiter_address = self.addresses.trusted_oracle_arbiter;
         let signer_address = self.signer_address;
         let public_provider = self.public_provider.clone();
+        let metrics = self.metrics.clone();
 
         tokio::spawn(async move {
             let eas = IEAS::new(eas_address, &wallet_provider);
@@ -1120,6 +1531,12 @@ impl TrustedOracleModule {
                     continue;
                 };
 
+                metrics.request_seen();
+                if let Some(block) = log.block_number {
+                    metrics.block_processed(block);
+                }
+                let _queued = QueuedRequest::new(&*metrics);
+
                 let Ok(attestation) = eas
                     .getAttestation(arbitration_log.inner.fulfillmentUid)
                     .call()
@@ -1168,22 +1585,35 @@ impl TrustedOracleModule {
                 };
 
                 match arbiter
-                    .arbitrate(attestation.uid, demand, decision_value)
+                    .arbitrate(attestation.uid, demand.clone(), decision_value)
                     .nonce(nonce)
                     .send()
                     .await
                 {
-                    Ok(tx) => {
-                        if let Ok(receipt) = tx.get_receipt().await {
+                    Ok(tx) => match tx.get_receipt().await {
+                        Ok(receipt) => {
+                            metrics.decision_made(decision_value);
+                            report_wallet_balance(&public_provider, signer_address, &*metrics)
+                                .await;
                             let decision = Decision {
                                 attestation,
+                                demand,
                                 decision: decision_value,
                                 receipt,
                             };
                             tokio::spawn(on_decision(&decision));
                         }
-                    }
+                        Err(err) => {
+                            metrics.arbitration_failed();
+                            tracing::error!(
+                                "Arbitration receipt failed for {}: {}",
+                                attestation.uid,
+                                err
+                            );
+                        }
+                    },
                     Err(err) => {
+                        metrics.arbitration_failed();
                         tracing::error!("Arbitration failed for {}: {}", attestation.uid, err);
                     }
                 }
@@ -1192,6 +1622,60 @@ impl TrustedOracleModule {
     }
 }
 
+/// Open a transport-agnostic log stream, returning the stream alongside a
+/// [`SubscriptionHandle`] used to release the underlying resource.
+///
+/// Internally this picks pubsub (`subscribe_logs` -> `SubscriptionStream`)
+/// or polling (`watch_logs` with `poll_interval` -> `PollerStream` flattened)
+/// based on the provider's pubsub capability.
+pub(crate) async fn open_log_stream(
+    provider: &SharedPublicProvider,
+    filter: &Filter,
+    poll_interval: Duration,
+) -> eyre::Result<(BoxedLogStream, SubscriptionHandle)> {
+    if provider_supports_pubsub(&**provider) {
+        let sub = provider.subscribe_logs(filter).await?;
+        let local_id = *sub.local_id();
+        let stream: BoxedLogStream = Box::pin(sub.into_stream());
+        Ok((
+            stream,
+            SubscriptionHandle {
+                inner: SubscriptionHandleInner::Pubsub { local_id },
+            },
+        ))
+    } else {
+        let cancel = CancellationToken::new();
+        let poller = provider
+            .watch_logs(filter)
+            .await?
+            .with_poll_interval(poll_interval);
+        let stream = poller.into_stream().flat_map(futures::stream::iter);
+        // Wrap the stream so it terminates when `cancel` is fired.
+        let cancel_clone = cancel.clone();
+        let stream: BoxedLogStream = Box::pin(stream.take_until(async move {
+            cancel_clone.cancelled().await;
+        }));
+        Ok((
+            stream,
+            SubscriptionHandle {
+                inner: SubscriptionHandleInner::Polling { cancel },
+            },
+        ))
+    }
+}
+
+/// Report the oracle wallet balance, logging instead of failing on RPC errors.
+async fn report_wallet_balance(
+    provider: &SharedPublicProvider,
+    signer_address: Address,
+    metrics: &dyn OracleMetrics,
+) {
+    match provider.get_balance(signer_address).await {
+        Ok(balance) => metrics.wallet_balance(balance),
+        Err(err) => tracing::warn!("Failed to fetch oracle wallet balance: {}", err),
+    }
+}
+
 // Type aliases for backwards compatibility
 pub type OracleModule = TrustedOracleModule;
 pub type OracleAddresses = TrustedOracleAddresses;
@@ -5,8 +5,12 @@ pub mod splitters;
 // Re-export oracle module from arbiters for backwards compatibility
 pub mod oracle {
     pub use super::arbiters::{
-        ArbitrateManyResult, ArbitrationMode, AttestationWithDemand, Decision, OracleAddresses,
-        OracleModule, TrustedOracleAddresses, TrustedOracleModule,
+        ArbitrateManyResult, ArbitrationMode, ArbitrationRequest, AttestationWithDemand, Decision,
+        DecisionAuditLog, DecisionRecord, DecisionRevision, FulfillmentWithArbitration,
+        MetricsServer, NoopOracleMetrics, OracleAddresses, OracleMetrics, OracleModule,
+        PastDecision, PrometheusOracleMetrics, RevisionMode, RevisionReport, SignedDecisionRecord,
+        TrustedOracleAddresses, TrustedOracleModule, read_decision_records_jsonl,
+        verify_decision_record,
     };
 }
 
@@ -0,0 +1,228 @@
+//! Delegated (EIP-712 signed) EAS attestations and revocations.
+//!
+//! An attester signs a request off-chain with [`Delegation::sign_attestation`]
+//! or [`Delegation::sign_revocation`]; anyone can then submit it with
+//! [`Delegation::attest_by_delegation`] or [`Delegation::revoke_by_delegation`]
+//! and pay the gas.
+
+use alloy::{
+    dyn_abi::Eip712Domain,
+    primitives::{Address, B256, U256, keccak256},
+    providers::Provider as _,
+    rpc::types::TransactionReceipt,
+    signers::Signer as _,
+    sol,
+    sol_types::SolStruct as _,
+};
+
+use super::AttestationModule;
+use crate::contracts::IEAS::{
+    self, AttestationRequest, DelegatedAttestationRequest, DelegatedRevocationRequest,
+    RevocationRequestData,
+};
+
+sol! {
+    /// EIP-712 helpers exposed by the EAS contract but not by `IEAS`.
+    #[sol(rpc)]
+    interface IEASEip712 {
+        function getName() external view returns (string memory);
+        function version() external view returns (string memory);
+        function getNonce(address account) external view returns (uint256);
+        function getAttestTypeHash() external pure returns (bytes32);
+        function getRevokeTypeHash() external pure returns (bytes32);
+    }
+
+    /// Typed data signed for `attestByDelegation`.
+    struct Attest {
+        address attester;
+        bytes32 schema;
+        address recipient;
+        uint64 expirationTime;
+        bool revocable;
+        bytes32 refUID;
+        bytes data;
+        uint256 value;
+        uint256 nonce;
+        uint64 deadline;
+    }
+
+    /// Typed data signed for `revokeByDelegation`.
+    struct Revoke {
+        address revoker;
+        bytes32 schema;
+        bytes32 uid;
+        uint256 value;
+        uint256 nonce;
+        uint64 deadline;
+    }
+}
+
+/// Converts a signature to the `(v, r, s)` form EAS expects, with `v` in {27, 28}.
+pub(super) fn to_eas_signature(signature: &alloy::signers::Signature) -> IEAS::Signature {
+    IEAS::Signature {
+        v: 27 + signature.v() as u8,
+        r: signature.r().into(),
+        s: signature.s().into(),
+    }
+}
+
+/// Delegated attestation API.
+pub struct Delegation<'a> {
+    module: &'a AttestationModule,
+}
+
+impl<'a> Delegation<'a> {
+    pub fn new(module: &'a AttestationModule) -> Self {
+        Self { module }
+    }
+
+    /// EIP-712 domain of the EAS contract, read from the chain.
+    pub async fn domain(&self) -> eyre::Result<Eip712Domain> {
+        let eas = IEASEip712::new(self.module.addresses.eas, &self.module.wallet_provider);
+        let (name, version, chain_id) = tokio::try_join!(
+            async { Ok::<_, eyre::Error>(eas.getName().call().await?) },
+            async { Ok(eas.version().call().await?) },
+            async { Ok(self.module.wallet_provider.get_chain_id().await?) },
+        )?;
+        Ok(Eip712Domain {
+            name: Some(name.into()),
+            version: Some(version.into()),
+            chain_id: Some(U256::from(chain_id)),
+            verifying_contract: Some(self.module.addresses.eas),
+            salt: None,
+        })
+    }
+
+    /// Current delegation nonce of `account`. Each submitted delegated
+    /// request consumes one, so sign requests one at a time.
+    pub async fn nonce(&self, account: Address) -> eyre::Result<U256> {
+        let eas = IEASEip712::new(self.module.addresses.eas, &self.module.wallet_provider);
+        Ok(eas.getNonce(account).call().await?)
+    }
+
+    /// Fails if the deployed EAS uses different typed-data layouts than
+    /// this client signs.
+    async fn check_type_hashes(&self) -> eyre::Result<()> {
+        let eas = IEASEip712::new(self.module.addresses.eas, &self.module.wallet_provider);
+        let (attest, revoke) = tokio::try_join!(
+            async { Ok::<_, eyre::Error>(eas.getAttestTypeHash().call().await?) },
+            async { Ok(eas.getRevokeTypeHash().call().await?) },
+        )?;
+        let expected_attest = keccak256(Attest::eip712_encode_type().as_bytes());
+        let expected_revoke = keccak256(Revoke::eip712_encode_type().as_bytes());
+        if attest != expected_attest || revoke != expected_revoke {
+            return Err(eyre::eyre!(
+                "EAS at {} uses unsupported delegation type hashes",
+                self.module.addresses.eas
+            ));
+        }
+        Ok(())
+    }
+
+    /// Signs `request` as this module's signer, valid until `deadline`
+    /// (unix seconds, `0` for no deadline).
+    pub async fn sign_attestation(
+        &self,
+        request: AttestationRequest,
+        deadline: u64,
+    ) -> eyre::Result<DelegatedAttestationRequest> {
+        self.check_type_hashes().await?;
+        let attester = self.module.signer.address();
+        let (domain, nonce) = tokio::try_join!(self.domain(), self.nonce(attester))?;
+
+        let typed = Attest {
+            attester,
+            schema: request.schema,
+            recipient: request.data.recipient,
+            expirationTime: request.data.expirationTime,
+            revocable: request.data.revocable,
+            refUID: request.data.refUID,
+            data: request.data.data.clone(),
+            value: request.data.value,
+            nonce,
+            deadline,
+        };
+        let signature = self
+            .module
+            .signer
+            .sign_hash(&typed.eip712_signing_hash(&domain))
+            .await?;
+
+        Ok(DelegatedAttestationRequest {
+            schema: request.schema,
+            data: request.data,
+            signature: to_eas_signature(&signature),
+            attester,
+            deadline,
+        })
+    }
+
+    /// Signs a revocation of `uid` under `schema` as this module's signer.
+    pub async fn sign_revocation(
+        &self,
+        schema: B256,
+        uid: B256,
+        value: U256,
+        deadline: u64,
+    ) -> eyre::Result<DelegatedRevocationRequest> {
+        self.check_type_hashes().await?;
+        let revoker = self.module.signer.address();
+        let (domain, nonce) = tokio::try_join!(self.domain(), self.nonce(revoker))?;
+
+        let typed = Revoke {
+            revoker,
+            schema,
+            uid,
+            value,
+            nonce,
+            deadline,
+        };
+        let signature = self
+            .module
+            .signer
+            .sign_hash(&typed.eip712_signing_hash(&domain))
+            .await?;
+
+        Ok(DelegatedRevocationRequest {
+            schema,
+            data: RevocationRequestData { uid, value },
+            signature: to_eas_signature(&signature),
+            revoker,
+            deadline,
+        })
+    }
+
+    /// Submits an attestation signed by someone else, paying the gas.
+    pub async fn attest_by_delegation(
+        &self,
+        request: DelegatedAttestationRequest,
+    ) -> eyre::Result<TransactionReceipt> {
+        let eas = IEAS::new(self.module.addresses.eas, &self.module.wallet_provider);
+        let value = request.data.value;
+        let receipt = eas
+            .attestByDelegation(request)
+            .value(value)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+        Ok(receipt)
+    }
+
+    /// Submits a revocation signed by someone else, paying the gas.
+    pub async fn revoke_by_delegation(
+        &self,
+        request: DelegatedRevocationRequest,
+    ) -> eyre::Result<TransactionReceipt> {
+        let eas = IEAS::new(self.module.addresses.eas, &self.module.wallet_provider);
+        let value = request.data.value;
+        let receipt = eas
+            .revokeByDelegation(request)
+            .value(value)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+        Ok(receipt)
+    }
+}
@@ -1,11 +1,18 @@
 //! Attestation obligation module
 //!
-//! Provides escrow and utility operations for attestation-based obligations.
+//! Provides escrow and utility operations for attestation-based obligations,
+//! plus delegated and off-chain EAS attestations and schema encoding helpers.
 
+pub mod delegation;
 pub mod escrow;
+pub mod offchain;
+pub mod schema;
 pub mod util;
 
+pub use delegation::Delegation;
 pub use escrow::Escrow;
+pub use offchain::Offchain;
+pub use schema::SchemaEncoder;
 pub use util::Util;
 
 use alloy::primitives::Address;
@@ -99,7 +106,7 @@ pub struct AttestationAddresses {
 
 #[derive(Clone)]
 pub struct AttestationModule {
-    _signer: PrivateKeySigner,
+    pub(crate) signer: PrivateKeySigner,
     pub(crate) wallet_provider: SharedWalletProvider,
     pub addresses: AttestationAddresses,
 }
@@ -156,7 +163,7 @@ impl AttestationModule {
         addresses: Option<AttestationAddresses>,
     ) -> eyre::Result<Self> {
         Ok(AttestationModule {
-            _signer: signer,
+            signer,
             wallet_provider,
             addresses: addresses.unwrap_or_default(),
         })
@@ -167,10 +174,21 @@ impl AttestationModule {
         Escrow::new(self)
     }
 
-    /// Access utility operations (get_attestation, register_schema, attest)
+    /// Access utility operations (get_attestation, register_schema, attest,
+    /// revoke, timestamp and their batch forms)
     pub fn util(&self) -> Util<'_> {
         Util::new(self)
     }
+
+    /// Access delegated (EIP-712 signed) attestation and revocation.
+    pub fn delegation(&self) -> Delegation<'_> {
+        Delegation::new(self)
+    }
+
+    /// Access off-chain attestation signing, verification and timestamping.
+    pub fn offchain(&self) -> Offchain<'_> {
+        Offchain::new(self)
+    }
 }
 
 impl AlkahestExtension for AttestationModule {
@@ -962,4 +980,174 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_schema_encoder_and_multi_attest_revoke() -> eyre::Result<()> {
+        let test = setup_test_environment().await?;
+        let util = test.alice_client.attestation().util();
+
+        let schema = format!(
+            "uint256 score, string note{}",
+            SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos()
+        );
+        let encoder = super::SchemaEncoder::new(&schema)?;
+        let schema_id = register_test_schema(&test, schema).await?;
+        assert_eq!(encoder.schema_uid(Address::ZERO, true), schema_id);
+
+        let request = |score: &str| -> eyre::Result<IEAS::AttestationRequestData> {
+            Ok(IEAS::AttestationRequestData {
+                recipient: test.bob.address(),
+                expirationTime: 0,
+                revocable: true,
+                refUID: FixedBytes::<32>::default(),
+                data: encoder.encode_str(&[score, "ok"])?,
+                value: U256::ZERO,
+            })
+        };
+        let receipt = util
+            .multi_attest(vec![IEAS::MultiAttestationRequest {
+                schema: schema_id,
+                data: vec![request("1")?, request("2")?],
+            }])
+            .await?;
+        let uids = super::Util::attested_uids(&receipt);
+        assert_eq!(uids.len(), 2);
+
+        let attestation = util.get_attestation(uids[1]).await?;
+        let decoded = encoder.decode(&attestation.data)?;
+        assert_eq!(
+            decoded[0].1,
+            alloy::dyn_abi::DynSolValue::Uint(U256::from(2), 256)
+        );
+
+        util.revoke(IEAS::RevocationRequest {
+            schema: schema_id,
+            data: IEAS::RevocationRequestData {
+                uid: uids[0],
+                value: U256::ZERO,
+            },
+        })
+        .await?;
+        util.multi_revoke(vec![IEAS::MultiRevocationRequest {
+            schema: schema_id,
+            data: vec![IEAS::RevocationRequestData {
+                uid: uids[1],
+                value: U256::ZERO,
+            }],
+        }])
+        .await?;
+        for uid in uids {
+            assert!(util.get_attestation(uid).await?.revocationTime > 0);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_attest_and_revoke_by_delegation() -> eyre::Result<()> {
+        let test = setup_test_environment().await?;
+        let schema_id = register_test_schema(
+            &test,
+            format!(
+                "string delegated{}",
+                SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos()
+            ),
+        )
+        .await?;
+
+        // Alice signs, Bob submits and pays the gas.
+        let signed = test
+            .alice_client
+            .attestation()
+            .delegation()
+            .sign_attestation(
+                IEAS::AttestationRequest {
+                    schema: schema_id,
+                    data: IEAS::AttestationRequestData {
+                        recipient: test.bob.address(),
+                        expirationTime: 0,
+                        revocable: true,
+                        refUID: FixedBytes::<32>::default(),
+                        data: Bytes::from_static(b"delegated"),
+                        value: U256::ZERO,
+                    },
+                },
+                0,
+            )
+            .await?;
+        let receipt = test
+            .bob_client
+            .attestation()
+            .delegation()
+            .attest_by_delegation(signed)
+            .await?;
+        let uid = DefaultAlkahestClient::get_attested_event(receipt)?.uid;
+
+        let attestation = test
+            .bob_client
+            .attestation()
+            .util()
+            .get_attestation(uid)
+            .await?;
+        assert_eq!(attestation.attester, test.alice.address());
+
+        let revocation = test
+            .alice_client
+            .attestation()
+            .delegation()
+            .sign_revocation(schema_id, uid, U256::ZERO, 0)
+            .await?;
+        test.bob_client
+            .attestation()
+            .delegation()
+            .revoke_by_delegation(revocation)
+            .await?;
+        let attestation = test
+            .bob_client
+            .attestation()
+            .util()
+            .get_attestation(uid)
+            .await?;
+        assert!(attestation.revocationTime > 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_offchain_attestation_timestamp_and_revoke() -> eyre::Result<()> {
+        let test = setup_test_environment().await?;
+        let offchain = test.alice_client.attestation().offchain();
+
+        let signed = offchain
+            .sign(super::offchain::OffchainAttestationData {
+                schema: FixedBytes::<32>::default(),
+                recipient: test.bob.address(),
+                time: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+                expiration_time: 0,
+                revocable: true,
+                ref_uid: FixedBytes::<32>::default(),
+                data: Bytes::from_static(b"off-chain"),
+            })
+            .await?;
+        offchain.verify(&signed).await?;
+        assert_eq!(signed.signer, test.alice.address());
+
+        assert_eq!(offchain.get_timestamp(&signed).await?, None);
+        offchain.timestamp(&signed).await?;
+        assert!(offchain.get_timestamp(&signed).await?.is_some());
+
+        // Only the signer can revoke.
+        assert!(
+            test.bob_client
+                .attestation()
+                .offchain()
+                .revoke(&signed)
+                .await
+                .is_err()
+        );
+        offchain.revoke(&signed).await?;
+        assert!(offchain.get_revocation(&signed).await?.is_some());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,355 @@
+//! EAS off-chain attestations.
+//!
+//! An off-chain attestation is an EIP-712 signature over the attestation
+//! fields under the `"EAS Attestation"` domain of an EAS deployment. It costs
+//! no gas; its UID can later be timestamped on-chain with
+//! [`Offchain::timestamp`] to prove it existed at that time, or revoked with
+//! [`Offchain::revoke`]. The format matches the EAS SDK's version 2 off-chain
+//! attestations, so they can be verified by other EAS tooling.
+
+use alloy::{
+    dyn_abi::Eip712Domain,
+    primitives::{Address, B256, Bytes, U256, keccak256},
+    providers::Provider as _,
+    rpc::types::TransactionReceipt,
+    signers::{Signature, Signer as _},
+    sol,
+    sol_types::SolStruct as _,
+};
+use serde::{Deserialize, Serialize};
+
+use super::AttestationModule;
+use crate::{contracts::IEAS, utils::generate_salt};
+
+/// Off-chain attestation format version produced by this module.
+pub const OFFCHAIN_ATTESTATION_VERSION: u16 = 2;
+
+/// EIP-712 domain name EAS uses for off-chain attestations.
+pub const OFFCHAIN_DOMAIN_NAME: &str = "EAS Attestation";
+
+sol! {
+    /// Typed data signed for a version 2 off-chain attestation.
+    struct Attest {
+        uint16 version;
+        bytes32 schema;
+        address recipient;
+        uint64 time;
+        uint64 expirationTime;
+        bool revocable;
+        bytes32 refUID;
+        bytes data;
+        bytes32 salt;
+    }
+}
+
+/// Fields of an off-chain attestation, before signing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OffchainAttestationData {
+    pub schema: B256,
+    pub recipient: Address,
+    /// Unix seconds the attestation claims to be made at.
+    pub time: u64,
+    /// Unix seconds it expires at, `0` for never.
+    pub expiration_time: u64,
+    pub revocable: bool,
+    #[serde(rename = "refUID")]
+    pub ref_uid: B256,
+    pub data: Bytes,
+}
+
+/// A signed off-chain attestation together with the domain it was signed
+/// under, so it can be verified without a provider.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedOffchainAttestation {
+    /// Off-chain attestation format version.
+    pub version: u16,
+    /// EAS contract version string used in the domain.
+    pub eas_version: String,
+    pub chain_id: u64,
+    /// EAS contract the attestation is bound to.
+    pub verifying_contract: Address,
+    #[serde(flatten)]
+    pub message: OffchainAttestationData,
+    pub salt: B256,
+    /// Off-chain UID; what gets timestamped or revoked on-chain.
+    pub uid: B256,
+    pub signer: Address,
+    /// 65-byte `r || s || v` signature.
+    pub signature: Bytes,
+}
+
+/// Computes the off-chain UID the way EAS does for version 2 attestations:
+/// a packed keccak256 over the fields, the zero attester, and a zero `uint32`
+/// bump.
+pub fn offchain_uid(version: u16, message: &OffchainAttestationData, salt: B256) -> B256 {
+    let mut packed =
+        Vec::with_capacity(2 + 32 + 20 + 20 + 8 + 8 + 1 + 32 + message.data.len() + 36);
+    packed.extend_from_slice(&version.to_be_bytes());
+    packed.extend_from_slice(message.schema.as_slice());
+    packed.extend_from_slice(message.recipient.as_slice());
+    packed.extend_from_slice(Address::ZERO.as_slice());
+    packed.extend_from_slice(&message.time.to_be_bytes());
+    packed.extend_from_slice(&message.expiration_time.to_be_bytes());
+    packed.push(message.revocable as u8);
+    packed.extend_from_slice(message.ref_uid.as_slice());
+    packed.extend_from_slice(&message.data);
+    packed.extend_from_slice(salt.as_slice());
+    packed.extend_from_slice(&0u32.to_be_bytes());
+    keccak256(packed)
+}
+
+/// EIP-712 domain for off-chain attestations against one EAS deployment.
+pub fn offchain_domain(eas_version: &str, chain_id: u64, eas: Address) -> Eip712Domain {
+    Eip712Domain {
+        name: Some(OFFCHAIN_DOMAIN_NAME.into()),
+        version: Some(eas_version.to_string().into()),
+        chain_id: Some(U256::from(chain_id)),
+        verifying_contract: Some(eas),
+        salt: None,
+    }
+}
+
+fn signing_hash(message: &OffchainAttestationData, salt: B256, domain: &Eip712Domain) -> B256 {
+    Attest {
+        version: OFFCHAIN_ATTESTATION_VERSION,
+        schema: message.schema,
+        recipient: message.recipient,
+        time: message.time,
+        expirationTime: message.expiration_time,
+        revocable: message.revocable,
+        refUID: message.ref_uid,
+        data: message.data.clone(),
+        salt,
+    }
+    .eip712_signing_hash(domain)
+}
+
+impl SignedOffchainAttestation {
+    /// Checks the UID and that `signature` recovers to `signer` under the
+    /// stored domain. Doesn't check revocation or expiry.
+    pub fn verify(&self) -> eyre::Result<()> {
+        if self.version != OFFCHAIN_ATTESTATION_VERSION {
+            return Err(eyre::eyre!(
+                "Unsupported off-chain attestation version {}",
+                self.version
+            ));
+        }
+        let uid = offchain_uid(self.version, &self.message, self.salt);
+        if uid != self.uid {
+            return Err(eyre::eyre!(
+                "UID mismatch: expected {uid}, got {}",
+                self.uid
+            ));
+        }
+        let domain = offchain_domain(&self.eas_version, self.chain_id, self.verifying_contract);
+        let signature = Signature::from_raw(&self.signature)?;
+        let recovered = signature.recover_address_from_prehash(&signing_hash(
+            &self.message,
+            self.salt,
+            &domain,
+        ))?;
+        if recovered != self.signer {
+            return Err(eyre::eyre!(
+                "Signature recovers to {recovered}, expected {}",
+                self.signer
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Off-chain attestation API.
+pub struct Offchain<'a> {
+    module: &'a AttestationModule,
+}
+
+impl<'a> Offchain<'a> {
+    pub fn new(module: &'a AttestationModule) -> Self {
+        Self { module }
+    }
+
+    /// Signing domain for this module's EAS deployment, read from the chain.
+    pub async fn domain(&self) -> eyre::Result<Eip712Domain> {
+        let (eas_version, chain_id) = self.domain_params().await?;
+        Ok(offchain_domain(
+            &eas_version,
+            chain_id,
+            self.module.addresses.eas,
+        ))
+    }
+
+    async fn domain_params(&self) -> eyre::Result<(String, u64)> {
+        let eas = IEAS::new(self.module.addresses.eas, &self.module.wallet_provider);
+        tokio::try_join!(
+            async { Ok::<_, eyre::Error>(eas.version().call().await?) },
+            async { Ok(self.module.wallet_provider.get_chain_id().await?) },
+        )
+    }
+
+    /// Signs `message` as this module's signer with a fresh random salt.
+    pub async fn sign(
+        &self,
+        message: OffchainAttestationData,
+    ) -> eyre::Result<SignedOffchainAttestation> {
+        let (eas_version, chain_id) = self.domain_params().await?;
+        let eas = self.module.addresses.eas;
+        let salt = generate_salt();
+        let domain = offchain_domain(&eas_version, chain_id, eas);
+
+        let signature = self
+            .module
+            .signer
+            .sign_hash(&signing_hash(&message, salt, &domain))
+            .await?;
+
+        Ok(SignedOffchainAttestation {
+            version: OFFCHAIN_ATTESTATION_VERSION,
+            eas_version,
+            chain_id,
+            verifying_contract: eas,
+            uid: offchain_uid(OFFCHAIN_ATTESTATION_VERSION, &message, salt),
+            message,
+            salt,
+            signer: self.module.signer.address(),
+            signature: signature.as_bytes().into(),
+        })
+    }
+
+    /// Verifies `attestation` and checks it was signed for this module's
+    /// EAS deployment.
+    pub async fn verify(&self, attestation: &SignedOffchainAttestation) -> eyre::Result<()> {
+        attestation.verify()?;
+        let (eas_version, chain_id) = self.domain_params().await?;
+        if attestation.verifying_contract != self.module.addresses.eas
+            || attestation.chain_id != chain_id
+            || attestation.eas_version != eas_version
+        {
+            return Err(eyre::eyre!(
+                "Attestation was signed for another EAS deployment"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Timestamps the attestation's UID on-chain.
+    pub async fn timestamp(
+        &self,
+        attestation: &SignedOffchainAttestation,
+    ) -> eyre::Result<TransactionReceipt> {
+        self.module.util().timestamp(attestation.uid).await
+    }
+
+    /// When the attestation's UID was timestamped, if it was.
+    pub async fn get_timestamp(
+        &self,
+        attestation: &SignedOffchainAttestation,
+    ) -> eyre::Result<Option<u64>> {
+        let time = self.module.util().get_timestamp(attestation.uid).await?;
+        Ok((time != 0).then_some(time))
+    }
+
+    /// Revokes the attestation on-chain. Only counts if this module's signer
+    /// is the attestation's signer.
+    pub async fn revoke(
+        &self,
+        attestation: &SignedOffchainAttestation,
+    ) -> eyre::Result<TransactionReceipt> {
+        if attestation.signer != self.module.signer.address() {
+            return Err(eyre::eyre!(
+                "Only the signer {} can revoke this attestation",
+                attestation.signer
+            ));
+        }
+        self.module.util().revoke_offchain(attestation.uid).await
+    }
+
+    /// When the attestation's signer revoked it, if they did.
+    pub async fn get_revocation(
+        &self,
+        attestation: &SignedOffchainAttestation,
+    ) -> eyre::Result<Option<u64>> {
+        let time = self
+            .module
+            .util()
+            .get_revoke_offchain(attestation.signer, attestation.uid)
+            .await?;
+        Ok((time != 0).then_some(time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::{SignerSync as _, local::PrivateKeySigner};
+
+    fn message() -> OffchainAttestationData {
+        OffchainAttestationData {
+            schema: B256::repeat_byte(0x01),
+            recipient: Address::repeat_byte(0x02),
+            time: 1_700_000_000,
+            expiration_time: 0,
+            revocable: true,
+            ref_uid: B256::ZERO,
+            data: Bytes::from_static(b"hello"),
+        }
+    }
+
+    fn sign_locally(signer: &PrivateKeySigner) -> SignedOffchainAttestation {
+        let eas = Address::repeat_byte(0xea);
+        let salt = B256::repeat_byte(0x5a);
+        let domain = offchain_domain("1.3.0", 31337, eas);
+        let signature = signer
+            .sign_hash_sync(&signing_hash(&message(), salt, &domain))
+            .unwrap();
+        SignedOffchainAttestation {
+            version: OFFCHAIN_ATTESTATION_VERSION,
+            eas_version: "1.3.0".to_string(),
+            chain_id: 31337,
+            verifying_contract: eas,
+            message: message(),
+            salt,
+            uid: offchain_uid(OFFCHAIN_ATTESTATION_VERSION, &message(), salt),
+            signer: signer.address(),
+            signature: signature.as_bytes().into(),
+        }
+    }
+
+    #[test]
+    fn test_verify_accepts_valid_attestation() {
+        let signer = PrivateKeySigner::random();
+        let attestation = sign_locally(&signer);
+        attestation.verify().unwrap();
+
+        // Survives a JSON roundtrip.
+        let json = serde_json::to_string(&attestation).unwrap();
+        let parsed: SignedOffchainAttestation = serde_json::from_str(&json).unwrap();
+        parsed.verify().unwrap();
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let signer = PrivateKeySigner::random();
+
+        let mut wrong_data = sign_locally(&signer);
+        wrong_data.message.data = Bytes::from_static(b"bye");
+        assert!(wrong_data.verify().is_err());
+
+        let mut wrong_signer = sign_locally(&signer);
+        wrong_signer.signer = Address::repeat_byte(0x03);
+        assert!(wrong_signer.verify().is_err());
+
+        let mut wrong_chain = sign_locally(&signer);
+        wrong_chain.chain_id = 1;
+        assert!(wrong_chain.verify().is_err());
+    }
+
+    #[test]
+    fn test_uid_depends_on_salt() {
+        assert_ne!(
+            offchain_uid(2, &message(), B256::repeat_byte(1)),
+            offchain_uid(2, &message(), B256::repeat_byte(2))
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! EAS schema strings as typed ABI encoders.
+//!
+//! EAS schemas are comma-separated Solidity parameter lists such as
+//! `"uint256 score, string note"`. [`SchemaEncoder`] parses one into
+//! [`DynSolType`]s and encodes or decodes attestation data for it the way the
+//! EAS SDK does (`abi.encode` of the fields as a parameter list).
+
+use alloy::{
+    dyn_abi::{DynSolType, DynSolValue},
+    primitives::{Address, B256, Bytes, keccak256},
+};
+
+/// One named field of an EAS schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaField {
+    /// Field name.
+    pub name: String,
+    /// Parsed Solidity type.
+    pub ty: DynSolType,
+}
+
+/// Encoder/decoder for one EAS schema string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaEncoder {
+    schema: String,
+    fields: Vec<SchemaField>,
+}
+
+impl SchemaEncoder {
+    /// Parses an EAS schema string like `"uint256 score, string note"`.
+    ///
+    /// Accepts the EAS `ipfsHash` alias for `bytes32` and the `uint`/`int`
+    /// shorthands.
+    pub fn new(schema: &str) -> eyre::Result<Self> {
+        let fields = split_top_level(schema)
+            .into_iter()
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+            .map(parse_field)
+            .collect::<eyre::Result<Vec<_>>>()?;
+        if fields.is_empty() {
+            return Err(eyre::eyre!("Schema has no fields"));
+        }
+        Ok(Self {
+            schema: schema.to_string(),
+            fields,
+        })
+    }
+
+    /// Schema string as given.
+    pub fn schema(&self) -> &str {
+        &self.schema
+    }
+
+    /// Parsed fields in order.
+    pub fn fields(&self) -> &[SchemaField] {
+        &self.fields
+    }
+
+    /// Encodes one value per field, checking each against its field type.
+    pub fn encode(&self, values: &[DynSolValue]) -> eyre::Result<Bytes> {
+        if values.len() != self.fields.len() {
+            return Err(eyre::eyre!(
+                "Schema has {} fields, got {} values",
+                self.fields.len(),
+                values.len()
+            ));
+        }
+        for (field, value) in self.fields.iter().zip(values) {
+            if !field.ty.matches(value) {
+                return Err(eyre::eyre!(
+                    "Value for `{}` doesn't match type {}",
+                    field.name,
+                    field.ty
+                ));
+            }
+        }
+        Ok(DynSolValue::Tuple(values.to_vec())
+            .abi_encode_params()
+            .into())
+    }
+
+    /// Encodes values given as strings, parsed with each field's type
+    /// (e.g. `"42"`, `"0x…"`, `"[1,2]"`, `"true"`).
+    pub fn encode_str(&self, values: &[&str]) -> eyre::Result<Bytes> {
+        if values.len() != self.fields.len() {
+            return Err(eyre::eyre!(
+                "Schema has {} fields, got {} values",
+                self.fields.len(),
+                values.len()
+            ));
+        }
+        let values = self
+            .fields
+            .iter()
+            .zip(values)
+            .map(|(field, value)| {
+                field.ty.coerce_str(value).map_err(|e| {
+                    eyre::eyre!("Invalid value for `{}` ({}): {e}", field.name, field.ty)
+                })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+        self.encode(&values)
+    }
+
+    /// Decodes attestation data into `(field name, value)` pairs.
+    pub fn decode(&self, data: &[u8]) -> eyre::Result<Vec<(String, DynSolValue)>> {
+        let tuple = DynSolType::Tuple(self.fields.iter().map(|f| f.ty.clone()).collect());
+        let DynSolValue::Tuple(values) = tuple.abi_decode_params(data)? else {
+            return Err(eyre::eyre!("Schema data did not decode to a tuple"));
+        };
+        Ok(self
+            .fields
+            .iter()
+            .map(|field| field.name.clone())
+            .zip(values)
+            .collect())
+    }
+
+    /// UID the Schema Registry assigns to this schema:
+    /// `keccak256(abi.encodePacked(schema, resolver, revocable))`.
+    pub fn schema_uid(&self, resolver: Address, revocable: bool) -> B256 {
+        schema_uid(&self.schema, resolver, revocable)
+    }
+}
+
+/// UID the Schema Registry assigns to `schema` registered with `resolver`
+/// and `revocable`.
+pub fn schema_uid(schema: &str, resolver: Address, revocable: bool) -> B256 {
+    let mut packed = Vec::with_capacity(schema.len() + 21);
+    packed.extend_from_slice(schema.as_bytes());
+    packed.extend_from_slice(resolver.as_slice());
+    packed.push(revocable as u8);
+    keccak256(packed)
+}
+
+/// Splits on commas that aren't inside parentheses.
+fn split_top_level(schema: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in schema.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&schema[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&schema[start..]);
+    parts
+}
+
+fn parse_field(field: &str) -> eyre::Result<SchemaField> {
+    let (ty, name) = field
+        .rsplit_once(char::is_whitespace)
+        .ok_or_else(|| eyre::eyre!("Schema field `{field}` needs a type and a name"))?;
+    let ty = normalize_type(ty.trim());
+    let ty = DynSolType::parse(&ty)
+        .map_err(|e| eyre::eyre!("Invalid type in schema field `{field}`: {e}"))?;
+    Ok(SchemaField {
+        name: name.to_string(),
+        ty,
+    })
+}
+
+fn normalize_type(ty: &str) -> String {
+    // Split off array suffixes so `uint[]` and `ipfsHash[2]` normalize too.
+    let base_end = ty.find('[').unwrap_or(ty.len());
+    let (base, suffix) = ty.split_at(base_end);
+    let base = match base {
+        "ipfsHash" => "bytes32",
+        "uint" => "uint256",
+        "int" => "int256",
+        other => other,
+    };
+    format!("{base}{suffix}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U256;
+
+    #[test]
+    fn test_parse_schema_fields() {
+        let encoder =
+            SchemaEncoder::new("uint256 score, string note, ipfsHash cid, uint[] ids").unwrap();
+        let fields: Vec<_> = encoder
+            .fields()
+            .iter()
+            .map(|f| (f.name.as_str(), f.ty.to_string()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("score", "uint256".to_string()),
+                ("note", "string".to_string()),
+                ("cid", "bytes32".to_string()),
+                ("ids", "uint256[]".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_schemas() {
+        assert!(SchemaEncoder::new("").is_err());
+        assert!(SchemaEncoder::new("uint256").is_err());
+        assert!(SchemaEncoder::new("notatype x").is_err());
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let encoder = SchemaEncoder::new("uint256 score, string note").unwrap();
+        let data = encoder
+            .encode(&[
+                DynSolValue::Uint(U256::from(7), 256),
+                DynSolValue::String("good".into()),
+            ])
+            .unwrap();
+
+        // Matches `abi.encode(uint256, string)`.
+        use alloy::sol_types::SolValue as _;
+        assert_eq!(
+            data.as_ref(),
+            (U256::from(7), "good".to_string()).abi_encode_params()
+        );
+
+        let decoded = encoder.decode(&data).unwrap();
+        assert_eq!(decoded[0].0, "score");
+        assert_eq!(decoded[0].1, DynSolValue::Uint(U256::from(7), 256));
+        assert_eq!(decoded[1].1, DynSolValue::String("good".into()));
+
+        assert_eq!(encoder.encode_str(&["7", "good"]).unwrap(), data);
+    }
+
+    #[test]
+    fn test_encode_rejects_mismatched_values() {
+        let encoder = SchemaEncoder::new("uint256 score, string note").unwrap();
+        assert!(encoder.encode(&[DynSolValue::Bool(true)]).is_err());
+        assert!(
+            encoder
+                .encode(&[DynSolValue::Bool(true), DynSolValue::String("x".into())])
+                .is_err()
+        );
+        assert!(encoder.encode_str(&["not a number", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_schema_uid_is_packed_keccak() {
+        let resolver = Address::repeat_byte(0x11);
+        let mut packed = b"uint256 score".to_vec();
+        packed.extend_from_slice(resolver.as_slice());
+        packed.push(1);
+        assert_eq!(
+            schema_uid("uint256 score", resolver, true),
+            keccak256(packed)
+        );
+    }
+}
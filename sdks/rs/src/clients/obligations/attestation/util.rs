@@ -1,12 +1,17 @@
 //! Attestation utility functions
 //!
-//! Core EAS operations: getting attestations, registering schemas, creating attestations.
+//! Core EAS operations: getting attestations, registering schemas, creating,
+//! revoking and timestamping attestations.
 
-use alloy::primitives::{Address, FixedBytes};
+use alloy::primitives::{Address, FixedBytes, U256};
 use alloy::rpc::types::TransactionReceipt;
+use alloy::sol_types::SolEvent as _;
 
 use crate::contracts;
-use crate::contracts::IEAS::{Attestation, AttestationRequest};
+use crate::contracts::IEAS::{
+    Attestation, AttestationRequest, Attested, MultiAttestationRequest, MultiRevocationRequest,
+    RevocationRequest,
+};
 
 use super::AttestationModule;
 
@@ -73,4 +78,160 @@ impl<'a> Util<'a> {
 
         Ok(receipt)
     }
+
+    /// Creates attestations for several schemas in one transaction.
+    ///
+    /// Sends the sum of all request values with the call. Use
+    /// [`Util::attested_uids`] to read the new UIDs from the receipt.
+    pub async fn multi_attest(
+        &self,
+        requests: Vec<MultiAttestationRequest>,
+    ) -> eyre::Result<TransactionReceipt> {
+        let eas_contract =
+            contracts::IEAS::new(self.module.addresses.eas, &self.module.wallet_provider);
+        let value = requests
+            .iter()
+            .flat_map(|request| &request.data)
+            .fold(U256::ZERO, |total, data| total + data.value);
+
+        let receipt = eas_contract
+            .multiAttest(requests)
+            .value(value)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// UIDs of every attestation created in `receipt`, in log order.
+    pub fn attested_uids(receipt: &TransactionReceipt) -> Vec<FixedBytes<32>> {
+        receipt
+            .inner
+            .logs()
+            .iter()
+            .filter(|log| log.topic0() == Some(&Attested::SIGNATURE_HASH))
+            .filter_map(|log| log.log_decode::<Attested>().ok())
+            .map(|log| log.inner.uid)
+            .collect()
+    }
+
+    /// Revokes an attestation. Only its attester can revoke it, and only if
+    /// it is revocable.
+    pub async fn revoke(&self, request: RevocationRequest) -> eyre::Result<TransactionReceipt> {
+        let eas_contract =
+            contracts::IEAS::new(self.module.addresses.eas, &self.module.wallet_provider);
+        let value = request.data.value;
+
+        let receipt = eas_contract
+            .revoke(request)
+            .value(value)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// Revokes attestations across several schemas in one transaction.
+    pub async fn multi_revoke(
+        &self,
+        requests: Vec<MultiRevocationRequest>,
+    ) -> eyre::Result<TransactionReceipt> {
+        let eas_contract =
+            contracts::IEAS::new(self.module.addresses.eas, &self.module.wallet_provider);
+        let value = requests
+            .iter()
+            .flat_map(|request| &request.data)
+            .fold(U256::ZERO, |total, data| total + data.value);
+
+        let receipt = eas_contract
+            .multiRevoke(requests)
+            .value(value)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// Timestamps `data` (e.g. an off-chain attestation UID) on-chain.
+    /// Each value can only be timestamped once.
+    pub async fn timestamp(&self, data: FixedBytes<32>) -> eyre::Result<TransactionReceipt> {
+        let eas_contract =
+            contracts::IEAS::new(self.module.addresses.eas, &self.module.wallet_provider);
+
+        let receipt = eas_contract
+            .timestamp(data)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// Timestamps several values in one transaction.
+    pub async fn multi_timestamp(
+        &self,
+        data: Vec<FixedBytes<32>>,
+    ) -> eyre::Result<TransactionReceipt> {
+        let eas_contract =
+            contracts::IEAS::new(self.module.addresses.eas, &self.module.wallet_provider);
+
+        let receipt = eas_contract
+            .multiTimestamp(data)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// When `data` was timestamped, or `0` if it never was.
+    pub async fn get_timestamp(&self, data: FixedBytes<32>) -> eyre::Result<u64> {
+        let eas_contract =
+            contracts::IEAS::new(self.module.addresses.eas, &self.module.wallet_provider);
+
+        Ok(eas_contract.getTimestamp(data).call().await?)
+    }
+
+    /// Records the revocation of an off-chain attestation UID by the signer.
+    pub async fn revoke_offchain(&self, data: FixedBytes<32>) -> eyre::Result<TransactionReceipt> {
+        let eas_contract =
+            contracts::IEAS::new(self.module.addresses.eas, &self.module.wallet_provider);
+
+        let receipt = eas_contract
+            .revokeOffchain(data)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// When `revoker` revoked the off-chain UID `data`, or `0` if they didn't.
+    pub async fn get_revoke_offchain(
+        &self,
+        revoker: Address,
+        data: FixedBytes<32>,
+    ) -> eyre::Result<u64> {
+        let eas_contract =
+            contracts::IEAS::new(self.module.addresses.eas, &self.module.wallet_provider);
+
+        Ok(eas_contract.getRevokeOffchain(revoker, data).call().await?)
+    }
+
+    /// Whether an attestation with `uid` exists.
+    pub async fn is_attestation_valid(&self, uid: FixedBytes<32>) -> eyre::Result<bool> {
+        let eas_contract =
+            contracts::IEAS::new(self.module.addresses.eas, &self.module.wallet_provider);
+
+        Ok(eas_contract.isAttestationValid(uid).call().await?)
+    }
 }
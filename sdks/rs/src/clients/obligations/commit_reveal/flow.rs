@@ -0,0 +1,467 @@
+//! Resumable commit → wait → reveal → collect orchestration.
+//!
+//! [`CommitRevealFlow::commit_then_reveal`] drives the whole protocol for one
+//! escrow. Every step is checkpointed to a [`RevealStateStore`] before the
+//! transaction that depends on it is sent, so a process that crashes between
+//! committing and revealing can call [`CommitRevealFlow::resume`] and finish
+//! the reveal before the commitment's deadline passes.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use alloy::{
+    primitives::{Address, Bytes, FixedBytes, U256},
+    providers::Provider as _,
+    rpc::types::TransactionReceipt,
+    sol,
+    sol_types::SolValue as _,
+};
+use serde::{Deserialize, Serialize};
+
+use super::CommitRevealObligationModule;
+use crate::{
+    contracts::{self, obligations::CommitRevealObligation},
+    utils::wait_for_block_after,
+};
+
+/// How long [`CommitRevealFlow`] waits for a block after the commit block
+/// before giving up on the reveal.
+const NEXT_BLOCK_TIMEOUT: Duration = Duration::from_secs(120);
+
+sol! {
+    /// Leading fields shared by every escrow obligation's data.
+    struct EscrowArbiterPrefix {
+        address arbiter;
+        bytes demand;
+    }
+}
+
+/// How far a persisted commit-reveal has progressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RevealStage {
+    /// State was saved but the commit transaction may not have landed.
+    Committing,
+    /// The commitment is on-chain and the bond is locked.
+    Committed,
+}
+
+/// Everything needed to finish a reveal after a restart.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingReveal {
+    /// Escrow being fulfilled; also the store key.
+    pub escrow_uid: FixedBytes<32>,
+    /// Escrow contract that created `escrow_uid` and will be collected from.
+    pub escrow_contract: Address,
+    /// Commitment hash posted on-chain.
+    pub commitment: FixedBytes<32>,
+    /// Address that committed and will receive the fulfillment.
+    pub claimer: Address,
+    /// Revealed payload.
+    pub payload: Bytes,
+    /// Salt hiding the payload until reveal.
+    pub salt: FixedBytes<32>,
+    /// Schema tag of the payload.
+    pub schema: FixedBytes<32>,
+    /// Bond locked by the commit, taken from the escrow demand.
+    pub bond_amount: U256,
+    /// Reveal window in seconds after the commit, taken from the escrow demand.
+    pub commit_deadline: U256,
+    /// Current progress.
+    pub stage: RevealStage,
+}
+
+impl PendingReveal {
+    /// Rebuilds the obligation data to reveal.
+    pub fn obligation_data(&self) -> CommitRevealObligation::ObligationData {
+        CommitRevealObligation::ObligationData {
+            payload: self.payload.clone(),
+            salt: self.salt,
+            schema: self.schema,
+        }
+    }
+}
+
+/// Persistence backend for in-flight commit-reveals, keyed by escrow UID.
+pub trait RevealStateStore: Send + Sync {
+    /// Loads the pending reveal for `escrow_uid`, if one was saved.
+    fn load(&self, escrow_uid: FixedBytes<32>) -> eyre::Result<Option<PendingReveal>>;
+    /// Inserts or replaces the pending reveal for `state.escrow_uid`.
+    fn save(&self, state: &PendingReveal) -> eyre::Result<()>;
+    /// Forgets the pending reveal for `escrow_uid`. Missing entries are not an error.
+    fn remove(&self, escrow_uid: FixedBytes<32>) -> eyre::Result<()>;
+    /// Lists every saved pending reveal.
+    fn list(&self) -> eyre::Result<Vec<PendingReveal>>;
+}
+
+/// Stores each pending reveal as `<escrow_uid>.json` in a directory.
+///
+/// Files contain the unrevealed payload and salt in plain text, so anyone
+/// who can read the directory can front-run the reveal. Don't use it for
+/// secret payloads; use [`VaultRevealStore`](super::VaultRevealStore), which
+/// encrypts the same state in a [`SaltVault`](super::SaltVault).
+#[derive(Debug, Clone)]
+pub struct FileRevealStore {
+    dir: PathBuf,
+}
+
+impl FileRevealStore {
+    /// Uses `dir` for state files, creating it if needed.
+    pub fn new(dir: impl AsRef<Path>) -> eyre::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, escrow_uid: FixedBytes<32>) -> PathBuf {
+        self.dir.join(format!("{escrow_uid}.json"))
+    }
+}
+
+impl RevealStateStore for FileRevealStore {
+    fn load(&self, escrow_uid: FixedBytes<32>) -> eyre::Result<Option<PendingReveal>> {
+        match std::fs::read(self.path(escrow_uid)) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, state: &PendingReveal) -> eyre::Result<()> {
+        // Write then rename so a crash never leaves a truncated state file.
+        let path = self.path(state.escrow_uid);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(state)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    fn remove(&self, escrow_uid: FixedBytes<32>) -> eyre::Result<()> {
+        match std::fs::remove_file(self.path(escrow_uid)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn list(&self) -> eyre::Result<Vec<PendingReveal>> {
+        let mut states = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                states.push(serde_json::from_slice(&std::fs::read(&path)?)?);
+            }
+        }
+        Ok(states)
+    }
+}
+
+/// Keeps pending reveals in memory. Useful for tests and short-lived
+/// processes that don't need crash recovery.
+#[derive(Debug, Default)]
+pub struct MemoryRevealStore {
+    states: Mutex<HashMap<FixedBytes<32>, PendingReveal>>,
+}
+
+impl MemoryRevealStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RevealStateStore for MemoryRevealStore {
+    fn load(&self, escrow_uid: FixedBytes<32>) -> eyre::Result<Option<PendingReveal>> {
+        let states = self
+            .states
+            .lock()
+            .map_err(|_| eyre::eyre!("Reveal store lock poisoned"))?;
+        Ok(states.get(&escrow_uid).cloned())
+    }
+
+    fn save(&self, state: &PendingReveal) -> eyre::Result<()> {
+        self.states
+            .lock()
+            .map_err(|_| eyre::eyre!("Reveal store lock poisoned"))?
+            .insert(state.escrow_uid, state.clone());
+        Ok(())
+    }
+
+    fn remove(&self, escrow_uid: FixedBytes<32>) -> eyre::Result<()> {
+        self.states
+            .lock()
+            .map_err(|_| eyre::eyre!("Reveal store lock poisoned"))?
+            .remove(&escrow_uid);
+        Ok(())
+    }
+
+    fn list(&self) -> eyre::Result<Vec<PendingReveal>> {
+        let states = self
+            .states
+            .lock()
+            .map_err(|_| eyre::eyre!("Reveal store lock poisoned"))?;
+        Ok(states.values().cloned().collect())
+    }
+}
+
+/// Result of a completed commit-reveal.
+#[derive(Debug, Clone)]
+pub struct RevealOutcome {
+    /// Commitment that was revealed.
+    pub commitment: FixedBytes<32>,
+    /// Fulfillment attestation created by the reveal.
+    pub fulfillment_uid: FixedBytes<32>,
+    /// Bond returned to the claimer.
+    pub bond_reclaimed: U256,
+    /// Commit receipt, when the commit was sent by this call.
+    pub commit_receipt: Option<TransactionReceipt>,
+    /// Reveal-and-collect receipt.
+    pub reveal_receipt: TransactionReceipt,
+}
+
+/// Commit-reveal orchestration backed by a [`RevealStateStore`].
+pub struct CommitRevealFlow<'a, S: RevealStateStore> {
+    pub(super) module: &'a CommitRevealObligationModule,
+    pub(super) store: &'a S,
+}
+
+impl<'a, S: RevealStateStore> CommitRevealFlow<'a, S> {
+    /// Commits to `data` against `escrow_uid`, waits a block, reveals, and
+    /// collects the escrow, returning once the bond has been reclaimed.
+    ///
+    /// Bond amount and reveal deadline come from the escrow's
+    /// `CommitRevealObligation` demand. If a reveal for `escrow_uid` is
+    /// already pending in the store this resumes it instead, provided it
+    /// carries the same `data`.
+    pub async fn commit_then_reveal(
+        &self,
+        escrow_uid: FixedBytes<32>,
+        data: CommitRevealObligation::ObligationData,
+    ) -> eyre::Result<RevealOutcome> {
+        if let Some(state) = self.store.load(escrow_uid)? {
+            if (&state.payload, state.salt, state.schema) != (&data.payload, data.salt, data.schema)
+            {
+                return Err(eyre::eyre!(
+                    "A different reveal is already pending for escrow {escrow_uid}"
+                ));
+            }
+            return self.drive(state).await;
+        }
+
+        let (escrow_contract, demand) = self.escrow_demand(escrow_uid).await?;
+        let claimer = self.module.signer.address();
+        let commitment = CommitRevealObligationModule::commitment_hash(escrow_uid, claimer, &data);
+        let state = PendingReveal {
+            escrow_uid,
+            escrow_contract,
+            commitment,
+            claimer,
+            payload: data.payload,
+            salt: data.salt,
+            schema: data.schema,
+            bond_amount: demand.bondAmount,
+            commit_deadline: demand.commitDeadline,
+            stage: RevealStage::Committing,
+        };
+        // Persist the salt before anything goes on-chain: losing it after
+        // committing means losing the bond.
+        self.store.save(&state)?;
+        self.drive(state).await
+    }
+
+    /// Finishes the pending reveal for `escrow_uid`, if the store has one.
+    pub async fn resume(&self, escrow_uid: FixedBytes<32>) -> eyre::Result<Option<RevealOutcome>> {
+        match self.store.load(escrow_uid)? {
+            Some(state) => Ok(Some(self.drive(state).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Finishes every pending reveal in the store, returning one result per
+    /// escrow. Failures don't stop the remaining reveals.
+    pub async fn resume_all(
+        &self,
+    ) -> eyre::Result<Vec<(FixedBytes<32>, eyre::Result<RevealOutcome>)>> {
+        let mut results = Vec::new();
+        for state in self.store.list()? {
+            let escrow_uid = state.escrow_uid;
+            results.push((escrow_uid, self.drive(state).await));
+        }
+        Ok(results)
+    }
+
+    async fn drive(&self, mut state: PendingReveal) -> eyre::Result<RevealOutcome> {
+        let claimer = self.module.signer.address();
+        if state.claimer != claimer {
+            return Err(eyre::eyre!(
+                "Pending reveal was committed by {}, but this client signs as {claimer}",
+                state.claimer
+            ));
+        }
+
+        let mut commit_receipt = None;
+        let (_, _, committer, _, _) = self.module.get_commitment(state.commitment).await?;
+        if committer == Address::ZERO {
+            let receipt = self
+                .module
+                .commit(state.commitment, state.bond_amount, state.commit_deadline)
+                .await?;
+            if !receipt.status() {
+                return Err(eyre::eyre!("Commit transaction reverted"));
+            }
+            commit_receipt = Some(receipt);
+        } else if committer != claimer {
+            return Err(eyre::eyre!(
+                "Commitment {} is held by {committer}",
+                state.commitment
+            ));
+        }
+        if state.stage != RevealStage::Committed {
+            state.stage = RevealStage::Committed;
+            self.store.save(&state)?;
+        }
+
+        if self.module.is_commitment_claimed(state.commitment).await? {
+            self.store.remove(state.escrow_uid)?;
+            return Err(eyre::eyre!(
+                "Commitment {} was already revealed or slashed",
+                state.commitment
+            ));
+        }
+
+        let (commit_block, commit_timestamp, _, _, commit_deadline) =
+            self.module.get_commitment(state.commitment).await?;
+        // The contract rejects reveals mined in the commit block.
+        wait_for_block_after(
+            &self.module.public_provider,
+            commit_block,
+            self.module.poll_interval,
+            NEXT_BLOCK_TIMEOUT,
+        )
+        .await?;
+
+        let reveal_by = U256::from(commit_timestamp).saturating_add(commit_deadline);
+        let latest = self
+            .module
+            .public_provider
+            .get_block_by_number(alloy::eips::BlockNumberOrTag::Latest)
+            .await?
+            .ok_or_else(|| eyre::eyre!("Latest block not found"))?;
+        if U256::from(latest.header.timestamp) > reveal_by {
+            return Err(eyre::eyre!(
+                "Reveal deadline for commitment {} passed at {reveal_by}",
+                state.commitment
+            ));
+        }
+
+        let reveal_receipt = self
+            .module
+            .reveal_and_collect(
+                state.obligation_data(),
+                claimer,
+                state.escrow_contract,
+                state.escrow_uid,
+            )
+            .await?;
+        if !reveal_receipt.status() {
+            return Err(eyre::eyre!("Reveal transaction reverted"));
+        }
+
+        let reclaimed = reveal_receipt
+            .decoded_log::<CommitRevealObligation::BondReclaimed>()
+            .ok_or_else(|| eyre::eyre!("No BondReclaimed event found"))?;
+        if !self.module.is_commitment_claimed(state.commitment).await? {
+            return Err(eyre::eyre!(
+                "Commitment {} not marked claimed after reveal",
+                state.commitment
+            ));
+        }
+        self.store.remove(state.escrow_uid)?;
+
+        Ok(RevealOutcome {
+            commitment: state.commitment,
+            fulfillment_uid: reclaimed.fulfillmentUid,
+            bond_reclaimed: reclaimed.amount,
+            commit_receipt,
+            reveal_receipt,
+        })
+    }
+
+    /// Reads the escrow contract and commit-reveal demand for `escrow_uid`,
+    /// failing if the escrow isn't arbitrated by this obligation contract.
+    async fn escrow_demand(
+        &self,
+        escrow_uid: FixedBytes<32>,
+    ) -> eyre::Result<(Address, CommitRevealObligation::DemandData)> {
+        let eas = contracts::IEAS::new(self.module.addresses.eas, &*self.module.public_provider);
+        let escrow = eas.getAttestation(escrow_uid).call().await?;
+        if escrow.uid == FixedBytes::<32>::ZERO {
+            return Err(eyre::eyre!("Escrow {escrow_uid} not found"));
+        }
+        let prefix = EscrowArbiterPrefix::abi_decode(&escrow.data)?;
+        if prefix.arbiter != self.module.addresses.obligation {
+            return Err(eyre::eyre!(
+                "Escrow {escrow_uid} is arbitrated by {}, not the commit-reveal obligation",
+                prefix.arbiter
+            ));
+        }
+        let demand = CommitRevealObligationModule::decode_demand(&prefix.demand)?;
+        Ok((escrow.attester, demand))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(escrow_uid: FixedBytes<32>) -> PendingReveal {
+        PendingReveal {
+            escrow_uid,
+            escrow_contract: Address::repeat_byte(0x01),
+            commitment: FixedBytes::repeat_byte(0x02),
+            claimer: Address::repeat_byte(0x03),
+            payload: Bytes::from(vec![0xde, 0xad]),
+            salt: FixedBytes::repeat_byte(0x04),
+            schema: FixedBytes::repeat_byte(0x05),
+            bond_amount: U256::from(100),
+            commit_deadline: U256::from(3600),
+            stage: RevealStage::Committing,
+        }
+    }
+
+    #[test]
+    fn test_file_store_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileRevealStore::new(dir.path()).unwrap();
+        let uid = FixedBytes::repeat_byte(0xaa);
+        assert!(store.load(uid).unwrap().is_none());
+
+        let mut state = sample(uid);
+        store.save(&state).unwrap();
+        state.stage = RevealStage::Committed;
+        store.save(&state).unwrap();
+        assert_eq!(store.load(uid).unwrap(), Some(state.clone()));
+        assert_eq!(store.list().unwrap(), vec![state]);
+
+        // A fresh store over the same directory sees the saved state.
+        let reopened = FileRevealStore::new(dir.path()).unwrap();
+        assert!(reopened.load(uid).unwrap().is_some());
+
+        store.remove(uid).unwrap();
+        store.remove(uid).unwrap();
+        assert!(store.load(uid).unwrap().is_none());
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_memory_store_roundtrip() {
+        let store = MemoryRevealStore::new();
+        let uid = FixedBytes::repeat_byte(0xbb);
+        store.save(&sample(uid)).unwrap();
+        assert_eq!(store.load(uid).unwrap(), Some(sample(uid)));
+        store.remove(uid).unwrap();
+        assert!(store.list().unwrap().is_empty());
+    }
+}
@@ -3,14 +3,26 @@ use crate::{
     contracts,
     extensions::{AlkahestExtension, ContractModule},
     impl_abi_conversions,
-    types::{DecodedAttestation, ProviderContext, SharedWalletProvider},
+    types::{DecodedAttestation, ProviderContext, SharedPublicProvider, SharedWalletProvider},
 };
 
+pub mod flow;
+pub mod salt;
+pub mod slasher;
+
+pub use flow::{
+    CommitRevealFlow, FileRevealStore, MemoryRevealStore, PendingReveal, RevealOutcome,
+    RevealStage, RevealStateStore,
+};
+pub use salt::{SaltRecord, SaltVault, VaultRevealStore, generate_salt};
+pub use slasher::{BondSlasher, CommitmentIndex, SlashReport, SlasherHandle, TrackedCommitment};
+
 impl_abi_conversions!(contracts::obligations::CommitRevealObligation::ObligationData);
 impl_abi_conversions!(contracts::obligations::CommitRevealObligation::DemandData);
 
 use alloy::{
-    primitives::{Address, Bytes, FixedBytes, U256},
+    primitives::{Address, Bytes, FixedBytes, U256, keccak256},
+    providers::{Provider as _, ProviderBuilder},
     rpc::types::TransactionReceipt,
     signers::local::PrivateKeySigner,
     sol_types::SolValue as _,
@@ -32,8 +44,10 @@ pub struct CommitRevealObligationAddresses {
 /// manual audits and has only been reviewed by automated audit tooling so far.
 #[derive(Clone)]
 pub struct CommitRevealObligationModule {
-    _signer: PrivateKeySigner,
-    wallet_provider: SharedWalletProvider,
+    pub(crate) signer: PrivateKeySigner,
+    pub(crate) public_provider: SharedPublicProvider,
+    pub(crate) wallet_provider: SharedWalletProvider,
+    pub(crate) poll_interval: std::time::Duration,
 
     pub addresses: CommitRevealObligationAddresses,
 }
@@ -67,14 +81,42 @@ impl ContractModule for CommitRevealObligationModule {
 
 impl CommitRevealObligationModule {
     /// Creates a commit-reveal module with optional custom addresses.
+    ///
+    /// Reads go through `wallet_provider`'s transport and event watchers
+    /// poll at [`DEFAULT_POLL_INTERVAL`](crate::utils::DEFAULT_POLL_INTERVAL)
+    /// on HTTP. Use [`CommitRevealObligationModule::with_providers`] to pass a separate read
+    /// provider and poll interval.
     pub fn new(
         signer: PrivateKeySigner,
         wallet_provider: SharedWalletProvider,
         addresses: Option<CommitRevealObligationAddresses>,
+    ) -> eyre::Result<Self> {
+        let public_provider = std::sync::Arc::new(
+            ProviderBuilder::new().connect_provider(wallet_provider.root().clone()),
+        );
+        Self::with_providers(
+            signer,
+            public_provider,
+            wallet_provider,
+            crate::utils::DEFAULT_POLL_INTERVAL,
+            addresses,
+        )
+    }
+
+    /// Creates the module with explicit read and wallet providers and the
+    /// poll interval used by event watchers on HTTP transports.
+    pub fn with_providers(
+        signer: PrivateKeySigner,
+        public_provider: SharedPublicProvider,
+        wallet_provider: SharedWalletProvider,
+        poll_interval: std::time::Duration,
+        addresses: Option<CommitRevealObligationAddresses>,
     ) -> eyre::Result<Self> {
         Ok(CommitRevealObligationModule {
-            _signer: signer,
+            signer,
+            public_provider,
             wallet_provider,
+            poll_interval,
             addresses: addresses.unwrap_or_default(),
         })
     }
@@ -276,6 +318,106 @@ impl CommitRevealObligationModule {
         Ok(result)
     }
 
+    /// Computes the commitment hash locally, without an RPC round trip.
+    ///
+    /// Matches `computeCommitment`:
+    /// `keccak256(abi.encode(refUID, claimer, keccak256(abi.encode(data))))`.
+    pub fn commitment_hash(
+        ref_uid: FixedBytes<32>,
+        claimer: Address,
+        data: &contracts::obligations::CommitRevealObligation::ObligationData,
+    ) -> FixedBytes<32> {
+        let data_hash = keccak256(Self::encode(data));
+        keccak256((ref_uid, claimer, data_hash).abi_encode_params())
+    }
+
+    /// Commit-reveal orchestration that checkpoints progress to `store`.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let store = FileRevealStore::new("./pending-reveals")?;
+    /// let outcome = client
+    ///     .commit_reveal()
+    ///     .flow(&store)
+    ///     .commit_then_reveal(escrow_uid, data)
+    ///     .await?;
+    /// ```
+    pub fn flow<'a, S: RevealStateStore>(&'a self, store: &'a S) -> CommitRevealFlow<'a, S> {
+        CommitRevealFlow {
+            module: self,
+            store,
+        }
+    }
+
+    /// Builds obligation data around `payload` with a freshly generated salt.
+    pub fn sealed_data(
+        payload: Bytes,
+        schema: FixedBytes<32>,
+    ) -> contracts::obligations::CommitRevealObligation::ObligationData {
+        contracts::obligations::CommitRevealObligation::ObligationData {
+            payload,
+            salt: generate_salt(),
+            schema,
+        }
+    }
+
+    /// Records `data` in `vault` and then commits to it against `escrow_uid`.
+    ///
+    /// The record is written before the commit is sent, so the salt survives
+    /// a crash. Refuses to commit if `data.salt` was already used for
+    /// another commitment in the vault, or if this commitment is already
+    /// on-chain.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let mut vault = SaltVault::open("./salts.vault", &passphrase)?;
+    /// let data = CommitRevealObligationModule::sealed_data(payload, schema);
+    /// let (commitment, _) = client
+    ///     .commit_reveal()
+    ///     .commit_with_vault(&mut vault, escrow_uid, data, bond, deadline)
+    ///     .await?;
+    /// // Later, possibly after a restart:
+    /// let data = vault.recover(commitment)?;
+    /// ```
+    pub async fn commit_with_vault(
+        &self,
+        vault: &mut SaltVault,
+        escrow_uid: FixedBytes<32>,
+        data: contracts::obligations::CommitRevealObligation::ObligationData,
+        bond_amount: U256,
+        commit_deadline: U256,
+    ) -> eyre::Result<(FixedBytes<32>, TransactionReceipt)> {
+        let commitment = Self::commitment_hash(escrow_uid, self.signer.address(), &data);
+        vault.insert(SaltRecord {
+            commitment,
+            escrow_uid,
+            payload: data.payload,
+            salt: data.salt,
+            schema: data.schema,
+        })?;
+        // Re-inserting an identical record is allowed so a crashed commit can
+        // be retried, but never once the commitment is on-chain.
+        let (_, _, committer, _, _) = self.get_commitment(commitment).await?;
+        if committer != Address::ZERO {
+            return Err(eyre::eyre!("Commitment {commitment} was already posted"));
+        }
+
+        let receipt = self
+            .commit(commitment, bond_amount, commit_deadline)
+            .await?;
+        Ok((commitment, receipt))
+    }
+
+    /// Watcher that slashes commitments left unrevealed past their deadline.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let reports = client.commit_reveal().slasher().dry_run(true).run_once().await?;
+    /// ```
+    pub fn slasher(&self) -> BondSlasher {
+        BondSlasher::new(self.clone())
+    }
+
     /// Slashes an unrevealed commitment after its reveal deadline has passed.
     pub async fn slash_bond(&self, commitment: FixedBytes<32>) -> eyre::Result<TransactionReceipt> {
         let contract = contracts::obligations::CommitRevealObligation::new(
@@ -429,6 +571,29 @@ mod tests {
         assert_eq!(decoded.commitDeadline, data.commitDeadline);
     }
 
+    #[test]
+    fn test_commitment_hash_matches_contract_encoding() {
+        let data = contracts::obligations::CommitRevealObligation::ObligationData {
+            payload: Bytes::from(vec![0xde, 0xad, 0xbe, 0xef]),
+            salt: FixedBytes::<32>::from([0x11; 32]),
+            schema: FixedBytes::<32>::from([0x44; 32]),
+        };
+        let ref_uid = FixedBytes::<32>::from([0x22; 32]);
+        let claimer = Address::repeat_byte(0x33);
+
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(ref_uid.as_slice());
+        preimage.extend_from_slice(&[0u8; 12]);
+        preimage.extend_from_slice(claimer.as_slice());
+        preimage
+            .extend_from_slice(keccak256(CommitRevealObligationModule::encode(&data)).as_slice());
+
+        assert_eq!(
+            CommitRevealObligationModule::commitment_hash(ref_uid, claimer, &data),
+            keccak256(preimage)
+        );
+    }
+
     #[test]
     fn test_default_addresses() {
         let addresses = CommitRevealObligationAddresses::default();
@@ -457,6 +622,12 @@ impl AlkahestExtension for CommitRevealObligationModule {
         providers: ProviderContext,
         config: Option<Self::Config>,
     ) -> eyre::Result<Self> {
-        Self::new(signer, providers.wallet.clone(), config)
+        Self::with_providers(
+            signer,
+            providers.public.clone(),
+            providers.wallet.clone(),
+            providers.poll_interval,
+            config,
+        )
     }
 }
@@ -0,0 +1,489 @@
+//! Salt generation and encrypted salt storage for commit-reveal.
+//!
+//! A commitment hides its payload behind a random salt. If the salt is lost
+//! before the reveal, the bond is lost with it, so [`SaltVault`] keeps every
+//! (payload, salt, schema, escrow UID) tuple in a passphrase-encrypted file
+//! keyed by commitment. The file is sealed with AES-256-GCM under a key
+//! derived from the passphrase with Argon2id. [`VaultRevealStore`] keeps
+//! [`CommitRevealFlow`](super::CommitRevealFlow) state in the same vault.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use aes_gcm::{
+    Aes256Gcm, KeyInit as _,
+    aead::{Aead as _, OsRng, rand_core::RngCore as _},
+};
+use alloy::primitives::{Bytes, FixedBytes, keccak256};
+use serde::{Deserialize, Serialize};
+
+use super::flow::{PendingReveal, RevealStateStore};
+use crate::contracts::obligations::CommitRevealObligation;
+pub use crate::utils::generate_salt;
+
+const VAULT_VERSION: u32 = 1;
+
+/// Everything needed to reveal one commitment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaltRecord {
+    /// Commitment hash; the vault key.
+    pub commitment: FixedBytes<32>,
+    /// Escrow the commitment was made against.
+    pub escrow_uid: FixedBytes<32>,
+    /// Payload to reveal.
+    pub payload: Bytes,
+    /// Salt hiding the payload.
+    pub salt: FixedBytes<32>,
+    /// Schema tag of the payload.
+    pub schema: FixedBytes<32>,
+}
+
+impl SaltRecord {
+    /// Rebuilds the obligation data to reveal.
+    pub fn obligation_data(&self) -> CommitRevealObligation::ObligationData {
+        CommitRevealObligation::ObligationData {
+            payload: self.payload.clone(),
+            salt: self.salt,
+            schema: self.schema,
+        }
+    }
+}
+
+/// Encrypted vault contents.
+#[derive(Serialize, Deserialize)]
+struct VaultContents {
+    records: Vec<SaltRecord>,
+    /// keccak256 of every salt ever inserted, including removed records.
+    used_salts: BTreeSet<FixedBytes<32>>,
+    /// In-flight commit-reveal flows, see [`VaultRevealStore`].
+    #[serde(default)]
+    pending: Vec<PendingReveal>,
+}
+
+/// On-disk envelope. Everything except the KDF salt and nonce is encrypted.
+#[derive(Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    kdf_salt: Bytes,
+    nonce: Bytes,
+    ciphertext: Bytes,
+}
+
+/// Passphrase-encrypted store of [`SaltRecord`]s keyed by commitment.
+///
+/// Every mutation re-encrypts and rewrites the whole file with a fresh
+/// nonce, so the file on disk always matches the in-memory records. The
+/// vault also remembers a hash of every salt it has stored, so removing a
+/// record after its reveal doesn't free its salt for reuse.
+pub struct SaltVault {
+    path: PathBuf,
+    key: [u8; 32],
+    kdf_salt: [u8; 16],
+    records: BTreeMap<FixedBytes<32>, SaltRecord>,
+    used_salts: BTreeSet<FixedBytes<32>>,
+    pending: BTreeMap<FixedBytes<32>, PendingReveal>,
+}
+
+impl std::fmt::Debug for SaltVault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SaltVault")
+            .field("path", &self.path)
+            .field("records", &self.records.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SaltVault {
+    /// Opens the vault at `path`, creating an empty one if the file doesn't
+    /// exist. Fails if the file exists but `passphrase` doesn't decrypt it.
+    pub fn open(path: impl AsRef<Path>, passphrase: &str) -> eyre::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let mut kdf_salt = [0u8; 16];
+                OsRng.fill_bytes(&mut kdf_salt);
+                let vault = Self {
+                    key: derive_key(passphrase, &kdf_salt)?,
+                    path,
+                    kdf_salt,
+                    records: BTreeMap::new(),
+                    used_salts: BTreeSet::new(),
+                    pending: BTreeMap::new(),
+                };
+                vault.persist()?;
+                return Ok(vault);
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let file: VaultFile = serde_json::from_slice(&bytes)?;
+        if file.version != VAULT_VERSION {
+            return Err(eyre::eyre!(
+                "Unsupported salt vault version {}",
+                file.version
+            ));
+        }
+        let kdf_salt: [u8; 16] = file
+            .kdf_salt
+            .as_ref()
+            .try_into()
+            .map_err(|_| eyre::eyre!("Malformed salt vault KDF salt"))?;
+        let nonce: [u8; 12] = file
+            .nonce
+            .as_ref()
+            .try_into()
+            .map_err(|_| eyre::eyre!("Malformed salt vault nonce"))?;
+        let key = derive_key(passphrase, &kdf_salt)?;
+        let plaintext = Aes256Gcm::new(&key.into())
+            .decrypt(&nonce.into(), file.ciphertext.as_ref())
+            .map_err(|_| eyre::eyre!("Wrong passphrase or corrupted salt vault"))?;
+        let contents: VaultContents = serde_json::from_slice(&plaintext)?;
+
+        Ok(Self {
+            path,
+            key,
+            kdf_salt,
+            records: contents
+                .records
+                .into_iter()
+                .map(|record| (record.commitment, record))
+                .collect(),
+            used_salts: contents.used_salts,
+            pending: contents
+                .pending
+                .into_iter()
+                .map(|state| (state.escrow_uid, state))
+                .collect(),
+        })
+    }
+
+    /// Path of the vault file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Looks up the record for `commitment`.
+    pub fn get(&self, commitment: FixedBytes<32>) -> Option<&SaltRecord> {
+        self.records.get(&commitment)
+    }
+
+    /// Recovers the obligation data to reveal for `commitment`.
+    pub fn recover(
+        &self,
+        commitment: FixedBytes<32>,
+    ) -> eyre::Result<CommitRevealObligation::ObligationData> {
+        self.get(commitment)
+            .map(SaltRecord::obligation_data)
+            .ok_or_else(|| eyre::eyre!("No salt recorded for commitment {commitment}"))
+    }
+
+    /// Records made against `escrow_uid`.
+    pub fn for_escrow(&self, escrow_uid: FixedBytes<32>) -> Vec<&SaltRecord> {
+        self.records
+            .values()
+            .filter(|record| record.escrow_uid == escrow_uid)
+            .collect()
+    }
+
+    /// Every stored record, ordered by commitment.
+    pub fn records(&self) -> impl Iterator<Item = &SaltRecord> {
+        self.records.values()
+    }
+
+    /// Whether `salt` was ever stored in this vault, even if its record has
+    /// since been removed.
+    pub fn contains_salt(&self, salt: FixedBytes<32>) -> bool {
+        self.used_salts.contains(&keccak256(salt))
+    }
+
+    /// Stores `record` and rewrites the vault file.
+    ///
+    /// Refuses a salt that any current or removed record used, unless it is
+    /// the same record being stored again.
+    pub fn insert(&mut self, record: SaltRecord) -> eyre::Result<()> {
+        if let Some(existing) = self.records.get(&record.commitment) {
+            if *existing == record {
+                return Ok(());
+            }
+            return Err(eyre::eyre!(
+                "A different record is already stored for commitment {}",
+                record.commitment
+            ));
+        }
+        if self.contains_salt(record.salt) {
+            return Err(eyre::eyre!(
+                "Salt {} was already used for another commitment",
+                record.salt
+            ));
+        }
+        self.used_salts.insert(keccak256(record.salt));
+        self.records.insert(record.commitment, record);
+        self.persist()
+    }
+
+    /// Deletes the record for `commitment` and rewrites the vault file. Its
+    /// salt stays marked as used.
+    pub fn remove(&mut self, commitment: FixedBytes<32>) -> eyre::Result<Option<SaltRecord>> {
+        let removed = self.records.remove(&commitment);
+        if removed.is_some() {
+            self.persist()?;
+        }
+        Ok(removed)
+    }
+
+    fn persist(&self) -> eyre::Result<()> {
+        let plaintext = serde_json::to_vec(&VaultContents {
+            records: self.records.values().cloned().collect(),
+            used_salts: self.used_salts.clone(),
+            pending: self.pending.values().cloned().collect(),
+        })?;
+
+        let mut nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = Aes256Gcm::new(&self.key.into())
+            .encrypt(&nonce.into(), plaintext.as_ref())
+            .map_err(|_| eyre::eyre!("Failed to encrypt salt vault"))?;
+
+        let file = VaultFile {
+            version: VAULT_VERSION,
+            kdf_salt: Bytes::copy_from_slice(&self.kdf_salt),
+            nonce: Bytes::copy_from_slice(&nonce),
+            ciphertext: ciphertext.into(),
+        };
+
+        // Write then rename so a crash never leaves a truncated vault.
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&file)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// [`RevealStateStore`] that keeps pending reveals encrypted in a
+/// [`SaltVault`].
+///
+/// Saving a pending reveal also stores its [`SaltRecord`], so salt reuse is
+/// refused just like with [`SaltVault::insert`] and the salt can be
+/// recovered with [`SaltVault::recover`]. Removing it after the reveal drops
+/// both, but the salt stays marked as used. Prefer this over
+/// [`FileRevealStore`](super::FileRevealStore) whenever the payload or salt
+/// must stay secret until the reveal.
+///
+/// # Example
+/// ```rust,ignore
+/// let store = VaultRevealStore::new(SaltVault::open("./salts.vault", &passphrase)?);
+/// let outcome = client
+///     .commit_reveal()
+///     .flow(&store)
+///     .commit_then_reveal(escrow_uid, data)
+///     .await?;
+/// ```
+#[derive(Debug)]
+pub struct VaultRevealStore {
+    vault: Mutex<SaltVault>,
+}
+
+impl VaultRevealStore {
+    /// Keeps flow state in `vault`.
+    pub fn new(vault: SaltVault) -> Self {
+        Self {
+            vault: Mutex::new(vault),
+        }
+    }
+
+    /// Returns the underlying vault.
+    pub fn into_inner(self) -> eyre::Result<SaltVault> {
+        self.vault
+            .into_inner()
+            .map_err(|_| eyre::eyre!("Salt vault lock poisoned"))
+    }
+
+    fn lock(&self) -> eyre::Result<std::sync::MutexGuard<'_, SaltVault>> {
+        self.vault
+            .lock()
+            .map_err(|_| eyre::eyre!("Salt vault lock poisoned"))
+    }
+}
+
+impl RevealStateStore for VaultRevealStore {
+    fn load(&self, escrow_uid: FixedBytes<32>) -> eyre::Result<Option<PendingReveal>> {
+        Ok(self.lock()?.pending.get(&escrow_uid).cloned())
+    }
+
+    fn save(&self, state: &PendingReveal) -> eyre::Result<()> {
+        let mut vault = self.lock()?;
+        let record = SaltRecord {
+            commitment: state.commitment,
+            escrow_uid: state.escrow_uid,
+            payload: state.payload.clone(),
+            salt: state.salt,
+            schema: state.schema,
+        };
+        if vault.records.get(&record.commitment) != Some(&record) {
+            if vault.contains_salt(record.salt) {
+                return Err(eyre::eyre!(
+                    "Salt {} was already used for another commitment",
+                    record.salt
+                ));
+            }
+            vault.used_salts.insert(keccak256(record.salt));
+            vault.records.insert(record.commitment, record);
+        }
+        vault.pending.insert(state.escrow_uid, state.clone());
+        vault.persist()
+    }
+
+    fn remove(&self, escrow_uid: FixedBytes<32>) -> eyre::Result<()> {
+        let mut vault = self.lock()?;
+        let Some(state) = vault.pending.remove(&escrow_uid) else {
+            return Ok(());
+        };
+        vault.records.remove(&state.commitment);
+        vault.persist()
+    }
+
+    fn list(&self) -> eyre::Result<Vec<PendingReveal>> {
+        Ok(self.lock()?.pending.values().cloned().collect())
+    }
+}
+
+fn derive_key(passphrase: &str, kdf_salt: &[u8; 16]) -> eyre::Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), kdf_salt, &mut key)
+        .map_err(|e| eyre::eyre!("Failed to derive salt vault key: {e}"))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::obligations::commit_reveal::RevealStage;
+    use alloy::primitives::{Address, U256};
+
+    fn record(byte: u8) -> SaltRecord {
+        SaltRecord {
+            commitment: FixedBytes::repeat_byte(byte),
+            escrow_uid: FixedBytes::repeat_byte(0xee),
+            payload: Bytes::from(vec![byte; 4]),
+            salt: generate_salt(),
+            schema: FixedBytes::ZERO,
+        }
+    }
+
+    fn pending(byte: u8) -> PendingReveal {
+        PendingReveal {
+            escrow_uid: FixedBytes::repeat_byte(byte),
+            escrow_contract: Address::ZERO,
+            commitment: FixedBytes::repeat_byte(byte ^ 0xff),
+            claimer: Address::ZERO,
+            payload: Bytes::from_static(b"secret payload"),
+            salt: generate_salt(),
+            schema: FixedBytes::ZERO,
+            bond_amount: U256::from(1),
+            commit_deadline: U256::from(60),
+            stage: RevealStage::Committing,
+        }
+    }
+
+    #[test]
+    fn test_generate_salt_is_random() {
+        assert_ne!(generate_salt(), generate_salt());
+        assert_ne!(generate_salt(), FixedBytes::ZERO);
+    }
+
+    #[test]
+    fn test_vault_roundtrip_and_wrong_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("salts.vault");
+
+        let mut vault = SaltVault::open(&path, "correct horse").unwrap();
+        let first = record(0x01);
+        vault.insert(first.clone()).unwrap();
+        vault.insert(record(0x02)).unwrap();
+
+        // Nothing sensitive is stored in plain text.
+        let raw = std::fs::read(&path).unwrap();
+        let salt_hex = first.salt.to_string();
+        assert!(!String::from_utf8_lossy(&raw).contains(&salt_hex[2..]));
+
+        let reopened = SaltVault::open(&path, "correct horse").unwrap();
+        assert_eq!(reopened.get(first.commitment), Some(&first));
+        assert_eq!(reopened.for_escrow(first.escrow_uid).len(), 2);
+        assert_eq!(reopened.recover(first.commitment).unwrap().salt, first.salt);
+
+        assert!(SaltVault::open(&path, "wrong").is_err());
+    }
+
+    #[test]
+    fn test_vault_rejects_reused_salt() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut vault = SaltVault::open(dir.path().join("salts.vault"), "pw").unwrap();
+
+        let first = record(0x01);
+        vault.insert(first.clone()).unwrap();
+        // Storing the identical record again is a no-op.
+        vault.insert(first.clone()).unwrap();
+
+        let reused = SaltRecord {
+            salt: first.salt,
+            ..record(0x02)
+        };
+        assert!(vault.insert(reused).is_err());
+
+        // Removing a revealed record doesn't free its salt, even across
+        // reopening the vault.
+        vault.remove(first.commitment).unwrap();
+        assert!(vault.get(first.commitment).is_none());
+        assert!(vault.contains_salt(first.salt));
+        let reused = SaltRecord {
+            salt: first.salt,
+            ..record(0x03)
+        };
+        assert!(vault.insert(reused.clone()).is_err());
+
+        let mut reopened = SaltVault::open(vault.path(), "pw").unwrap();
+        assert!(reopened.contains_salt(first.salt));
+        assert!(reopened.insert(reused).is_err());
+    }
+
+    #[test]
+    fn test_vault_reveal_store_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("salts.vault");
+        let store = VaultRevealStore::new(SaltVault::open(&path, "pw").unwrap());
+
+        let mut state = pending(0x01);
+        store.save(&state).unwrap();
+        // Re-saving the same flow at a later stage is allowed.
+        state.stage = RevealStage::Committed;
+        store.save(&state).unwrap();
+        assert_eq!(store.load(state.escrow_uid).unwrap(), Some(state.clone()));
+
+        // Another flow can't reuse the salt.
+        let reused = PendingReveal {
+            salt: state.salt,
+            ..pending(0x02)
+        };
+        assert!(store.save(&reused).is_err());
+
+        // Nothing is stored in plain text, and state survives reopening.
+        let raw = std::fs::read(&path).unwrap();
+        assert!(!String::from_utf8_lossy(&raw).contains("secret payload"));
+        let vault = SaltVault::open(&path, "pw").unwrap();
+        assert_eq!(vault.recover(state.commitment).unwrap().salt, state.salt);
+        let store = VaultRevealStore::new(vault);
+        assert_eq!(store.list().unwrap(), vec![state.clone()]);
+
+        // Finishing the flow forgets it but keeps the salt used.
+        store.remove(state.escrow_uid).unwrap();
+        assert!(store.load(state.escrow_uid).unwrap().is_none());
+        let vault = store.into_inner().unwrap();
+        assert!(vault.get(state.commitment).is_none());
+        assert!(vault.contains_salt(state.salt));
+    }
+}
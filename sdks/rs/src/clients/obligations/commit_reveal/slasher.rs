@@ -0,0 +1,478 @@
+//! Watcher that slashes commitments whose reveal deadline passed unrevealed.
+//!
+//! [`BondSlasher`] indexes `Committed`, `BondReclaimed` and `BondSlashed`
+//! events into a [`CommitmentIndex`], reads each commitment's
+//! `commitTimestamp + commitDeadline` via `get_commitment`, and calls
+//! `slashBond` once the chain's latest block is past that point. Slashed
+//! bonds are paid to the contract's `slashedBondRecipient`; every payout is
+//! reported as a [`SlashReport`]. In dry-run mode nothing is sent and the
+//! reports describe what would have been slashed.
+
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    time::Duration,
+};
+
+use alloy::{
+    eips::BlockNumberOrTag,
+    primitives::{Address, FixedBytes, U256},
+    providers::Provider as _,
+    rpc::types::{Filter, Log, TransactionReceipt},
+    sol_types::SolEvent as _,
+};
+use futures::StreamExt as _;
+use tokio_util::sync::CancellationToken;
+
+use super::CommitRevealObligationModule;
+use crate::{
+    clients::arbiters::{SubscriptionHandle, open_log_stream},
+    contracts::obligations::CommitRevealObligation::{BondReclaimed, BondSlashed, Committed},
+    types::SharedPublicProvider,
+};
+
+/// An unclaimed commitment and the time after which its bond can be slashed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedCommitment {
+    /// Commitment hash.
+    pub commitment: FixedBytes<32>,
+    /// Address that posted the bond.
+    pub committer: Address,
+    /// Bond locked by the commitment.
+    pub bond_amount: U256,
+    /// Unix timestamp (`commitTimestamp + commitDeadline`) after which the
+    /// bond becomes slashable.
+    pub slashable_after: U256,
+}
+
+/// Unclaimed commitments known to a [`BondSlasher`], keyed by commitment.
+#[derive(Debug, Clone, Default)]
+pub struct CommitmentIndex {
+    commitments: HashMap<FixedBytes<32>, TrackedCommitment>,
+    /// Commitments already reported by a dry run.
+    reported: HashSet<FixedBytes<32>>,
+}
+
+impl CommitmentIndex {
+    /// Creates an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts tracking a commitment, replacing any previous entry.
+    pub fn insert(&mut self, tracked: TrackedCommitment) {
+        self.commitments.insert(tracked.commitment, tracked);
+    }
+
+    /// Stops tracking a commitment.
+    pub fn remove(&mut self, commitment: FixedBytes<32>) -> Option<TrackedCommitment> {
+        self.reported.remove(&commitment);
+        self.commitments.remove(&commitment)
+    }
+
+    /// Marks a tracked commitment as reported, returning `false` if it
+    /// already was or isn't tracked.
+    pub fn mark_reported(&mut self, commitment: FixedBytes<32>) -> bool {
+        self.commitments.contains_key(&commitment) && self.reported.insert(commitment)
+    }
+
+    /// Whether a dry run has already reported the commitment.
+    pub fn is_reported(&self, commitment: FixedBytes<32>) -> bool {
+        self.reported.contains(&commitment)
+    }
+
+    /// Looks up a tracked commitment.
+    pub fn get(&self, commitment: FixedBytes<32>) -> Option<&TrackedCommitment> {
+        self.commitments.get(&commitment)
+    }
+
+    /// Number of tracked commitments.
+    pub fn len(&self) -> usize {
+        self.commitments.len()
+    }
+
+    /// Whether no commitments are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.commitments.is_empty()
+    }
+
+    /// Commitments posted by `committer`.
+    pub fn by_committer(&self, committer: Address) -> Vec<FixedBytes<32>> {
+        self.commitments
+            .values()
+            .filter(|tracked| tracked.committer == committer)
+            .map(|tracked| tracked.commitment)
+            .collect()
+    }
+
+    /// Commitments slashable at block timestamp `now`, earliest deadline first.
+    pub fn expired(&self, now: u64) -> Vec<TrackedCommitment> {
+        let now = U256::from(now);
+        let mut expired: Vec<_> = self
+            .commitments
+            .values()
+            .filter(|tracked| now > tracked.slashable_after)
+            .cloned()
+            .collect();
+        expired.sort_by_key(|tracked| tracked.slashable_after);
+        expired
+    }
+}
+
+/// A slashed (or, in dry-run mode, slashable) bond.
+#[derive(Debug, Clone)]
+pub struct SlashReport {
+    /// Commitment whose bond was slashed.
+    pub commitment: FixedBytes<32>,
+    /// Address that posted the bond.
+    pub committer: Address,
+    /// `slashedBondRecipient` that received the bond.
+    pub recipient: Address,
+    /// Amount paid to `recipient`.
+    pub amount: U256,
+    /// `slashBond` receipt; `None` in dry-run mode.
+    pub receipt: Option<TransactionReceipt>,
+}
+
+/// Handle for a running [`BondSlasher::watch`] task.
+pub struct SlasherHandle {
+    subscription: SubscriptionHandle,
+    cancel: CancellationToken,
+    provider: SharedPublicProvider,
+}
+
+impl SlasherHandle {
+    /// Stops the watcher and releases its log subscription.
+    pub async fn stop(self) -> eyre::Result<()> {
+        self.cancel.cancel();
+        self.subscription.unsubscribe(&self.provider).await
+    }
+}
+
+/// Finds and slashes commitments that missed their reveal deadline.
+#[derive(Clone)]
+pub struct BondSlasher {
+    module: CommitRevealObligationModule,
+    dry_run: bool,
+    from_block: u64,
+}
+
+impl BondSlasher {
+    pub(super) fn new(module: CommitRevealObligationModule) -> Self {
+        Self {
+            module,
+            dry_run: false,
+            from_block: 0,
+        }
+    }
+
+    /// Report slashable bonds without sending `slashBond` transactions.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// First block scanned for historical events. Defaults to genesis.
+    pub fn from_block(mut self, from_block: u64) -> Self {
+        self.from_block = from_block;
+        self
+    }
+
+    fn events_filter(&self) -> Filter {
+        Filter::new()
+            .address(self.module.addresses.obligation)
+            .event_signature(vec![
+                Committed::SIGNATURE_HASH,
+                BondReclaimed::SIGNATURE_HASH,
+                BondSlashed::SIGNATURE_HASH,
+            ])
+            .from_block(self.from_block)
+    }
+
+    /// Builds an index of unclaimed commitments from historical events.
+    pub async fn index(&self) -> eyre::Result<CommitmentIndex> {
+        let filter = self.events_filter().to_block(BlockNumberOrTag::Latest);
+        let logs = self.module.public_provider.get_logs(&filter).await?;
+
+        let mut index = CommitmentIndex::new();
+        for log in logs {
+            self.apply_log(&mut index, &log).await?;
+        }
+        Ok(index)
+    }
+
+    /// Updates `index` with one `Committed`, `BondReclaimed` or `BondSlashed` log.
+    ///
+    /// `BondReclaimed` is keyed by fulfillment rather than commitment, so the
+    /// claimer's tracked commitments are re-checked with `commitmentClaimed`.
+    pub async fn apply_log(&self, index: &mut CommitmentIndex, log: &Log) -> eyre::Result<()> {
+        match log.topic0() {
+            Some(&Committed::SIGNATURE_HASH) => {
+                let event = log.log_decode::<Committed>()?.inner.data;
+                if let Some(tracked) = self.track(event.commitment).await? {
+                    index.insert(tracked);
+                }
+            }
+            Some(&BondReclaimed::SIGNATURE_HASH) => {
+                let event = log.log_decode::<BondReclaimed>()?.inner.data;
+                for commitment in index.by_committer(event.claimer) {
+                    if self.module.is_commitment_claimed(commitment).await? {
+                        index.remove(commitment);
+                    }
+                }
+            }
+            Some(&BondSlashed::SIGNATURE_HASH) => {
+                let event = log.log_decode::<BondSlashed>()?.inner.data;
+                index.remove(event.commitment);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Reads a commitment's on-chain state, returning `None` once it's claimed.
+    async fn track(&self, commitment: FixedBytes<32>) -> eyre::Result<Option<TrackedCommitment>> {
+        if self.module.is_commitment_claimed(commitment).await? {
+            return Ok(None);
+        }
+        let (_, commit_timestamp, committer, bond_amount, commit_deadline) =
+            self.module.get_commitment(commitment).await?;
+        if committer == Address::ZERO {
+            return Ok(None);
+        }
+        Ok(Some(TrackedCommitment {
+            commitment,
+            committer,
+            bond_amount,
+            slashable_after: U256::from(commit_timestamp).saturating_add(commit_deadline),
+        }))
+    }
+
+    /// Slashes every commitment in `index` whose deadline is behind the latest
+    /// block, removing it from the index.
+    ///
+    /// In dry-run mode commitments stay in the index but are marked reported,
+    /// so each one is reported once rather than on every call.
+    ///
+    /// Commitments that turn out to be claimed already (e.g. slashed by
+    /// someone else first) are dropped silently; other failures are logged
+    /// and left in the index for the next attempt.
+    pub async fn slash_expired(
+        &self,
+        index: &mut CommitmentIndex,
+    ) -> eyre::Result<Vec<SlashReport>> {
+        let latest = self
+            .module
+            .public_provider
+            .get_block_by_number(BlockNumberOrTag::Latest)
+            .await?
+            .ok_or_else(|| eyre::eyre!("Latest block not found"))?;
+        let expired = index.expired(latest.header.timestamp);
+        if expired.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let recipient = self.module.slashed_bond_recipient().await?;
+        let mut reports = Vec::with_capacity(expired.len());
+        for tracked in expired {
+            if self
+                .module
+                .is_commitment_claimed(tracked.commitment)
+                .await?
+            {
+                index.remove(tracked.commitment);
+                continue;
+            }
+
+            if self.dry_run {
+                if !index.mark_reported(tracked.commitment) {
+                    continue;
+                }
+                reports.push(SlashReport {
+                    commitment: tracked.commitment,
+                    committer: tracked.committer,
+                    recipient,
+                    amount: tracked.bond_amount,
+                    receipt: None,
+                });
+                continue;
+            }
+
+            match self.slash(&tracked).await {
+                Ok(report) => {
+                    index.remove(tracked.commitment);
+                    reports.push(report);
+                }
+                Err(err) => {
+                    tracing::warn!("Failed to slash commitment {}: {}", tracked.commitment, err);
+                    if self
+                        .module
+                        .is_commitment_claimed(tracked.commitment)
+                        .await
+                        .unwrap_or(false)
+                    {
+                        index.remove(tracked.commitment);
+                    }
+                }
+            }
+        }
+        Ok(reports)
+    }
+
+    async fn slash(&self, tracked: &TrackedCommitment) -> eyre::Result<SlashReport> {
+        let receipt = self.module.slash_bond(tracked.commitment).await?;
+        if !receipt.status() {
+            return Err(eyre::eyre!("slashBond reverted"));
+        }
+        let event = receipt
+            .decoded_log::<BondSlashed>()
+            .ok_or_else(|| eyre::eyre!("No BondSlashed event found"))?;
+        Ok(SlashReport {
+            commitment: tracked.commitment,
+            committer: tracked.committer,
+            recipient: event.recipient,
+            amount: event.amount,
+            receipt: Some(receipt),
+        })
+    }
+
+    /// Indexes past events and slashes whatever has already expired.
+    pub async fn run_once(&self) -> eyre::Result<Vec<SlashReport>> {
+        let mut index = self.index().await?;
+        self.slash_expired(&mut index).await
+    }
+
+    /// Indexes past events, then keeps the index current from live events
+    /// and checks for expired commitments every `check_interval`, calling
+    /// `on_slash` for each report.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let handle = client
+    ///     .commit_reveal()
+    ///     .slasher()
+    ///     .dry_run(true)
+    ///     .watch(Duration::from_secs(60), |report: &SlashReport| {
+    ///         let report = report.clone();
+    ///         async move { println!("slashable: {report:?}") }
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn watch<
+        OnSlashFut: Future<Output = ()> + Send + 'static,
+        OnSlash: Fn(&SlashReport) -> OnSlashFut + Send + Sync + 'static,
+    >(
+        self,
+        check_interval: Duration,
+        on_slash: OnSlash,
+    ) -> eyre::Result<SlasherHandle> {
+        // Subscribe before indexing so no event falls between the two.
+        let (mut stream, subscription) = open_log_stream(
+            &self.module.public_provider,
+            &self.events_filter(),
+            self.module.poll_interval,
+        )
+        .await?;
+        let mut index = self.index().await?;
+
+        let cancel = CancellationToken::new();
+        let task_cancel = cancel.clone();
+        let provider = self.module.public_provider.clone();
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(check_interval);
+            loop {
+                tokio::select! {
+                    _ = task_cancel.cancelled() => break,
+                    log = stream.next() => {
+                        let Some(log) = log else { break };
+                        if let Err(err) = self.apply_log(&mut index, &log).await {
+                            tracing::error!("Failed to index commit-reveal event: {}", err);
+                        }
+                    }
+                    _ = ticker.tick() => {
+                        match self.slash_expired(&mut index).await {
+                            Ok(reports) => {
+                                for report in &reports {
+                                    tokio::spawn(on_slash(report));
+                                }
+                            }
+                            Err(err) => tracing::error!("Bond slashing pass failed: {}", err),
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(SlasherHandle {
+            subscription,
+            cancel,
+            provider,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracked(byte: u8, committer: Address, slashable_after: u64) -> TrackedCommitment {
+        TrackedCommitment {
+            commitment: FixedBytes::repeat_byte(byte),
+            committer,
+            bond_amount: U256::from(100),
+            slashable_after: U256::from(slashable_after),
+        }
+    }
+
+    #[test]
+    fn test_index_expired_is_strict_and_ordered() {
+        let alice = Address::repeat_byte(0xa1);
+        let mut index = CommitmentIndex::new();
+        index.insert(tracked(0x03, alice, 300));
+        index.insert(tracked(0x01, alice, 100));
+        index.insert(tracked(0x02, alice, 200));
+
+        // The contract requires `block.timestamp > slashable_after`.
+        assert!(index.expired(100).is_empty());
+        let expired: Vec<_> = index
+            .expired(250)
+            .into_iter()
+            .map(|t| t.commitment)
+            .collect();
+        assert_eq!(
+            expired,
+            vec![FixedBytes::repeat_byte(0x01), FixedBytes::repeat_byte(0x02)]
+        );
+    }
+
+    #[test]
+    fn test_index_by_committer_and_remove() {
+        let alice = Address::repeat_byte(0xa1);
+        let bob = Address::repeat_byte(0xb0);
+        let mut index = CommitmentIndex::new();
+        index.insert(tracked(0x01, alice, 100));
+        index.insert(tracked(0x02, bob, 100));
+
+        assert_eq!(index.by_committer(bob), vec![FixedBytes::repeat_byte(0x02)]);
+        assert!(index.remove(FixedBytes::repeat_byte(0x02)).is_some());
+        assert!(index.by_committer(bob).is_empty());
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn test_index_marks_reported_once() {
+        let alice = Address::repeat_byte(0xa1);
+        let commitment = FixedBytes::repeat_byte(0x01);
+        let mut index = CommitmentIndex::new();
+        assert!(!index.mark_reported(commitment));
+
+        index.insert(tracked(0x01, alice, 100));
+        assert!(index.mark_reported(commitment));
+        assert!(!index.mark_reported(commitment));
+        assert!(index.is_reported(commitment));
+
+        index.remove(commitment);
+        assert!(!index.is_reported(commitment));
+    }
+}
@@ -0,0 +1,153 @@
+//! Single-hook escrow obligation client
+//!
+//! `HookEscrowObligation` locks one asset through one hook and releases it
+//! to whoever fulfills the escrow's demand.
+
+use alloy::{
+    primitives::{Address, FixedBytes},
+    rpc::types::TransactionReceipt,
+    sol_types::SolValue as _,
+};
+
+use super::super::{HookBasedModule, HookEscrowData, hook_data::HookAsset};
+use crate::{
+    contracts::{self, obligations::escrow::hook_based::HookEscrowObligation},
+    types::{ArbiterData, DecodedAttestation},
+};
+
+/// Single-hook escrow API
+pub struct Hook<'a> {
+    module: &'a HookBasedModule,
+}
+
+impl<'a> Hook<'a> {
+    pub fn new(module: &'a HookBasedModule) -> Self {
+        Self { module }
+    }
+
+    /// Get the contract address
+    pub fn address(&self) -> Address {
+        self.module.addresses.hook_escrow_obligation
+    }
+
+    /// Obligation data escrowing `asset` for `item`
+    pub fn obligation_data(&self, asset: &HookAsset, item: &ArbiterData) -> HookEscrowData {
+        HookEscrowData {
+            arbiter: item.arbiter,
+            demand: item.demand.clone(),
+            hook: asset.hook(&self.module.addresses),
+            hookData: asset.hook_data(),
+        }
+    }
+
+    /// Gets an escrow obligation by its attestation UID.
+    pub async fn get_obligation(
+        &self,
+        uid: FixedBytes<32>,
+    ) -> eyre::Result<DecodedAttestation<HookEscrowData>> {
+        let eas_contract =
+            contracts::IEAS::new(self.module.addresses.eas, &self.module.wallet_provider);
+
+        let attestation = eas_contract.getAttestation(uid).call().await?;
+        let obligation_data = HookEscrowData::abi_decode(&attestation.data)?;
+
+        Ok(DecodedAttestation {
+            attestation,
+            data: obligation_data,
+        })
+    }
+
+    /// Escrows `asset` for a custom demand.
+    ///
+    /// Makes any missing hook approvals first, then sends the asset's native
+    /// value, if any, with the escrow transaction.
+    pub async fn create(
+        &self,
+        asset: &HookAsset,
+        item: &ArbiterData,
+        expiration: u64,
+    ) -> eyre::Result<TransactionReceipt> {
+        self.module
+            .util()
+            .approve_assets(std::slice::from_ref(asset), self.address())
+            .await?;
+
+        let escrow_contract =
+            HookEscrowObligation::new(self.address(), &self.module.wallet_provider);
+        let receipt = escrow_contract
+            .doObligation(self.obligation_data(asset, item), expiration)
+            .value(asset.value())
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// Escrows the signer's `asset` with `recipient` as the escrow's owner,
+    /// who can reclaim it after expiration.
+    ///
+    /// Makes any missing hook approvals first, like [`Self::create`].
+    pub async fn create_for(
+        &self,
+        asset: &HookAsset,
+        item: &ArbiterData,
+        expiration: u64,
+        recipient: Address,
+    ) -> eyre::Result<TransactionReceipt> {
+        self.module
+            .util()
+            .approve_assets(std::slice::from_ref(asset), self.address())
+            .await?;
+
+        let escrow_contract =
+            HookEscrowObligation::new(self.address(), &self.module.wallet_provider);
+        let receipt = escrow_contract
+            .doObligationFor(self.obligation_data(asset, item), expiration, recipient)
+            .value(asset.value())
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// Collects an escrow with a fulfillment that satisfies its demand.
+    pub async fn collect(
+        &self,
+        escrow: FixedBytes<32>,
+        fulfillment: FixedBytes<32>,
+    ) -> eyre::Result<TransactionReceipt> {
+        let escrow_contract =
+            HookEscrowObligation::new(self.address(), &self.module.wallet_provider);
+
+        let receipt = escrow_contract
+            .collect(escrow, fulfillment)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// Returns an expired escrow's asset to the escrow's owner.
+    pub async fn reclaim_expired(
+        &self,
+        escrow: FixedBytes<32>,
+    ) -> eyre::Result<TransactionReceipt> {
+        let escrow_contract =
+            HookEscrowObligation::new(self.address(), &self.module.wallet_provider);
+
+        let receipt = escrow_contract
+            .reclaim(escrow)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+}
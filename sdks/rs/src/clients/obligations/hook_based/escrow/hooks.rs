@@ -0,0 +1,357 @@
+//! Multi-hook escrow obligation client
+//!
+//! `HooksEscrowObligation` locks several assets, each through its own hook,
+//! under one escrow and releases them together. [`HooksEscrowBuilder`]
+//! assembles such an escrow one asset at a time.
+
+use std::collections::HashSet;
+
+use alloy::{
+    primitives::{Address, FixedBytes, U256},
+    rpc::types::TransactionReceipt,
+    sol_types::SolValue as _,
+};
+
+use super::super::{
+    HookBasedModule, HooksEscrowData,
+    hook_data::{HookAsset, HookAttestationRequest},
+};
+use crate::{
+    contracts::{
+        self,
+        obligations::escrow::hook_based::{
+            HooksEscrowObligation, hooks::AttestationReferenceEscrowHook,
+        },
+    },
+    types::{ArbiterData, DecodedAttestation, Erc20Data, Erc721Data, Erc1155Data, NativeTokenData},
+};
+
+/// Most hooks one `HooksEscrowObligation` escrow may use, as enforced by
+/// the contract's `MAX_HOOKS`.
+pub const MAX_HOOKS: usize = 50;
+
+/// Multi-hook escrow API
+pub struct Hooks<'a> {
+    module: &'a HookBasedModule,
+}
+
+impl<'a> Hooks<'a> {
+    pub fn new(module: &'a HookBasedModule) -> Self {
+        Self { module }
+    }
+
+    /// Get the contract address
+    pub fn address(&self) -> Address {
+        self.module.addresses.hooks_escrow_obligation
+    }
+
+    /// Start building an escrow for `item` that expires at `expiration`.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let receipt = client
+    ///     .hook_based()
+    ///     .escrow()
+    ///     .hooks()
+    ///     .builder(item, expiration)
+    ///     .erc20(Erc20Data { address: usdc, value: U256::from(10_000_000) })
+    ///     .erc721(Erc721Data { address: nft, id: U256::from(42) })
+    ///     .native_token(NativeTokenData { value: parse_ether("0.1")? })
+    ///     .create()
+    ///     .await?;
+    /// ```
+    pub fn builder(&self, item: ArbiterData, expiration: u64) -> HooksEscrowBuilder<'a> {
+        HooksEscrowBuilder::new(Hooks::new(self.module), item, expiration)
+    }
+
+    /// Obligation data escrowing `assets` for `item`
+    pub fn obligation_data(&self, assets: &[HookAsset], item: &ArbiterData) -> HooksEscrowData {
+        HooksEscrowData {
+            arbiter: item.arbiter,
+            demand: item.demand.clone(),
+            hooks: assets
+                .iter()
+                .map(|a| a.hook(&self.module.addresses))
+                .collect(),
+            hookDatas: assets.iter().map(HookAsset::hook_data).collect(),
+            values: assets.iter().map(HookAsset::value).collect(),
+        }
+    }
+
+    /// Gets an escrow obligation by its attestation UID.
+    pub async fn get_obligation(
+        &self,
+        uid: FixedBytes<32>,
+    ) -> eyre::Result<DecodedAttestation<HooksEscrowData>> {
+        let eas_contract =
+            contracts::IEAS::new(self.module.addresses.eas, &self.module.wallet_provider);
+
+        let attestation = eas_contract.getAttestation(uid).call().await?;
+        let obligation_data = HooksEscrowData::abi_decode(&attestation.data)?;
+
+        Ok(DecodedAttestation {
+            attestation,
+            data: obligation_data,
+        })
+    }
+
+    /// Escrows all of `assets` for a custom demand.
+    ///
+    /// Checks the assets with [`validate_assets`], makes any missing hook
+    /// approvals, then sends the assets' total native value with the escrow
+    /// transaction.
+    pub async fn create(
+        &self,
+        assets: &[HookAsset],
+        item: &ArbiterData,
+        expiration: u64,
+    ) -> eyre::Result<TransactionReceipt> {
+        validate_assets(assets)?;
+        self.module
+            .util()
+            .approve_assets(assets, self.address())
+            .await?;
+
+        let escrow_contract =
+            HooksEscrowObligation::new(self.address(), &self.module.wallet_provider);
+        let receipt = escrow_contract
+            .doObligation(self.obligation_data(assets, item), expiration)
+            .value(total_value(assets))
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// Escrows the signer's `assets` with `recipient` as the escrow's owner,
+    /// who can reclaim them after expiration.
+    ///
+    /// Makes any missing hook approvals first, like [`Self::create`].
+    pub async fn create_for(
+        &self,
+        assets: &[HookAsset],
+        item: &ArbiterData,
+        expiration: u64,
+        recipient: Address,
+    ) -> eyre::Result<TransactionReceipt> {
+        validate_assets(assets)?;
+        self.module
+            .util()
+            .approve_assets(assets, self.address())
+            .await?;
+
+        let escrow_contract =
+            HooksEscrowObligation::new(self.address(), &self.module.wallet_provider);
+        let receipt = escrow_contract
+            .doObligationFor(self.obligation_data(assets, item), expiration, recipient)
+            .value(total_value(assets))
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// Collects an escrow with a fulfillment that satisfies its demand.
+    pub async fn collect(
+        &self,
+        escrow: FixedBytes<32>,
+        fulfillment: FixedBytes<32>,
+    ) -> eyre::Result<TransactionReceipt> {
+        let escrow_contract =
+            HooksEscrowObligation::new(self.address(), &self.module.wallet_provider);
+
+        let receipt = escrow_contract
+            .collect(escrow, fulfillment)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// Returns an expired escrow's assets to the escrow's owner.
+    pub async fn reclaim_expired(
+        &self,
+        escrow: FixedBytes<32>,
+    ) -> eyre::Result<TransactionReceipt> {
+        let escrow_contract =
+            HooksEscrowObligation::new(self.address(), &self.module.wallet_provider);
+
+        let receipt = escrow_contract
+            .reclaim(escrow)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        Ok(receipt)
+    }
+}
+
+/// Builder for a multi-asset `HooksEscrowObligation` escrow
+///
+/// Collects assets in order, picks each one's hook, and creates the escrow
+/// with all approvals and the total native value in one call.
+pub struct HooksEscrowBuilder<'a> {
+    hooks: Hooks<'a>,
+    item: ArbiterData,
+    expiration: u64,
+    recipient: Option<Address>,
+    assets: Vec<HookAsset>,
+}
+
+impl<'a> HooksEscrowBuilder<'a> {
+    pub fn new(hooks: Hooks<'a>, item: ArbiterData, expiration: u64) -> Self {
+        Self {
+            hooks,
+            item,
+            expiration,
+            recipient: None,
+            assets: Vec::new(),
+        }
+    }
+
+    /// Add any hook asset
+    pub fn asset(mut self, asset: HookAsset) -> Self {
+        self.assets.push(asset);
+        self
+    }
+
+    /// Add ERC20 tokens
+    pub fn erc20(self, token: Erc20Data) -> Self {
+        self.asset(HookAsset::Erc20(token))
+    }
+
+    /// Add one ERC721 token
+    pub fn erc721(self, token: Erc721Data) -> Self {
+        self.asset(HookAsset::Erc721(token))
+    }
+
+    /// Add ERC1155 tokens
+    pub fn erc1155(self, token: Erc1155Data) -> Self {
+        self.asset(HookAsset::Erc1155(token))
+    }
+
+    /// Add native tokens
+    pub fn native_token(self, native: NativeTokenData) -> Self {
+        self.asset(HookAsset::NativeToken(native))
+    }
+
+    /// Add an attestation made on release
+    pub fn attestation(self, request: HookAttestationRequest) -> Self {
+        self.asset(HookAsset::Attestation(request))
+    }
+
+    /// Add a validation attestation made on release
+    pub fn attestation_reference(self, data: AttestationReferenceEscrowHook::HookData) -> Self {
+        self.asset(HookAsset::AttestationReference(data))
+    }
+
+    /// Make `recipient` the escrow's owner instead of the signer
+    pub fn with_recipient(mut self, recipient: Address) -> Self {
+        self.recipient = Some(recipient);
+        self
+    }
+
+    /// Assets added so far, in escrow order
+    pub fn assets(&self) -> &[HookAsset] {
+        &self.assets
+    }
+
+    /// Native value sent with the escrow transaction
+    pub fn total_value(&self) -> U256 {
+        total_value(&self.assets)
+    }
+
+    /// Obligation data for the escrow, after checking the assets
+    pub fn obligation_data(&self) -> eyre::Result<HooksEscrowData> {
+        validate_assets(&self.assets)?;
+        Ok(self.hooks.obligation_data(&self.assets, &self.item))
+    }
+
+    /// Make all approvals and create the escrow.
+    pub async fn create(self) -> eyre::Result<TransactionReceipt> {
+        match self.recipient {
+            Some(recipient) => {
+                self.hooks
+                    .create_for(&self.assets, &self.item, self.expiration, recipient)
+                    .await
+            }
+            None => {
+                self.hooks
+                    .create(&self.assets, &self.item, self.expiration)
+                    .await
+            }
+        }
+    }
+}
+
+/// Check `assets` before creating a hooks escrow.
+///
+/// `HooksEscrowObligation` itself only enforces at most [`MAX_HOOKS`] assets
+/// (and that `msg.value` matches their total native value). The other checks
+/// are SDK policy: an escrow with no assets locks nothing, and an ERC721
+/// token listed twice would make the second hook's transfer revert.
+pub fn validate_assets(assets: &[HookAsset]) -> eyre::Result<()> {
+    if assets.is_empty() {
+        return Err(eyre::eyre!("A hooks escrow needs at least one asset"));
+    }
+    if assets.len() > MAX_HOOKS {
+        return Err(eyre::eyre!(
+            "A hooks escrow takes at most {MAX_HOOKS} assets, got {}",
+            assets.len()
+        ));
+    }
+
+    let mut erc721s = HashSet::new();
+    for asset in assets {
+        let HookAsset::Erc721(token) = asset else {
+            continue;
+        };
+        if !erc721s.insert((token.address, token.id)) {
+            return Err(eyre::eyre!(
+                "ERC721 token {} #{} is listed more than once",
+                token.address,
+                token.id
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn total_value(assets: &[HookAsset]) -> U256 {
+    assets.iter().map(HookAsset::value).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_assets_enforces_limits_and_policy() {
+        let token = Address::repeat_byte(0xaa);
+        let erc20 = HookAsset::erc20(token, U256::from(1));
+        let nft = HookAsset::erc721(token, U256::from(42));
+
+        assert!(validate_assets(&[]).is_err());
+        assert!(validate_assets(&[erc20.clone(), erc20.clone(), nft.clone()]).is_ok());
+        assert!(validate_assets(&[nft.clone(), nft]).is_err());
+        assert!(validate_assets(&vec![erc20.clone(); MAX_HOOKS]).is_ok());
+        assert!(validate_assets(&vec![erc20; MAX_HOOKS + 1]).is_err());
+    }
+
+    #[test]
+    fn total_value_sums_native_and_attestation_values() {
+        let assets = [
+            HookAsset::native_token(U256::from(3)),
+            HookAsset::erc20(Address::ZERO, U256::from(100)),
+            HookAsset::native_token(U256::from(4)),
+        ];
+        assert_eq!(total_value(&assets), U256::from(7));
+    }
+}
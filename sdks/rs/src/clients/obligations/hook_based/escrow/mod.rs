@@ -0,0 +1,31 @@
+//! Hook-based escrow obligations module
+//!
+//! - `hook`: `HookEscrowObligation`, escrowing one asset through one hook
+//! - `hooks`: `HooksEscrowObligation`, escrowing several assets through
+//!   several hooks at once
+
+pub mod hook;
+pub mod hooks;
+
+use super::HookBasedModule;
+
+/// Escrow API for hook-based escrows
+pub struct Escrow<'a> {
+    module: &'a HookBasedModule,
+}
+
+impl<'a> Escrow<'a> {
+    pub fn new(module: &'a HookBasedModule) -> Self {
+        Self { module }
+    }
+
+    /// Access single-hook escrow API
+    pub fn hook(&self) -> hook::Hook<'a> {
+        hook::Hook::new(self.module)
+    }
+
+    /// Access multi-hook escrow API
+    pub fn hooks(&self) -> hooks::Hooks<'a> {
+        hooks::Hooks::new(self.module)
+    }
+}
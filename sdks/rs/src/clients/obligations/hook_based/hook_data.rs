@@ -0,0 +1,230 @@
+//! Typed hook data for the built-in escrow hooks
+//!
+//! Each hook in `hooks/` decodes its own `HookData` struct. [`HookAsset`]
+//! pairs that data with the hook it belongs to, so callers describe what is
+//! escrowed and the client picks the hook address, encodes the hook data and
+//! works out the native value the hook expects.
+
+use alloy::{
+    primitives::{Address, Bytes, FixedBytes, U256},
+    sol_types::SolValue as _,
+};
+
+use super::HookBasedAddresses;
+use crate::{
+    contracts::obligations::escrow::hook_based::hooks::{
+        AttestationEscrowHook, AttestationReferenceEscrowHook, ERC20EscrowHook, ERC721EscrowHook,
+        ERC1155EscrowHook, NativeTokenEscrowHook,
+    },
+    types::{Erc20Data, Erc721Data, Erc1155Data, NativeTokenData},
+};
+
+/// Attestation created by `AttestationEscrowHook` on release.
+pub type HookAttestationRequest = AttestationEscrowHook::AttestationRequest;
+
+/// An asset escrowed through one of the built-in escrow hooks.
+#[derive(Debug, Clone)]
+pub enum HookAsset {
+    /// ERC20 tokens, pulled by `ERC20EscrowHook`.
+    Erc20(Erc20Data),
+    /// One ERC721 token, pulled by `ERC721EscrowHook`.
+    Erc721(Erc721Data),
+    /// ERC1155 tokens, pulled by `ERC1155EscrowHook`.
+    Erc1155(Erc1155Data),
+    /// Native tokens, sent with the escrow transaction to `NativeTokenEscrowHook`.
+    NativeToken(NativeTokenData),
+    /// An attestation `AttestationEscrowHook` makes on release. Its
+    /// `data.value` is sent with the escrow transaction.
+    Attestation(HookAttestationRequest),
+    /// A validation attestation `AttestationReferenceEscrowHook` makes on
+    /// release, referencing an existing attestation.
+    AttestationReference(AttestationReferenceEscrowHook::HookData),
+}
+
+impl HookAsset {
+    /// ERC20 tokens
+    pub fn erc20(token: Address, amount: U256) -> Self {
+        Self::Erc20(Erc20Data {
+            address: token,
+            value: amount,
+        })
+    }
+
+    /// One ERC721 token
+    pub fn erc721(token: Address, id: U256) -> Self {
+        Self::Erc721(Erc721Data { address: token, id })
+    }
+
+    /// ERC1155 tokens
+    pub fn erc1155(token: Address, id: U256, amount: U256) -> Self {
+        Self::Erc1155(Erc1155Data {
+            address: token,
+            id,
+            value: amount,
+        })
+    }
+
+    /// Native tokens, in wei
+    pub fn native_token(amount: U256) -> Self {
+        Self::NativeToken(NativeTokenData { value: amount })
+    }
+
+    /// An attestation made on release
+    pub fn attestation(request: HookAttestationRequest) -> Self {
+        Self::Attestation(request)
+    }
+
+    /// A validation attestation of `attestation_uid` made on release
+    pub fn attestation_reference(
+        attestation_uid: FixedBytes<32>,
+        recipient: Address,
+        validation_expiration_time: u64,
+        validation_revocable: bool,
+    ) -> Self {
+        Self::AttestationReference(AttestationReferenceEscrowHook::HookData {
+            attestationUid: attestation_uid,
+            recipient,
+            validationExpirationTime: validation_expiration_time,
+            validationRevocable: validation_revocable,
+        })
+    }
+
+    /// Address of the hook that escrows this asset
+    pub fn hook(&self, addresses: &HookBasedAddresses) -> Address {
+        match self {
+            Self::Erc20(_) => addresses.erc20_escrow_hook,
+            Self::Erc721(_) => addresses.erc721_escrow_hook,
+            Self::Erc1155(_) => addresses.erc1155_escrow_hook,
+            Self::NativeToken(_) => addresses.native_token_escrow_hook,
+            Self::Attestation(_) => addresses.attestation_escrow_hook,
+            Self::AttestationReference(_) => addresses.attestation_reference_escrow_hook,
+        }
+    }
+
+    /// ABI-encoded `HookData` for this asset's hook
+    pub fn hook_data(&self) -> Bytes {
+        match self {
+            Self::Erc20(token) => ERC20EscrowHook::HookData {
+                token: token.address,
+                amount: token.value,
+            }
+            .into(),
+            Self::Erc721(token) => ERC721EscrowHook::HookData {
+                token: token.address,
+                tokenId: token.id,
+            }
+            .into(),
+            Self::Erc1155(token) => ERC1155EscrowHook::HookData {
+                token: token.address,
+                tokenId: token.id,
+                amount: token.value,
+            }
+            .into(),
+            Self::NativeToken(native) => NativeTokenEscrowHook::HookData {
+                amount: native.value,
+            }
+            .into(),
+            Self::Attestation(request) => AttestationEscrowHook::HookData {
+                attestation: request.clone(),
+            }
+            .into(),
+            Self::AttestationReference(data) => data.clone().into(),
+        }
+    }
+
+    /// Native value the hook expects with the escrow transaction
+    pub fn value(&self) -> U256 {
+        match self {
+            Self::NativeToken(native) => native.value,
+            Self::Attestation(request) => request.data.value,
+            Self::Erc20(_) | Self::Erc721(_) | Self::Erc1155(_) | Self::AttestationReference(_) => {
+                U256::ZERO
+            }
+        }
+    }
+
+    /// Decode hook data for `hook`, one of the built-in hooks in `addresses`.
+    pub fn decode(
+        addresses: &HookBasedAddresses,
+        hook: Address,
+        hook_data: &Bytes,
+    ) -> eyre::Result<Self> {
+        let data = hook_data.as_ref();
+        let asset = if hook == addresses.erc20_escrow_hook {
+            let d = ERC20EscrowHook::HookData::abi_decode(data)?;
+            Self::erc20(d.token, d.amount)
+        } else if hook == addresses.erc721_escrow_hook {
+            let d = ERC721EscrowHook::HookData::abi_decode(data)?;
+            Self::erc721(d.token, d.tokenId)
+        } else if hook == addresses.erc1155_escrow_hook {
+            let d = ERC1155EscrowHook::HookData::abi_decode(data)?;
+            Self::erc1155(d.token, d.tokenId, d.amount)
+        } else if hook == addresses.native_token_escrow_hook {
+            let d = NativeTokenEscrowHook::HookData::abi_decode(data)?;
+            Self::native_token(d.amount)
+        } else if hook == addresses.attestation_escrow_hook {
+            let d = AttestationEscrowHook::HookData::abi_decode(data)?;
+            Self::Attestation(d.attestation)
+        } else if hook == addresses.attestation_reference_escrow_hook {
+            Self::AttestationReference(AttestationReferenceEscrowHook::HookData::abi_decode(data)?)
+        } else {
+            return Err(eyre::eyre!("{hook} is not a built-in escrow hook"));
+        };
+        Ok(asset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addresses() -> HookBasedAddresses {
+        HookBasedAddresses {
+            eas: Address::repeat_byte(0x01),
+            hook_escrow_obligation: Address::repeat_byte(0x02),
+            hooks_escrow_obligation: Address::repeat_byte(0x03),
+            erc20_escrow_hook: Address::repeat_byte(0x10),
+            erc721_escrow_hook: Address::repeat_byte(0x11),
+            erc1155_escrow_hook: Address::repeat_byte(0x12),
+            native_token_escrow_hook: Address::repeat_byte(0x13),
+            attestation_escrow_hook: Address::repeat_byte(0x14),
+            attestation_reference_escrow_hook: Address::repeat_byte(0x15),
+        }
+    }
+
+    #[test]
+    fn hook_data_roundtrips_through_matching_hook() -> eyre::Result<()> {
+        let addresses = addresses();
+        let token = Address::repeat_byte(0xaa);
+        let assets = [
+            HookAsset::erc20(token, U256::from(5)),
+            HookAsset::erc721(token, U256::from(7)),
+            HookAsset::erc1155(token, U256::from(7), U256::from(3)),
+            HookAsset::native_token(U256::from(9)),
+            HookAsset::attestation_reference(FixedBytes::repeat_byte(0xbb), token, 0, true),
+        ];
+
+        for asset in assets {
+            let hook = asset.hook(&addresses);
+            let decoded = HookAsset::decode(&addresses, hook, &asset.hook_data())?;
+            assert_eq!(decoded.hook(&addresses), hook);
+            assert_eq!(decoded.hook_data(), asset.hook_data());
+        }
+
+        let unknown = HookAsset::decode(&addresses, Address::ZERO, &Bytes::new());
+        assert!(unknown.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn only_native_and_attestation_hooks_take_value() {
+        assert_eq!(
+            HookAsset::native_token(U256::from(9)).value(),
+            U256::from(9)
+        );
+        assert_eq!(
+            HookAsset::erc20(Address::ZERO, U256::from(9)).value(),
+            U256::ZERO
+        );
+    }
+}
@@ -1,3 +1,14 @@
+//! Hook-based escrow obligations module
+//!
+//! This module provides functionality for hook-based escrows:
+//! - Single-hook and multi-hook escrow obligations
+//! - Typed hook data for the built-in hooks
+//! - Hook approvals
+
+pub mod escrow;
+pub mod hook_data;
+pub mod util;
+
 use alloy::{
     primitives::{Address, Bytes},
     signers::local::PrivateKeySigner,
@@ -14,10 +25,10 @@ use crate::{
 };
 
 /// Obligation data for a hook-based escrow with one hook.
-type HookEscrowData =
+pub type HookEscrowData =
     contracts::obligations::escrow::hook_based::HookEscrowObligation::ObligationData;
 /// Obligation data for a hook-based escrow with multiple hooks.
-type HooksEscrowData =
+pub type HooksEscrowData =
     contracts::obligations::escrow::hook_based::HooksEscrowObligation::ObligationData;
 
 impl_abi_conversions!(HookEscrowData);
@@ -99,8 +110,8 @@ pub enum HookBasedContract {
 /// automated audit tooling so far.
 #[derive(Clone)]
 pub struct HookBasedModule {
-    _signer: PrivateKeySigner,
-    _wallet_provider: SharedWalletProvider,
+    pub(crate) signer: PrivateKeySigner,
+    pub(crate) wallet_provider: SharedWalletProvider,
     pub addresses: HookBasedAddresses,
 }
 
@@ -132,12 +143,33 @@ impl HookBasedModule {
         addresses: Option<HookBasedAddresses>,
     ) -> eyre::Result<Self> {
         Ok(Self {
-            _signer: signer,
-            _wallet_provider: wallet_provider,
+            signer,
+            wallet_provider,
             addresses: addresses.unwrap_or_default(),
         })
     }
 
+    /// Access escrow API
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let asset = HookAsset::erc20(token, amount);
+    ///
+    /// // One asset through one hook
+    /// client.hook_based().escrow().hook().create(&asset, &item, expiration).await?;
+    ///
+    /// // Several assets through several hooks
+    /// client.hook_based().escrow().hooks().create(&[asset, nft], &item, expiration).await?;
+    /// ```
+    pub fn escrow(&self) -> escrow::Escrow<'_> {
+        escrow::Escrow::new(self)
+    }
+
+    /// Access utility API (hook approvals)
+    pub fn util(&self) -> util::Util<'_> {
+        util::Util::new(self)
+    }
+
     /// Encodes single-hook escrow obligation data.
     pub fn encode_hook_escrow(data: &HookEscrowData) -> Bytes {
         data.abi_encode().into()
@@ -0,0 +1,166 @@
+//! Hook approvals
+//!
+//! Hooks pull escrowed tokens from the escrow creator, and only accept
+//! `onLock` calls from escrow obligation contracts the creator approved in
+//! the hook. Both approvals are made here, skipping any already in place.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use alloy::{
+    primitives::{Address, U256},
+    rpc::types::TransactionReceipt,
+};
+
+use super::{HookBasedModule, hook_data::HookAsset};
+use crate::contracts::{self, obligations::escrow::hook_based::hooks::ERC20EscrowHook};
+
+// Every hook inherits `ApprovedEscrowHook`, so any hook binding can manage
+// escrow approvals.
+type AnyEscrowHook<P> = ERC20EscrowHook::ERC20EscrowHookInstance<P>;
+
+/// Utility API for hook approvals
+pub struct Util<'a> {
+    module: &'a HookBasedModule,
+}
+
+impl<'a> Util<'a> {
+    pub fn new(module: &'a HookBasedModule) -> Self {
+        Self { module }
+    }
+
+    /// Check whether the signer approved `escrow` to lock assets in `hook`.
+    pub async fn is_escrow_approved(&self, hook: Address, escrow: Address) -> eyre::Result<bool> {
+        let hook = AnyEscrowHook::new(hook, &self.module.wallet_provider);
+        let approved = hook
+            .isEscrowApproved(self.module.signer.address(), escrow)
+            .call()
+            .await?;
+        Ok(approved)
+    }
+
+    /// Approve `escrow` to lock the signer's assets in `hook`.
+    pub async fn approve_escrow(
+        &self,
+        hook: Address,
+        escrow: Address,
+    ) -> eyre::Result<TransactionReceipt> {
+        let hook = AnyEscrowHook::new(hook, &self.module.wallet_provider);
+        let receipt = hook
+            .approveEscrow(escrow)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+        Ok(receipt)
+    }
+
+    /// Revoke `escrow`'s approval to lock the signer's assets in `hook`.
+    pub async fn unapprove_escrow(
+        &self,
+        hook: Address,
+        escrow: Address,
+    ) -> eyre::Result<TransactionReceipt> {
+        let hook = AnyEscrowHook::new(hook, &self.module.wallet_provider);
+        let receipt = hook
+            .unapproveEscrow(escrow)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+        Ok(receipt)
+    }
+
+    /// Make every approval needed to escrow `assets` through `escrow`.
+    ///
+    /// Approves `escrow` in each hook involved and lets each hook pull its
+    /// tokens: ERC20 allowances cover the total per token, ERC721 tokens are
+    /// approved individually, and ERC1155 contracts are approved for all.
+    /// Approvals already in place are skipped.
+    ///
+    /// # Returns
+    /// * `Result<Vec<TransactionReceipt>>` - Receipts of the approvals sent
+    pub async fn approve_assets(
+        &self,
+        assets: &[HookAsset],
+        escrow: Address,
+    ) -> eyre::Result<Vec<TransactionReceipt>> {
+        let addresses = &self.module.addresses;
+        let owner = self.module.signer.address();
+        let mut receipts = Vec::new();
+
+        let hooks: BTreeSet<Address> = assets.iter().map(|a| a.hook(addresses)).collect();
+        for hook in hooks {
+            if !self.is_escrow_approved(hook, escrow).await? {
+                receipts.push(self.approve_escrow(hook, escrow).await?);
+            }
+        }
+
+        let mut erc20_totals: BTreeMap<Address, U256> = BTreeMap::new();
+        let mut erc1155_tokens = BTreeSet::new();
+        for asset in assets {
+            match asset {
+                HookAsset::Erc20(token) => {
+                    *erc20_totals.entry(token.address).or_default() += token.value;
+                }
+                HookAsset::Erc721(token) => {
+                    let hook = addresses.erc721_escrow_hook;
+                    let erc721 =
+                        contracts::IERC721::new(token.address, &self.module.wallet_provider);
+                    if erc721.getApproved(token.id).call().await? == hook
+                        || erc721.isApprovedForAll(owner, hook).call().await?
+                    {
+                        continue;
+                    }
+                    receipts.push(
+                        erc721
+                            .approve(hook, token.id)
+                            .send()
+                            .await?
+                            .get_receipt()
+                            .await?,
+                    );
+                }
+                HookAsset::Erc1155(token) => {
+                    erc1155_tokens.insert(token.address);
+                }
+                HookAsset::NativeToken(_)
+                | HookAsset::Attestation(_)
+                | HookAsset::AttestationReference(_) => {}
+            }
+        }
+
+        for (token, total) in erc20_totals {
+            let hook = addresses.erc20_escrow_hook;
+            let erc20 = contracts::IERC20::new(token, &self.module.wallet_provider);
+            if erc20.allowance(owner, hook).call().await? >= total {
+                continue;
+            }
+            receipts.push(
+                erc20
+                    .approve(hook, total)
+                    .send()
+                    .await?
+                    .get_receipt()
+                    .await?,
+            );
+        }
+
+        for token in erc1155_tokens {
+            let hook = addresses.erc1155_escrow_hook;
+            let erc1155 = contracts::IERC1155::new(token, &self.module.wallet_provider);
+            if erc1155.isApprovedForAll(owner, hook).call().await? {
+                continue;
+            }
+            receipts.push(
+                erc1155
+                    .setApprovalForAll(hook, true)
+                    .send()
+                    .await?
+                    .get_receipt()
+                    .await?,
+            );
+        }
+
+        Ok(receipts)
+    }
+}
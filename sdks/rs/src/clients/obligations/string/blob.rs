@@ -0,0 +1,397 @@
+//! Content-addressed storage for large `StringObligation` payloads.
+//!
+//! Instead of putting a large item on-chain, [`ContentAddressedStrings`]
+//! writes it to a [`BlobStore`] and puts a small [`BlobPointer`] (keccak256
+//! hash, URI and size) in the obligation's `item`. Reading through the same
+//! accessor fetches the blob again and checks it against the hash, so callers
+//! see the original item. Items under the size threshold stay inline.
+
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+};
+
+use tokio::io::AsyncReadExt as _;
+
+use alloy::{
+    primitives::{B256, Bytes, FixedBytes, keccak256},
+    rpc::types::TransactionReceipt,
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use super::StringObligationModule;
+use crate::{contracts::obligations::StringObligation, types::DecodedAttestation};
+
+/// Items at least this many bytes long are offloaded by default.
+pub const DEFAULT_BLOB_THRESHOLD: usize = 1024;
+
+/// Largest blob resolved by default. Pointers come from on-chain items that
+/// anyone can write, so their claimed size is checked against this before
+/// anything is fetched.
+pub const DEFAULT_MAX_BLOB_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Off-chain storage for payload bytes.
+///
+/// Implement this for IPFS, S3 or any other backend; [`FsBlobStore`] is the
+/// built-in local filesystem backend.
+pub trait BlobStore: Send + Sync {
+    /// Stores `bytes`, whose keccak256 is `hash`, and returns a URI that
+    /// [`BlobStore::get`] can later resolve.
+    fn put(&self, hash: B256, bytes: &[u8]) -> impl Future<Output = eyre::Result<String>> + Send;
+
+    /// Fetches the blob `pointer` refers to.
+    ///
+    /// `pointer` comes from an on-chain item written by whoever made the
+    /// obligation, so implementations must not trust its URI blindly and
+    /// must not read more than `pointer.size` bytes.
+    fn get(&self, pointer: &BlobPointer) -> impl Future<Output = eyre::Result<Vec<u8>>> + Send;
+}
+
+/// Stores blobs as files named by their hash in a local directory, with
+/// `file://` URIs.
+///
+/// Reads only ever open `<dir>/<hash>`; a pointer whose URI names any other
+/// path is rejected.
+#[derive(Debug, Clone)]
+pub struct FsBlobStore {
+    dir: PathBuf,
+}
+
+impl FsBlobStore {
+    /// Uses `dir` for blob files, creating it if needed.
+    pub fn new(dir: impl AsRef<Path>) -> eyre::Result<Self> {
+        std::fs::create_dir_all(dir.as_ref())?;
+        Ok(Self {
+            dir: std::fs::canonicalize(dir.as_ref())?,
+        })
+    }
+}
+
+impl BlobStore for FsBlobStore {
+    async fn put(&self, hash: B256, bytes: &[u8]) -> eyre::Result<String> {
+        let path = self.dir.join(hash.to_string());
+        tokio::fs::write(&path, bytes).await?;
+        Ok(format!("file://{}", path.display()))
+    }
+
+    async fn get(&self, pointer: &BlobPointer) -> eyre::Result<Vec<u8>> {
+        let path = self.dir.join(pointer.hash.to_string());
+        if pointer.uri != format!("file://{}", path.display()) {
+            return Err(eyre::eyre!(
+                "Blob URI {} is outside the store at {}",
+                pointer.uri,
+                self.dir.display()
+            ));
+        }
+
+        let file = tokio::fs::File::open(&path).await?;
+        let metadata = file.metadata().await?;
+        if !metadata.is_file() || metadata.len() != pointer.size {
+            return Err(eyre::eyre!(
+                "Blob file {} is {} bytes, expected {}",
+                path.display(),
+                metadata.len(),
+                pointer.size
+            ));
+        }
+        let mut bytes = Vec::with_capacity(pointer.size as usize);
+        file.take(pointer.size).read_to_end(&mut bytes).await?;
+        Ok(bytes)
+    }
+}
+
+/// Reference to an offloaded payload, stored in place of the item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobPointer {
+    /// keccak256 of the payload bytes.
+    pub hash: B256,
+    /// Location understood by the [`BlobStore`] that stored the payload.
+    pub uri: String,
+    /// Payload length in bytes.
+    pub size: u64,
+}
+
+/// On-chain item shape for an offloaded payload.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PointerItem {
+    #[serde(rename = "alkahest:blob")]
+    blob: BlobPointer,
+}
+
+impl BlobPointer {
+    /// Parses an obligation item as a pointer, or `None` for inline items.
+    pub fn parse(item: &str) -> Option<Self> {
+        serde_json::from_str::<PointerItem>(item)
+            .ok()
+            .map(|pointer| pointer.blob)
+    }
+
+    /// Serializes the pointer as an obligation item.
+    pub fn to_item(&self) -> eyre::Result<String> {
+        Ok(serde_json::to_string(&PointerItem { blob: self.clone() })?)
+    }
+}
+
+/// Offloads `item` to `store` if it is at least `threshold` bytes, returning
+/// the item to put on-chain.
+pub async fn offload_item<S: BlobStore>(
+    store: &S,
+    item: String,
+    threshold: usize,
+) -> eyre::Result<String> {
+    if item.len() < threshold {
+        return Ok(item);
+    }
+    let hash = keccak256(item.as_bytes());
+    let uri = store.put(hash, item.as_bytes()).await?;
+    BlobPointer {
+        hash,
+        uri,
+        size: item.len() as u64,
+    }
+    .to_item()
+}
+
+/// Resolves an on-chain item, fetching and verifying it if it is a pointer.
+///
+/// Pointers claiming more than `max_size` bytes are rejected before the
+/// store is asked for them.
+pub async fn resolve_item<S: BlobStore>(
+    store: &S,
+    item: String,
+    max_size: u64,
+) -> eyre::Result<String> {
+    let Some(pointer) = BlobPointer::parse(&item) else {
+        return Ok(item);
+    };
+    if pointer.size > max_size {
+        return Err(eyre::eyre!(
+            "Blob at {} claims {} bytes, over the {max_size} byte limit",
+            pointer.uri,
+            pointer.size
+        ));
+    }
+    let bytes = store.get(&pointer).await?;
+    let hash = keccak256(&bytes);
+    if hash != pointer.hash {
+        return Err(eyre::eyre!(
+            "Blob at {} hashes to {hash}, expected {}",
+            pointer.uri,
+            pointer.hash
+        ));
+    }
+    if bytes.len() as u64 != pointer.size {
+        return Err(eyre::eyre!(
+            "Blob at {} is {} bytes, expected {}",
+            pointer.uri,
+            bytes.len(),
+            pointer.size
+        ));
+    }
+    Ok(String::from_utf8(bytes)?)
+}
+
+/// [`StringObligationModule`] operations that offload large items to a
+/// [`BlobStore`].
+pub struct ContentAddressedStrings<'a, S: BlobStore> {
+    module: &'a StringObligationModule,
+    store: &'a S,
+    threshold: usize,
+    max_size: u64,
+}
+
+impl<'a, S: BlobStore> ContentAddressedStrings<'a, S> {
+    pub(super) fn new(module: &'a StringObligationModule, store: &'a S) -> Self {
+        Self {
+            module,
+            store,
+            threshold: DEFAULT_BLOB_THRESHOLD,
+            max_size: DEFAULT_MAX_BLOB_SIZE,
+        }
+    }
+
+    /// Offload items of at least `bytes` bytes. `0` offloads everything.
+    pub fn threshold(mut self, bytes: usize) -> Self {
+        self.threshold = bytes;
+        self
+    }
+
+    /// Refuse to resolve blobs larger than `bytes`.
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = bytes;
+        self
+    }
+
+    /// Creates a `StringObligation`, offloading `item` if it is large.
+    pub async fn do_obligation(
+        &self,
+        item: String,
+        schema: Option<B256>,
+        ref_uid: Option<FixedBytes<32>>,
+    ) -> eyre::Result<TransactionReceipt> {
+        let item = offload_item(self.store, item, self.threshold).await?;
+        self.module.do_obligation(item, schema, ref_uid).await
+    }
+
+    /// Serializes `obligation_data` to JSON and creates a `StringObligation`,
+    /// offloading the JSON if it is large.
+    pub async fn do_obligation_json<T: Serialize>(
+        &self,
+        obligation_data: T,
+        schema: Option<B256>,
+        ref_uid: Option<FixedBytes<32>>,
+    ) -> eyre::Result<TransactionReceipt> {
+        self.do_obligation(serde_json::to_string(&obligation_data)?, schema, ref_uid)
+            .await
+    }
+
+    /// Loads a fulfillment attestation with its item resolved from the store.
+    pub async fn get_obligation(
+        &self,
+        uid: FixedBytes<32>,
+    ) -> eyre::Result<DecodedAttestation<StringObligation::ObligationData>> {
+        let mut obligation = self.module.get_obligation(uid).await?;
+        obligation.data.item =
+            resolve_item(self.store, obligation.data.item, self.max_size).await?;
+        Ok(obligation)
+    }
+
+    /// Decodes ABI-encoded obligation data, resolving its item from the store.
+    pub async fn decode(
+        &self,
+        obligation_data: &Bytes,
+    ) -> eyre::Result<StringObligation::ObligationData> {
+        let mut data = StringObligationModule::decode(obligation_data)?;
+        data.item = resolve_item(self.store, data.item, self.max_size).await?;
+        Ok(data)
+    }
+
+    /// Decodes ABI-encoded obligation data as JSON, resolving its item from
+    /// the store.
+    pub async fn decode_json<T: DeserializeOwned>(
+        &self,
+        obligation_data: &Bytes,
+    ) -> eyre::Result<T> {
+        Ok(serde_json::from_str(
+            &self.decode(obligation_data).await?.item,
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_small_items_stay_inline() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsBlobStore::new(dir.path()).unwrap();
+
+        let item = offload_item(&store, "short".to_string(), 16).await.unwrap();
+        assert_eq!(item, "short");
+        assert!(BlobPointer::parse(&item).is_none());
+        assert_eq!(
+            resolve_item(&store, item, DEFAULT_MAX_BLOB_SIZE)
+                .await
+                .unwrap(),
+            "short"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_large_items_roundtrip_through_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsBlobStore::new(dir.path()).unwrap();
+        let payload = "x".repeat(4096);
+
+        let item = offload_item(&store, payload.clone(), DEFAULT_BLOB_THRESHOLD)
+            .await
+            .unwrap();
+        let pointer = BlobPointer::parse(&item).unwrap();
+        assert_eq!(pointer.hash, keccak256(payload.as_bytes()));
+        assert_eq!(pointer.size, 4096);
+        assert!(item.len() < 256);
+
+        assert_eq!(
+            resolve_item(&store, item, DEFAULT_MAX_BLOB_SIZE)
+                .await
+                .unwrap(),
+            payload
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tampered_blob_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsBlobStore::new(dir.path()).unwrap();
+
+        let item = offload_item(&store, "original".to_string(), 0)
+            .await
+            .unwrap();
+        let pointer = BlobPointer::parse(&item).unwrap();
+        let path = pointer.uri.strip_prefix("file://").unwrap();
+        std::fs::write(path, "tampered").unwrap();
+
+        assert!(
+            resolve_item(&store, item, DEFAULT_MAX_BLOB_SIZE)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pointer_outside_store_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsBlobStore::new(dir.path().join("blobs")).unwrap();
+        let outside = dir.path().join("secret");
+        std::fs::write(&outside, "secret").unwrap();
+
+        let item = BlobPointer {
+            hash: keccak256("secret"),
+            uri: format!("file://{}", outside.display()),
+            size: 6,
+        }
+        .to_item()
+        .unwrap();
+        assert!(
+            resolve_item(&store, item, DEFAULT_MAX_BLOB_SIZE)
+                .await
+                .is_err()
+        );
+
+        let huge = BlobPointer {
+            hash: keccak256("secret"),
+            uri: "file:///dev/zero".to_string(),
+            size: u64::MAX,
+        }
+        .to_item()
+        .unwrap();
+        assert!(
+            resolve_item(&store, huge, DEFAULT_MAX_BLOB_SIZE)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_size_mismatch_is_rejected_before_reading() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsBlobStore::new(dir.path()).unwrap();
+
+        let item = offload_item(&store, "x".repeat(64), 0).await.unwrap();
+        let mut pointer = BlobPointer::parse(&item).unwrap();
+        pointer.size = 8;
+        assert!(store.get(&pointer).await.is_err());
+        assert!(
+            resolve_item(&store, item, 32).await.is_err(),
+            "pointer over the size limit"
+        );
+    }
+
+    #[test]
+    fn test_pointer_parse_ignores_other_json() {
+        assert!(BlobPointer::parse(r#"{"hash":"0x00","uri":"a","size":1}"#).is_none());
+        assert!(BlobPointer::parse("not json").is_none());
+    }
+}
@@ -6,6 +6,12 @@ use crate::{
     types::{DecodedAttestation, ProviderContext, SharedWalletProvider},
 };
 
+pub mod blob;
+pub mod typed;
+
+pub use blob::{BlobPointer, BlobStore, ContentAddressedStrings, FsBlobStore};
+pub use typed::{TypedPayload, TypedStringObligation, schema_tag};
+
 // --- ABI conversions for String obligation types ---
 impl_abi_conversions!(contracts::obligations::StringObligation::ObligationData);
 use alloy::{
@@ -131,6 +137,37 @@ impl StringObligationModule {
         Ok(encoded)
     }
 
+    /// Typed API for payload type `T`, tagged with [`schema_tag::<T>()`].
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let receipt = client
+    ///     .string_obligation()
+    ///     .typed::<JobResult>()
+    ///     .do_obligation(&result, Some(escrow_uid))
+    ///     .await?;
+    /// ```
+    pub fn typed<T: TypedPayload>(&self) -> TypedStringObligation<'_, T> {
+        TypedStringObligation::new(self)
+    }
+
+    /// Operations that keep large items off-chain in `store`, putting only
+    /// a content hash and URI in the obligation.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let store = FsBlobStore::new("./blobs")?;
+    /// let blobs = client.string_obligation().content_addressed(&store);
+    /// let receipt = blobs.do_obligation_json(&large_result, None, Some(escrow_uid)).await?;
+    /// let result: JobResult = blobs.decode_json(&fulfillment.data).await?;
+    /// ```
+    pub fn content_addressed<'a, S: BlobStore>(
+        &'a self,
+        store: &'a S,
+    ) -> ContentAddressedStrings<'a, S> {
+        ContentAddressedStrings::new(self, store)
+    }
+
     pub async fn do_obligation(
         &self,
         item: String,
@@ -0,0 +1,369 @@
+//! Typed `StringObligation` payloads bound to a schema tag.
+//!
+//! A [`TypedPayload`] type is serialized to JSON in the obligation's `item`
+//! and tagged with [`schema_tag`], the keccak256 of the JSON Schema that
+//! `schemars` derives for the type, minus titles and descriptions. Encoding refuses values that fail
+//! [`TypedPayload::validate`]; decoding refuses items whose tag doesn't match
+//! or whose JSON doesn't fit the type. Oracles can use
+//! [`TypedStringObligation::arbitrate_with`] to only see fulfillments that
+//! carry their payload type.
+
+use std::marker::PhantomData;
+
+use alloy::{
+    primitives::{B256, Bytes, FixedBytes, keccak256},
+    rpc::types::TransactionReceipt,
+};
+use schemars::JsonSchema;
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::StringObligationModule;
+use crate::{
+    clients::arbiters::AttestationWithDemand,
+    contracts::{IEAS::Attestation, obligations::StringObligation},
+    types::DecodedAttestation,
+};
+
+/// A Rust type carried as JSON in a `StringObligation`.
+///
+/// Derive `JsonSchema` with the schemars version re-exported as
+/// [`alkahest_rs::schemars`](crate::schemars); another major version may
+/// derive a different schema and so a different [`schema_tag`].
+///
+/// # Example
+/// ```rust,ignore
+/// use alkahest_rs::schemars::{self, JsonSchema};
+///
+/// #[derive(Serialize, Deserialize, JsonSchema)]
+/// #[schemars(crate = "alkahest_rs::schemars")]
+/// struct JobResult {
+///     job_id: u64,
+///     output: String,
+/// }
+///
+/// impl TypedPayload for JobResult {
+///     fn validate(&self) -> eyre::Result<()> {
+///         eyre::ensure!(!self.output.is_empty(), "output must not be empty");
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait TypedPayload: Serialize + DeserializeOwned + JsonSchema {
+    /// Checks invariants the type system can't express. Runs on both encode
+    /// and decode; accepts everything by default.
+    fn validate(&self) -> eyre::Result<()> {
+        Ok(())
+    }
+}
+
+/// Schema tag for `T`: keccak256 of its derived JSON Schema document with
+/// every `title` and `description` removed, serialized compactly with object
+/// keys sorted.
+///
+/// Renaming the top-level type or editing doc comments keeps the tag, but
+/// any change to field names, types or serde attributes changes it. Nested
+/// types appear under `$defs` by name, so renaming those changes it too.
+/// Other SDKs producing the same payload must hash the same normalized
+/// schema document.
+pub fn schema_tag<T: JsonSchema>() -> B256 {
+    let mut schema = schemars::schema_for!(T).to_value();
+    strip_annotations(&mut schema);
+    let mut canonical = String::new();
+    write_canonical(&schema, &mut canonical);
+    keccak256(canonical)
+}
+
+/// Writes `value` as compact JSON with object keys sorted at every level, so
+/// the output doesn't depend on serde_json's `preserve_order` feature.
+fn write_canonical(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::from(key.as_str()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// Removes `title` and `description` keywords from a schema and its
+/// subschemas, leaving property names and literal values untouched.
+fn strip_annotations(schema: &mut serde_json::Value) {
+    match schema {
+        serde_json::Value::Object(map) => {
+            map.remove("title");
+            map.remove("description");
+            for (keyword, value) in map.iter_mut() {
+                match keyword.as_str() {
+                    // Maps from names to subschemas.
+                    "properties" | "patternProperties" | "$defs" | "definitions"
+                    | "dependentSchemas" => {
+                        if let serde_json::Value::Object(subschemas) = value {
+                            subschemas.values_mut().for_each(strip_annotations);
+                        }
+                    }
+                    // Literal JSON values, not schemas.
+                    "const" | "enum" | "default" | "examples" | "required" => {}
+                    _ => strip_annotations(value),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(strip_annotations),
+        _ => {}
+    }
+}
+
+/// Typed view of [`StringObligationModule`] for payload type `T`.
+pub struct TypedStringObligation<'a, T> {
+    module: &'a StringObligationModule,
+    _payload: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: TypedPayload> TypedStringObligation<'a, T> {
+    pub(super) fn new(module: &'a StringObligationModule) -> Self {
+        Self {
+            module,
+            _payload: PhantomData,
+        }
+    }
+
+    /// Schema tag written to and expected in `ObligationData.schema`.
+    pub fn schema(&self) -> B256 {
+        schema_tag::<T>()
+    }
+
+    /// Builds validated obligation data for `value`.
+    pub fn to_obligation_data(value: &T) -> eyre::Result<StringObligation::ObligationData> {
+        value.validate()?;
+        Ok(StringObligation::ObligationData {
+            item: serde_json::to_string(value)?,
+            schema: schema_tag::<T>(),
+        })
+    }
+
+    /// Parses and validates obligation data, checking its schema tag.
+    pub fn from_obligation_data(data: &StringObligation::ObligationData) -> eyre::Result<T> {
+        let expected = schema_tag::<T>();
+        if data.schema != expected {
+            return Err(eyre::eyre!(
+                "Schema tag mismatch: expected {expected}, got {}",
+                data.schema
+            ));
+        }
+        let value: T = serde_json::from_str(&data.item)?;
+        value.validate()?;
+        Ok(value)
+    }
+
+    /// ABI-encodes a validated, tagged obligation for `value`.
+    pub fn encode(value: &T) -> eyre::Result<Bytes> {
+        Ok(StringObligationModule::encode(&Self::to_obligation_data(
+            value,
+        )?))
+    }
+
+    /// Decodes ABI-encoded obligation data into `T`, checking tag and validity.
+    pub fn decode(obligation_data: &Bytes) -> eyre::Result<T> {
+        Self::from_obligation_data(&StringObligationModule::decode(obligation_data)?)
+    }
+
+    /// Decodes a fulfillment attestation's data into `T`.
+    pub fn decode_attestation(attestation: &Attestation) -> eyre::Result<T> {
+        Self::decode(&attestation.data)
+    }
+
+    /// Whether `obligation_data` carries this payload type's schema tag.
+    /// Doesn't parse the item.
+    pub fn matches(obligation_data: &Bytes) -> bool {
+        StringObligationModule::decode(obligation_data)
+            .is_ok_and(|data| data.schema == schema_tag::<T>())
+    }
+
+    /// Creates a `StringObligation` attestation for a validated `value`.
+    pub async fn do_obligation(
+        &self,
+        value: &T,
+        ref_uid: Option<FixedBytes<32>>,
+    ) -> eyre::Result<TransactionReceipt> {
+        let data = Self::to_obligation_data(value)?;
+        self.module
+            .do_obligation(data.item, Some(data.schema), ref_uid)
+            .await
+    }
+
+    /// Loads a fulfillment attestation and decodes it into `T`.
+    pub async fn get_obligation(&self, uid: FixedBytes<32>) -> eyre::Result<DecodedAttestation<T>> {
+        let obligation = self.module.get_obligation(uid).await?;
+        Ok(DecodedAttestation {
+            data: Self::from_obligation_data(&obligation.data)?,
+            attestation: obligation.attestation,
+        })
+    }
+
+    /// Wraps a typed oracle handler for `TrustedOracleModule::arbitrate_many_*`.
+    ///
+    /// Fulfillments with another schema tag, or that fail to decode or
+    /// validate as `T`, are skipped (`None`) without reaching `arbitrate`.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let arbitrate = TypedStringObligation::<JobResult>::arbitrate_with(
+    ///     |result: &JobResult, _| Some(result.output == "ok"),
+    /// );
+    /// client.oracle().arbitrate_many_sync(arbitrate, on_decision, mode).await?;
+    /// ```
+    pub fn arbitrate_with<Arbitrate>(
+        arbitrate: Arbitrate,
+    ) -> impl Fn(&AttestationWithDemand) -> Option<bool> + Clone + Send + Sync + 'static
+    where
+        Arbitrate: Fn(&T, &AttestationWithDemand) -> Option<bool> + Clone + Send + Sync + 'static,
+    {
+        move |request: &AttestationWithDemand| {
+            let value = Self::decode_attestation(&request.attestation).ok()?;
+            arbitrate(&value, request)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::b256;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+    struct JobResult {
+        job_id: u64,
+        output: String,
+    }
+
+    impl TypedPayload for JobResult {
+        fn validate(&self) -> eyre::Result<()> {
+            eyre::ensure!(!self.output.is_empty(), "output must not be empty");
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, JsonSchema)]
+    struct Other {
+        job_id: u64,
+    }
+
+    impl TypedPayload for Other {}
+
+    type Typed = TypedStringObligation<'static, JobResult>;
+
+    #[test]
+    fn test_schema_tag_is_stable_and_type_specific() {
+        assert_eq!(schema_tag::<JobResult>(), schema_tag::<JobResult>());
+        assert_ne!(schema_tag::<JobResult>(), schema_tag::<Other>());
+        assert_ne!(schema_tag::<JobResult>(), B256::ZERO);
+    }
+
+    /// Same shape as [`JobResult`] under another name and docs.
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    struct RenamedJobResult {
+        /// Job identifier.
+        job_id: u64,
+        output: String,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    struct Titled {
+        title: String,
+        description: String,
+    }
+
+    #[test]
+    fn test_schema_tag_ignores_names_and_docs() {
+        assert_eq!(schema_tag::<JobResult>(), schema_tag::<RenamedJobResult>());
+
+        // Fields called `title`/`description` are part of the shape.
+        let mut schema = schemars::schema_for!(Titled).to_value();
+        strip_annotations(&mut schema);
+        let properties = schema["properties"].as_object().unwrap();
+        assert!(properties.contains_key("title"));
+        assert!(properties.contains_key("description"));
+        assert!(schema.get("title").is_none());
+    }
+
+    #[test]
+    fn test_canonical_json_sorts_keys() {
+        let value = serde_json::json!({"b": [{"d": 1, "c": null}], "a": "x"});
+        let mut out = String::new();
+        write_canonical(&value, &mut out);
+        assert_eq!(out, r#"{"a":"x","b":[{"c":null,"d":1}]}"#);
+    }
+
+    #[test]
+    fn test_schema_tag_is_pinned() {
+        // Other SDKs must produce this exact tag for the same payload shape;
+        // if it changes, every existing typed obligation stops decoding.
+        assert_eq!(
+            schema_tag::<JobResult>(),
+            b256!("0xeb935e78390e10857b19a99d9caf94166b8f702d5ffce03bf9bff3630f64bdb7")
+        );
+    }
+
+    #[test]
+    fn test_typed_roundtrip() {
+        let value = JobResult {
+            job_id: 7,
+            output: "ok".to_string(),
+        };
+        let encoded = Typed::encode(&value).unwrap();
+        assert!(Typed::matches(&encoded));
+        assert!(!TypedStringObligation::<Other>::matches(&encoded));
+        assert_eq!(Typed::decode(&encoded).unwrap(), value);
+    }
+
+    #[test]
+    fn test_encode_rejects_invalid_value() {
+        let value = JobResult {
+            job_id: 7,
+            output: String::new(),
+        };
+        assert!(Typed::encode(&value).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_wrong_tag_and_shape() {
+        // Right shape, untagged.
+        let untagged = StringObligationModule::encode_json(
+            JobResult {
+                job_id: 1,
+                output: "ok".to_string(),
+            },
+            None,
+        )
+        .unwrap();
+        assert!(Typed::decode(&untagged).is_err());
+
+        // Right tag, wrong shape.
+        let misshapen = StringObligationModule::encode_json(
+            Other { job_id: 1 },
+            Some(schema_tag::<JobResult>()),
+        )
+        .unwrap();
+        assert!(Typed::decode(&misshapen).is_err());
+    }
+}
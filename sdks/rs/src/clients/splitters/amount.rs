@@ -0,0 +1,381 @@
+//! Amount splitter client
+//!
+//! `ERC20Splitter`, `ERC1155Splitter` and `NativeTokenSplitter` share one
+//! ABI: an oracle records `(recipient, amount)` splits that must add up to
+//! the escrowed amount, then anyone can collect the escrow through the
+//! splitter and pay them out. This client covers all three, selected by
+//! [`AmountSplitterKind`].
+
+use std::future::Future;
+
+use alloy::{
+    primitives::{Address, Bytes, FixedBytes, U256},
+    rpc::types::TransactionReceipt,
+    sol_types::SolValue as _,
+};
+
+use super::{
+    AmountSplit, AnySplitter, EXECUTOR_SENTINEL, MAX_SPLITS, SplittersModule,
+    events::Distribution,
+    oracle::{
+        AmountArbitrateManyResult, AmountFamily, AmountSplitDecision, AmountSplitRequest,
+        SplitterOracle,
+    },
+};
+use crate::{
+    clients::arbiters::ArbitrationMode,
+    contracts::obligations::escrow::default_escrow::{
+        ERC20EscrowObligation, ERC1155EscrowObligation, NativeTokenEscrowObligation,
+    },
+};
+
+/// Which amount splitter a client talks to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AmountSplitterKind {
+    Erc20,
+    Erc1155,
+    NativeToken,
+}
+
+/// Builder for amount splits
+///
+/// # Example
+/// ```rust,ignore
+/// let splits = AmountSplits::new()
+///     .pay(alice, U256::from(60))
+///     .pay(bob, U256::from(30))
+///     .pay_executor(U256::from(10))
+///     .build(U256::from(100))?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AmountSplits {
+    splits: Vec<AmountSplit>,
+}
+
+impl AmountSplits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pay `amount` to `recipient`
+    pub fn pay(mut self, recipient: Address, amount: U256) -> Self {
+        self.splits.push(AmountSplit { recipient, amount });
+        self
+    }
+
+    /// Pay `amount` to whoever created the fulfillment through the splitter
+    pub fn pay_executor(self, amount: U256) -> Self {
+        self.pay(EXECUTOR_SENTINEL, amount)
+    }
+
+    /// Sum of all split amounts so far
+    pub fn total(&self) -> U256 {
+        self.splits.iter().map(|s| s.amount).sum()
+    }
+
+    /// Finish the splits, checking them against the escrowed amount.
+    pub fn build(self, escrow_amount: U256) -> eyre::Result<Vec<AmountSplit>> {
+        validate_amount_splits(&self.splits, escrow_amount)?;
+        Ok(self.splits)
+    }
+}
+
+impl From<AmountSplits> for Vec<AmountSplit> {
+    fn from(splits: AmountSplits) -> Self {
+        splits.splits
+    }
+}
+
+/// Check `splits` the way the splitter's `arbitrate` does: between one and
+/// [`MAX_SPLITS`] splits, adding up to exactly `escrow_amount`.
+pub fn validate_amount_splits(splits: &[AmountSplit], escrow_amount: U256) -> eyre::Result<()> {
+    validate_split_count(splits.len())?;
+    let total = splits
+        .iter()
+        .try_fold(U256::ZERO, |total, s| total.checked_add(s.amount))
+        .ok_or_else(|| eyre::eyre!("Split amounts overflow uint256"))?;
+    if total != escrow_amount {
+        return Err(eyre::eyre!(
+            "Splits total {total} but the escrow holds {escrow_amount}"
+        ));
+    }
+    Ok(())
+}
+
+/// Amount held by an escrow of the obligation `kind` splits, decoded from
+/// its obligation data.
+pub(super) fn decode_escrow_amount(kind: AmountSplitterKind, data: &[u8]) -> eyre::Result<U256> {
+    Ok(match kind {
+        AmountSplitterKind::Erc20 => {
+            ERC20EscrowObligation::ObligationData::abi_decode(data)?.amount
+        }
+        AmountSplitterKind::Erc1155 => {
+            ERC1155EscrowObligation::ObligationData::abi_decode(data)?.amount
+        }
+        AmountSplitterKind::NativeToken => {
+            NativeTokenEscrowObligation::ObligationData::abi_decode(data)?.amount
+        }
+    })
+}
+
+pub(super) fn validate_split_count(count: usize) -> eyre::Result<()> {
+    if count == 0 {
+        return Err(eyre::eyre!("At least one split is required"));
+    }
+    if count > MAX_SPLITS {
+        return Err(eyre::eyre!(
+            "At most {MAX_SPLITS} splits are allowed, got {count}"
+        ));
+    }
+    Ok(())
+}
+
+/// Amount splitter API for any [`AmountSplitterKind`]
+pub struct AmountSplitter<'a> {
+    module: &'a SplittersModule,
+    kind: AmountSplitterKind,
+}
+
+impl<'a> AmountSplitter<'a> {
+    pub fn new(module: &'a SplittersModule, kind: AmountSplitterKind) -> Self {
+        Self { module, kind }
+    }
+
+    /// The splitter variant this client talks to
+    pub fn kind(&self) -> AmountSplitterKind {
+        self.kind
+    }
+
+    /// Get the contract address
+    pub fn address(&self) -> Address {
+        match self.kind {
+            AmountSplitterKind::Erc20 => self.module.addresses.erc20_splitter,
+            AmountSplitterKind::Erc1155 => self.module.addresses.erc1155_splitter,
+            AmountSplitterKind::NativeToken => self.module.addresses.native_token_splitter,
+        }
+    }
+
+    /// Amount held by `escrow`, decoded from its obligation data
+    pub async fn escrow_amount(&self, escrow: FixedBytes<32>) -> eyre::Result<U256> {
+        let attestation = self
+            .module
+            .escrow_attestation_on(self.address(), escrow)
+            .await?;
+        decode_escrow_amount(self.kind, &attestation.data)
+    }
+
+    /// Ask `oracle` to decide splits for a fulfillment of `escrow`.
+    ///
+    /// Only the escrow's attester or recipient can request arbitration.
+    pub async fn request_arbitration(
+        &self,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+        oracle: Address,
+        demand: Bytes,
+    ) -> eyre::Result<TransactionReceipt> {
+        self.module
+            .request_arbitration_on(self.address(), fulfillment, escrow, oracle, demand)
+            .await
+    }
+
+    /// Record the signer's split decision as oracle.
+    ///
+    /// The splits are checked against the escrowed amount before sending.
+    pub async fn arbitrate(
+        &self,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+        splits: impl Into<Vec<AmountSplit>>,
+    ) -> eyre::Result<TransactionReceipt> {
+        let splits = splits.into();
+        validate_amount_splits(&splits, self.escrow_amount(escrow).await?)?;
+
+        // All three amount splitters take the same `Split` tuple.
+        let receipt = AnySplitter::new(self.address(), &self.module.wallet_provider)
+            .arbitrate(fulfillment, escrow, splits)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+        Ok(receipt)
+    }
+
+    /// Splits `oracle` recorded for a fulfillment of `escrow`
+    pub async fn get_splits(
+        &self,
+        oracle: Address,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+    ) -> eyre::Result<Vec<AmountSplit>> {
+        let splits = AnySplitter::new(self.address(), &self.module.wallet_provider)
+            .getSplits(oracle, fulfillment, escrow)
+            .call()
+            .await?;
+        Ok(splits)
+    }
+
+    /// Check whether `oracle` has decided splits for a fulfillment of `escrow`
+    pub async fn has_decision(
+        &self,
+        oracle: Address,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+    ) -> eyre::Result<bool> {
+        self.module
+            .has_decision_on(self.address(), oracle, fulfillment, escrow)
+            .await
+    }
+
+    /// Create a fulfillment owned by the splitter, recording the signer as
+    /// its fulfiller so [`EXECUTOR_SENTINEL`] splits pay the signer.
+    ///
+    /// Calls `doObligationRaw(data, expiration, ref_uid)` on
+    /// `obligation_contract`, forwarding `value`. The fulfillment UID is in
+    /// the receipt's [`super::events::FulfillmentCreated`] event.
+    pub async fn create_fulfillment(
+        &self,
+        obligation_contract: Address,
+        data: Bytes,
+        expiration: u64,
+        ref_uid: FixedBytes<32>,
+        value: U256,
+    ) -> eyre::Result<TransactionReceipt> {
+        self.module
+            .create_fulfillment_on(
+                self.address(),
+                obligation_contract,
+                data,
+                expiration,
+                ref_uid,
+                value,
+            )
+            .await
+    }
+
+    /// Fulfiller recorded for a splitter-owned fulfillment, if any
+    pub async fn fulfiller(&self, fulfillment: FixedBytes<32>) -> eyre::Result<Option<Address>> {
+        self.module.fulfiller_on(self.address(), fulfillment).await
+    }
+
+    /// Collect `escrow` from `escrow_contract` and pay out the recorded
+    /// splits, reverting if any payout fails.
+    pub async fn collect_and_distribute(
+        &self,
+        escrow_contract: Address,
+        escrow: FixedBytes<32>,
+        fulfillment: FixedBytes<32>,
+    ) -> eyre::Result<TransactionReceipt> {
+        self.module
+            .collect_and_distribute_on(self.address(), escrow_contract, escrow, fulfillment, false)
+            .await
+    }
+
+    /// Collect `escrow` and pay out the recorded splits, skipping payouts
+    /// that fail. Skipped funds stay in the splitter; use only when
+    /// [`Self::collect_and_distribute`] is permanently blocked.
+    pub async fn unsafe_partially_collect_and_distribute(
+        &self,
+        escrow_contract: Address,
+        escrow: FixedBytes<32>,
+        fulfillment: FixedBytes<32>,
+    ) -> eyre::Result<TransactionReceipt> {
+        self.module
+            .collect_and_distribute_on(self.address(), escrow_contract, escrow, fulfillment, true)
+            .await
+    }
+
+    /// Decode the distribution logged by this splitter in `receipt`
+    pub fn distribution(&self, receipt: &TransactionReceipt) -> eyre::Result<Distribution> {
+        Distribution::from_receipt(receipt, self.address())
+    }
+
+    /// Answer this splitter's arbitration requests as oracle, based on `mode`
+    ///
+    /// # Arguments
+    /// * `arbitrate` - Sync callback returning the splits for a request, or `None` to skip it
+    /// * `on_decision` - Callback invoked after each decision on a future request
+    /// * `mode` - Which requests to process (see `ArbitrationMode`)
+    ///
+    /// Splits are checked against what the escrow holds before being sent.
+    pub async fn arbitrate_many_sync<
+        Arbitrate: Fn(&AmountSplitRequest) -> Option<Vec<AmountSplit>> + Clone + Send + Sync + 'static,
+        OnDecisionFut: Future<Output = ()> + Send + 'static,
+        OnDecision: Fn(&AmountSplitDecision) -> OnDecisionFut + Clone + Send + Sync + 'static,
+    >(
+        &self,
+        arbitrate: Arbitrate,
+        on_decision: OnDecision,
+        mode: ArbitrationMode,
+    ) -> eyre::Result<AmountArbitrateManyResult> {
+        self.arbitrate_many_async(
+            move |request: &AmountSplitRequest| std::future::ready(arbitrate(request)),
+            on_decision,
+            mode,
+        )
+        .await
+    }
+
+    /// Answer this splitter's arbitration requests as oracle (async callback version)
+    ///
+    /// See [`Self::arbitrate_many_sync`].
+    pub async fn arbitrate_many_async<
+        ArbitrateFut: Future<Output = Option<Vec<AmountSplit>>> + Send + 'static,
+        Arbitrate: Fn(&AmountSplitRequest) -> ArbitrateFut + Clone + Send + Sync + 'static,
+        OnDecisionFut: Future<Output = ()> + Send + 'static,
+        OnDecision: Fn(&AmountSplitDecision) -> OnDecisionFut + Clone + Send + Sync + 'static,
+    >(
+        &self,
+        arbitrate: Arbitrate,
+        on_decision: OnDecision,
+        mode: ArbitrationMode,
+    ) -> eyre::Result<AmountArbitrateManyResult> {
+        SplitterOracle::new(
+            self.module,
+            AmountFamily {
+                address: self.address(),
+                kind: self.kind,
+            },
+        )
+        .arbitrate_many(arbitrate, on_decision, mode)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amount_splits_must_match_escrow_amount() {
+        let alice = Address::repeat_byte(0x01);
+        let splits = AmountSplits::new()
+            .pay(alice, U256::from(60))
+            .pay_executor(U256::from(40));
+        assert_eq!(splits.total(), U256::from(100));
+        assert!(splits.clone().build(U256::from(99)).is_err());
+
+        let splits = splits.build(U256::from(100)).unwrap();
+        assert_eq!(splits[1].recipient, EXECUTOR_SENTINEL);
+
+        assert!(AmountSplits::new().build(U256::ZERO).is_err());
+        let too_many =
+            (0..=MAX_SPLITS).fold(AmountSplits::new(), |s, _| s.pay(alice, U256::from(1)));
+        assert!(too_many.build(U256::from(MAX_SPLITS + 1)).is_err());
+    }
+
+    #[test]
+    fn overflowing_splits_are_rejected() {
+        let splits = vec![
+            AmountSplit {
+                recipient: Address::ZERO,
+                amount: U256::MAX,
+            },
+            AmountSplit {
+                recipient: Address::ZERO,
+                amount: U256::from(1),
+            },
+        ];
+        assert!(validate_amount_splits(&splits, U256::ZERO).is_err());
+    }
+}
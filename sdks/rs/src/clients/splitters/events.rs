@@ -0,0 +1,322 @@
+//! Splitter fulfillment and distribution events
+//!
+//! `collectAndDistribute` logs one `EscrowCollectedAndDistributed` event,
+//! and `unsafePartiallyCollectAndDistribute` additionally logs a
+//! `*TransferFailedOnDistribute` event for each payout that failed and was
+//! left in the splitter. [`Distribution::from_receipt`] gathers both.
+
+use alloy::{
+    primitives::{Address, FixedBytes, Log, U256},
+    rpc::types::TransactionReceipt,
+    sol_types::SolEvent,
+};
+
+use super::AmountSplit;
+use crate::contracts::utils::splitters::{
+    ERC20Splitter, ERC1155Splitter, NativeTokenSplitter, token_bundle::TokenBundleSplitter,
+};
+
+/// `FulfillmentCreated` as emitted by any splitter
+pub type FulfillmentCreated = ERC20Splitter::FulfillmentCreated;
+/// `ArbitrationRequested` as emitted by any splitter
+pub type ArbitrationRequested = ERC20Splitter::ArbitrationRequested;
+
+/// A payout `unsafePartiallyCollectAndDistribute` could not make.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailedDistribution {
+    Erc20 {
+        recipient: Address,
+        token: Address,
+        amount: U256,
+    },
+    Erc721 {
+        recipient: Address,
+        token: Address,
+        token_id: U256,
+    },
+    Erc1155 {
+        recipient: Address,
+        token: Address,
+        token_id: U256,
+        amount: U256,
+    },
+    NativeToken {
+        recipient: Address,
+        amount: U256,
+    },
+}
+
+/// An escrow collected and distributed by a splitter.
+#[derive(Debug, Clone)]
+pub struct Distribution {
+    pub escrow: FixedBytes<32>,
+    pub fulfillment: FixedBytes<32>,
+    /// Fulfiller recorded by `createFulfillment`, or zero if none was.
+    pub fulfiller: Address,
+    /// Distributed token, for ERC20 and ERC1155 splitters
+    pub token: Option<Address>,
+    /// Distributed token ID, for ERC1155 splitters
+    pub token_id: Option<U256>,
+    /// Splits as logged by amount splitters. Token-bundle splitters do not
+    /// log their splits, so this is empty for them.
+    pub splits: Vec<AmountSplit>,
+    /// Payouts that failed during a partial distribution
+    pub failed: Vec<FailedDistribution>,
+}
+
+impl Distribution {
+    /// Decode the distribution `splitter` logged in `receipt`.
+    pub fn from_receipt(receipt: &TransactionReceipt, splitter: Address) -> eyre::Result<Self> {
+        let mut distribution = None;
+        let mut failed = Vec::new();
+
+        for log in receipt.inner.logs() {
+            if log.address() != splitter {
+                continue;
+            }
+            let Some(topic0) = log.topic0() else {
+                continue;
+            };
+            let log = &log.inner;
+
+            if let Some(d) = decode_distribution(*topic0, log)? {
+                distribution = Some(d);
+            } else if let Some(f) = decode_failure(*topic0, log)? {
+                failed.push(f);
+            }
+        }
+
+        let mut distribution = distribution.ok_or_else(|| {
+            eyre::eyre!("No EscrowCollectedAndDistributed event from splitter {splitter}")
+        })?;
+        distribution.failed = failed;
+        Ok(distribution)
+    }
+}
+
+/// Get the `FulfillmentCreated` event from a `createFulfillment` receipt.
+pub fn fulfillment_created(receipt: &TransactionReceipt) -> eyre::Result<FulfillmentCreated> {
+    receipt
+        .inner
+        .logs()
+        .iter()
+        .find(|log| log.topic0() == Some(&FulfillmentCreated::SIGNATURE_HASH))
+        .ok_or_else(|| eyre::eyre!("No FulfillmentCreated event found"))?
+        .log_decode::<FulfillmentCreated>()
+        .map(|log| log.inner.data)
+        .map_err(Into::into)
+}
+
+fn decode_distribution(topic0: FixedBytes<32>, log: &Log) -> eyre::Result<Option<Distribution>> {
+    let distribution = match topic0 {
+        ERC20Splitter::EscrowCollectedAndDistributed::SIGNATURE_HASH => {
+            let e = ERC20Splitter::EscrowCollectedAndDistributed::decode_log_data(&log.data)?;
+            Distribution {
+                escrow: e.escrowUid,
+                fulfillment: e.fulfillmentUid,
+                fulfiller: e.fulfiller,
+                token: Some(e.token),
+                token_id: None,
+                splits: e.splits,
+                failed: Vec::new(),
+            }
+        }
+        ERC1155Splitter::EscrowCollectedAndDistributed::SIGNATURE_HASH => {
+            let e = ERC1155Splitter::EscrowCollectedAndDistributed::decode_log_data(&log.data)?;
+            Distribution {
+                escrow: e.escrow,
+                fulfillment: e.fulfillment,
+                fulfiller: e.fulfiller,
+                token: Some(e.token),
+                token_id: Some(e.tokenId),
+                splits: e
+                    .splits
+                    .into_iter()
+                    .map(|s| AmountSplit {
+                        recipient: s.recipient,
+                        amount: s.amount,
+                    })
+                    .collect(),
+                failed: Vec::new(),
+            }
+        }
+        NativeTokenSplitter::EscrowCollectedAndDistributed::SIGNATURE_HASH => {
+            let e = NativeTokenSplitter::EscrowCollectedAndDistributed::decode_log_data(&log.data)?;
+            Distribution {
+                escrow: e.escrowUid,
+                fulfillment: e.fulfillmentUid,
+                fulfiller: e.fulfiller,
+                token: None,
+                token_id: None,
+                splits: e
+                    .splits
+                    .into_iter()
+                    .map(|s| AmountSplit {
+                        recipient: s.recipient,
+                        amount: s.amount,
+                    })
+                    .collect(),
+                failed: Vec::new(),
+            }
+        }
+        TokenBundleSplitter::EscrowCollectedAndDistributed::SIGNATURE_HASH => {
+            let e = TokenBundleSplitter::EscrowCollectedAndDistributed::decode_log_data(&log.data)?;
+            Distribution {
+                escrow: e.escrowUid,
+                fulfillment: e.fulfillmentUid,
+                fulfiller: e.fulfiller,
+                token: None,
+                token_id: None,
+                splits: Vec::new(),
+                failed: Vec::new(),
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(distribution))
+}
+
+// The failure events have the same signatures in every splitter that emits
+// them, so the token-bundle binding decodes them all.
+fn decode_failure(topic0: FixedBytes<32>, log: &Log) -> eyre::Result<Option<FailedDistribution>> {
+    let failure = match topic0 {
+        TokenBundleSplitter::ERC20TransferFailedOnDistribute::SIGNATURE_HASH => {
+            let e =
+                TokenBundleSplitter::ERC20TransferFailedOnDistribute::decode_log_data(&log.data)?;
+            FailedDistribution::Erc20 {
+                recipient: e.recipient,
+                token: e.token,
+                amount: e.amount,
+            }
+        }
+        TokenBundleSplitter::ERC721TransferFailedOnDistribute::SIGNATURE_HASH => {
+            let e =
+                TokenBundleSplitter::ERC721TransferFailedOnDistribute::decode_log_data(&log.data)?;
+            FailedDistribution::Erc721 {
+                recipient: e.recipient,
+                token: e.token,
+                token_id: e.tokenId,
+            }
+        }
+        TokenBundleSplitter::ERC1155TransferFailedOnDistribute::SIGNATURE_HASH => {
+            let e =
+                TokenBundleSplitter::ERC1155TransferFailedOnDistribute::decode_log_data(&log.data)?;
+            FailedDistribution::Erc1155 {
+                recipient: e.recipient,
+                token: e.token,
+                token_id: e.tokenId,
+                amount: e.amount,
+            }
+        }
+        TokenBundleSplitter::NativeTransferFailedOnDistribute::SIGNATURE_HASH => {
+            let e =
+                TokenBundleSplitter::NativeTransferFailedOnDistribute::decode_log_data(&log.data)?;
+            FailedDistribution::NativeToken {
+                recipient: e.recipient,
+                amount: e.amount,
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(failure))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        consensus::{Receipt, ReceiptEnvelope, ReceiptWithBloom},
+        primitives::{B256, Bloom},
+        rpc::types::Log as RpcLog,
+    };
+
+    use super::*;
+
+    fn receipt(logs: Vec<Log>) -> TransactionReceipt {
+        let logs = logs
+            .into_iter()
+            .map(|inner| RpcLog {
+                inner,
+                ..Default::default()
+            })
+            .collect();
+        TransactionReceipt {
+            inner: ReceiptEnvelope::Eip1559(ReceiptWithBloom {
+                receipt: Receipt {
+                    status: true.into(),
+                    cumulative_gas_used: 0,
+                    logs,
+                },
+                logs_bloom: Bloom::default(),
+            }),
+            transaction_hash: B256::ZERO,
+            transaction_index: None,
+            block_hash: None,
+            block_number: None,
+            gas_used: 0,
+            effective_gas_price: 0,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: Address::ZERO,
+            to: None,
+            contract_address: None,
+        }
+    }
+
+    fn log(address: Address, event: &impl SolEvent) -> Log {
+        Log {
+            address,
+            data: event.encode_log_data(),
+        }
+    }
+
+    #[test]
+    fn decodes_partial_native_distribution() -> eyre::Result<()> {
+        let splitter = Address::repeat_byte(0x55);
+        let recipient = Address::repeat_byte(0x01);
+        let split = NativeTokenSplitter::Split {
+            recipient,
+            amount: U256::from(7),
+        };
+        let receipt = receipt(vec![
+            log(
+                splitter,
+                &TokenBundleSplitter::NativeTransferFailedOnDistribute {
+                    recipient,
+                    amount: U256::from(7),
+                },
+            ),
+            log(
+                splitter,
+                &NativeTokenSplitter::EscrowCollectedAndDistributed {
+                    escrowUid: FixedBytes::repeat_byte(0x0e),
+                    fulfillmentUid: FixedBytes::repeat_byte(0x0f),
+                    fulfiller: Address::ZERO,
+                    splits: vec![split],
+                },
+            ),
+            // Logs from other contracts are ignored.
+            log(
+                Address::repeat_byte(0x66),
+                &TokenBundleSplitter::NativeTransferFailedOnDistribute {
+                    recipient,
+                    amount: U256::from(1),
+                },
+            ),
+        ]);
+
+        let distribution = Distribution::from_receipt(&receipt, splitter)?;
+        assert_eq!(distribution.escrow, FixedBytes::repeat_byte(0x0e));
+        assert_eq!(distribution.splits.len(), 1);
+        assert_eq!(distribution.splits[0].amount, U256::from(7));
+        assert_eq!(
+            distribution.failed,
+            vec![FailedDistribution::NativeToken {
+                recipient,
+                amount: U256::from(7),
+            }]
+        );
+
+        assert!(Distribution::from_receipt(&receipt, Address::repeat_byte(0x66)).is_err());
+        Ok(())
+    }
+}
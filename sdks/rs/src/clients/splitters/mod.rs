@@ -0,0 +1,379 @@
+//! Splitter arbiters module
+//!
+//! Splitters are arbiters that collect an escrow themselves and distribute
+//! it between several recipients, as decided by a trusted oracle:
+//! - `amount`: ERC20, ERC1155 and native-token splitters
+//! - `token_bundle`: validated and unvalidated token-bundle splitters
+//! - `events`: fulfillment and distribution events
+//! - `oracle`: runtime answering arbitration requests with split decisions
+
+pub mod amount;
+pub mod events;
+pub mod oracle;
+pub mod token_bundle;
+
+use alloy::{
+    primitives::{Address, Bytes, FixedBytes, U256, address, keccak256},
+    providers::{Provider as _, ProviderBuilder},
+    rpc::types::TransactionReceipt,
+    signers::local::PrivateKeySigner,
+    sol_types::SolValue as _,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    addresses::BASE_SEPOLIA_ADDRESSES,
+    contracts,
+    extensions::{AlkahestExtension, ContractModule},
+    impl_abi_conversions,
+    types::{ProviderContext, SharedPublicProvider, SharedWalletProvider},
+};
+
+/// Common splitter arbiter demand data.
+pub type SplitterDemandData = contracts::utils::splitters::ERC20Splitter::DemandData;
+/// Split item for native/ERC20/ERC1155 amount-based splitters.
+pub type AmountSplit = contracts::utils::splitters::ERC20Splitter::Split;
+/// Split item for token-bundle splitters.
+pub type BundleSplit =
+    contracts::utils::splitters::token_bundle::TokenBundleSplitterBase::BundleSplit;
+
+impl_abi_conversions!(SplitterDemandData);
+impl_abi_conversions!(AmountSplit);
+impl_abi_conversions!(BundleSplit);
+
+/// Split recipient standing for whoever created the fulfillment through
+/// the splitter's `createFulfillment`.
+pub const EXECUTOR_SENTINEL: Address = address!("0x000000000000000000000000000000000000EEEE");
+
+/// Most splits one decision may hold, as enforced by the splitters'
+/// `MAX_SPLITS`.
+pub const MAX_SPLITS: usize = 50;
+
+// Every splitter inherits the same `BaseSplitter` and `IArbiter` functions,
+// so the ERC20 splitter binding can call them on any splitter.
+type AnySplitter<P> = contracts::utils::splitters::ERC20Splitter::ERC20SplitterInstance<P>;
+
+/// Contract addresses used by the splitter module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplittersAddresses {
+    /// ERC20Splitter contract address.
+    pub erc20_splitter: Address,
+    /// ERC1155Splitter contract address.
+    pub erc1155_splitter: Address,
+    /// NativeTokenSplitter contract address.
+    pub native_token_splitter: Address,
+    /// TokenBundleSplitter contract address.
+    pub token_bundle_splitter: Address,
+    /// TokenBundleSplitterUnvalidated contract address.
+    pub token_bundle_splitter_unvalidated: Address,
+}
+
+impl Default for SplittersAddresses {
+    /// Returns Base Sepolia splitter addresses.
+    fn default() -> Self {
+        BASE_SEPOLIA_ADDRESSES.splitters_addresses
+    }
+}
+
+/// Contracts addressable through the splitter module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitterContract {
+    /// ERC20Splitter contract.
+    Erc20Splitter,
+    /// ERC1155Splitter contract.
+    Erc1155Splitter,
+    /// NativeTokenSplitter contract.
+    NativeTokenSplitter,
+    /// TokenBundleSplitter contract.
+    TokenBundleSplitter,
+    /// TokenBundleSplitterUnvalidated contract.
+    TokenBundleSplitterUnvalidated,
+}
+
+/// Rust client module for splitter helpers.
+///
+/// Security note: the underlying splitter contracts have not been included in
+/// professional manual audits and have only been reviewed by automated audit
+/// tooling so far.
+#[derive(Clone)]
+pub struct SplittersModule {
+    pub(crate) signer: PrivateKeySigner,
+    pub(crate) public_provider: SharedPublicProvider,
+    pub(crate) wallet_provider: SharedWalletProvider,
+    /// Inherited from the parent ``AlkahestClient``. Used by the oracle
+    /// runtime to poll for arbitration requests over HTTP.
+    pub(crate) poll_interval: std::time::Duration,
+    pub addresses: SplittersAddresses,
+}
+
+impl ContractModule for SplittersModule {
+    type Contract = SplitterContract;
+
+    fn address(&self, contract: Self::Contract) -> Address {
+        match contract {
+            SplitterContract::Erc20Splitter => self.addresses.erc20_splitter,
+            SplitterContract::Erc1155Splitter => self.addresses.erc1155_splitter,
+            SplitterContract::NativeTokenSplitter => self.addresses.native_token_splitter,
+            SplitterContract::TokenBundleSplitter => self.addresses.token_bundle_splitter,
+            SplitterContract::TokenBundleSplitterUnvalidated => {
+                self.addresses.token_bundle_splitter_unvalidated
+            }
+        }
+    }
+}
+
+impl SplittersModule {
+    /// Creates a splitter module with optional custom addresses.
+    ///
+    /// Reads go through `wallet_provider`'s transport and event watchers
+    /// poll at [`DEFAULT_POLL_INTERVAL`](crate::utils::DEFAULT_POLL_INTERVAL)
+    /// on HTTP. Use [`SplittersModule::with_providers`] to pass a separate read
+    /// provider and poll interval.
+    pub fn new(
+        signer: PrivateKeySigner,
+        wallet_provider: SharedWalletProvider,
+        addresses: Option<SplittersAddresses>,
+    ) -> eyre::Result<Self> {
+        let public_provider = std::sync::Arc::new(
+            ProviderBuilder::new().connect_provider(wallet_provider.root().clone()),
+        );
+        Self::with_providers(
+            signer,
+            public_provider,
+            wallet_provider,
+            crate::utils::DEFAULT_POLL_INTERVAL,
+            addresses,
+        )
+    }
+
+    /// Creates the module with explicit read and wallet providers and the
+    /// poll interval used by event watchers on HTTP transports.
+    pub fn with_providers(
+        signer: PrivateKeySigner,
+        public_provider: SharedPublicProvider,
+        wallet_provider: SharedWalletProvider,
+        poll_interval: std::time::Duration,
+        addresses: Option<SplittersAddresses>,
+    ) -> eyre::Result<Self> {
+        Ok(Self {
+            signer,
+            public_provider,
+            wallet_provider,
+            poll_interval,
+            addresses: addresses.unwrap_or_default(),
+        })
+    }
+
+    /// Access the ERC20 splitter API
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let splits = AmountSplits::new()
+    ///     .pay(alice, U256::from(70))
+    ///     .pay_executor(U256::from(30));
+    /// client.splitters().erc20().arbitrate(fulfillment, escrow, splits).await?;
+    /// client
+    ///     .splitters()
+    ///     .erc20()
+    ///     .collect_and_distribute(escrow_contract, escrow, fulfillment)
+    ///     .await?;
+    /// ```
+    pub fn erc20(&self) -> amount::AmountSplitter<'_> {
+        amount::AmountSplitter::new(self, amount::AmountSplitterKind::Erc20)
+    }
+
+    /// Access the ERC1155 splitter API
+    pub fn erc1155(&self) -> amount::AmountSplitter<'_> {
+        amount::AmountSplitter::new(self, amount::AmountSplitterKind::Erc1155)
+    }
+
+    /// Access the native-token splitter API
+    pub fn native_token(&self) -> amount::AmountSplitter<'_> {
+        amount::AmountSplitter::new(self, amount::AmountSplitterKind::NativeToken)
+    }
+
+    /// Access the token-bundle splitter API
+    pub fn token_bundle(&self) -> token_bundle::TokenBundleSplitter<'_> {
+        token_bundle::TokenBundleSplitter::new(self, false)
+    }
+
+    /// Access the unvalidated token-bundle splitter API
+    ///
+    /// The contract skips split validation; this client still checks splits
+    /// against the escrow before sending them.
+    pub fn token_bundle_unvalidated(&self) -> token_bundle::TokenBundleSplitter<'_> {
+        token_bundle::TokenBundleSplitter::new(self, true)
+    }
+
+    /// Encodes splitter demand data.
+    pub fn encode_demand(data: &SplitterDemandData) -> Bytes {
+        data.abi_encode().into()
+    }
+
+    /// Decodes ABI-encoded splitter demand data.
+    pub fn decode_demand(data: &Bytes) -> eyre::Result<SplitterDemandData> {
+        Ok(SplitterDemandData::abi_decode(data.as_ref())?)
+    }
+
+    /// Computes the splitter decision key for a fulfillment and escrow UID.
+    pub fn decision_key(fulfillment: FixedBytes<32>, escrow: FixedBytes<32>) -> FixedBytes<32> {
+        let mut packed = Vec::with_capacity(64);
+        packed.extend_from_slice(fulfillment.as_slice());
+        packed.extend_from_slice(escrow.as_slice());
+        keccak256(packed)
+    }
+}
+
+// Calls shared by every splitter, used by the per-family clients.
+impl SplittersModule {
+    async fn request_arbitration_on(
+        &self,
+        splitter: Address,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+        oracle: Address,
+        demand: Bytes,
+    ) -> eyre::Result<TransactionReceipt> {
+        let receipt = AnySplitter::new(splitter, &self.wallet_provider)
+            .requestArbitration(fulfillment, escrow, oracle, demand)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+        Ok(receipt)
+    }
+
+    async fn create_fulfillment_on(
+        &self,
+        splitter: Address,
+        obligation_contract: Address,
+        data: Bytes,
+        expiration: u64,
+        ref_uid: FixedBytes<32>,
+        value: U256,
+    ) -> eyre::Result<TransactionReceipt> {
+        let receipt = AnySplitter::new(splitter, &self.wallet_provider)
+            .createFulfillment(obligation_contract, data, expiration, ref_uid)
+            .value(value)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+        Ok(receipt)
+    }
+
+    async fn has_decision_on(
+        &self,
+        splitter: Address,
+        oracle: Address,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+    ) -> eyre::Result<bool> {
+        let decided = AnySplitter::new(splitter, &self.wallet_provider)
+            .hasDecision(oracle, Self::decision_key(fulfillment, escrow))
+            .call()
+            .await?;
+        Ok(decided)
+    }
+
+    async fn fulfiller_on(
+        &self,
+        splitter: Address,
+        fulfillment: FixedBytes<32>,
+    ) -> eyre::Result<Option<Address>> {
+        let fulfiller = AnySplitter::new(splitter, &self.wallet_provider)
+            .fulfillers(fulfillment)
+            .call()
+            .await?;
+        Ok((fulfiller != Address::ZERO).then_some(fulfiller))
+    }
+
+    async fn collect_and_distribute_on(
+        &self,
+        splitter: Address,
+        escrow_contract: Address,
+        escrow: FixedBytes<32>,
+        fulfillment: FixedBytes<32>,
+        partial: bool,
+    ) -> eyre::Result<TransactionReceipt> {
+        let splitter = AnySplitter::new(splitter, &self.wallet_provider);
+        let pending = if partial {
+            splitter
+                .unsafePartiallyCollectAndDistribute(escrow_contract, escrow, fulfillment)
+                .send()
+                .await?
+        } else {
+            splitter
+                .collectAndDistribute(escrow_contract, escrow, fulfillment)
+                .send()
+                .await?
+        };
+        Ok(pending.get_receipt().await?)
+    }
+
+    /// Fetch the escrow attestation `escrow` through the EAS `splitter` uses.
+    async fn escrow_attestation_on(
+        &self,
+        splitter: Address,
+        escrow: FixedBytes<32>,
+    ) -> eyre::Result<contracts::IEAS::Attestation> {
+        let eas = AnySplitter::new(splitter, &self.wallet_provider)
+            .eas()
+            .call()
+            .await?;
+        let attestation = contracts::IEAS::new(eas, &self.wallet_provider)
+            .getAttestation(escrow)
+            .call()
+            .await?;
+        Ok(attestation)
+    }
+}
+
+impl AlkahestExtension for SplittersModule {
+    type Config = SplittersAddresses;
+
+    async fn init(
+        signer: PrivateKeySigner,
+        providers: ProviderContext,
+        config: Option<Self::Config>,
+    ) -> eyre::Result<Self> {
+        Self::with_providers(
+            signer,
+            providers.public.clone(),
+            providers.wallet.clone(),
+            providers.poll_interval,
+            config,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{Address, Bytes, FixedBytes};
+
+    use super::*;
+
+    #[test]
+    fn splitter_demand_encode_decode_roundtrip() {
+        let data = SplitterDemandData {
+            oracle: Address::repeat_byte(0x11),
+            data: Bytes::from_static(&[0x12, 0x34]),
+        };
+
+        let encoded = SplittersModule::encode_demand(&data);
+        let decoded = SplittersModule::decode_demand(&encoded).unwrap();
+
+        assert_eq!(decoded.oracle, data.oracle);
+        assert_eq!(decoded.data, data.data);
+    }
+
+    #[test]
+    fn decision_key_hashes_fulfillment_and_escrow() {
+        let key = SplittersModule::decision_key(
+            FixedBytes::<32>::repeat_byte(0x11),
+            FixedBytes::<32>::repeat_byte(0x22),
+        );
+
+        assert_ne!(key, FixedBytes::<32>::default());
+    }
+}
@@ -0,0 +1,437 @@
+//! Splitter oracle runtime
+//!
+//! Splitter oracles answer `ArbitrationRequested` events with a list of
+//! splits instead of the boolean `TrustedOracleArbiter` takes. This runtime
+//! mirrors `TrustedOracleModule::arbitrate_many*`: it gathers past requests
+//! and/or listens for new ones according to an [`ArbitrationMode`], hands
+//! each (fulfillment, escrow) pair to a handler, checks the returned splits
+//! against what the escrow holds, and submits them. It is started from a
+//! splitter client, e.g. `client.splitters().erc20().arbitrate_many_sync(..)`.
+
+use std::{
+    future::Future,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use alloy::{
+    eips::BlockNumberOrTag,
+    primitives::{Address, Bytes, FixedBytes, U256},
+    providers::Provider as _,
+    rpc::types::{Filter, TransactionReceipt},
+    sol_types::SolEvent as _,
+};
+use futures::{
+    StreamExt as _,
+    future::{join_all, try_join_all},
+};
+
+use super::{
+    AmountSplit, AnySplitter, BundleSplit, SplittersModule,
+    amount::{AmountSplitterKind, decode_escrow_amount, validate_amount_splits},
+    events::ArbitrationRequested,
+    token_bundle::{TokenBundleEscrowData, validate_bundle_splits},
+};
+use crate::{
+    clients::arbiters::{ArbitrationMode, BoxedLogStream, SubscriptionHandle, open_log_stream},
+    contracts::{
+        IEAS::{self, Attestation},
+        utils::splitters::token_bundle::TokenBundleSplitter,
+    },
+    types::{SharedPublicProvider, SharedWalletProvider},
+};
+
+/// A request for a split decision on one fulfillment of an escrow
+#[derive(Debug, Clone)]
+pub struct SplitRequest<L> {
+    pub fulfillment: Attestation,
+    pub escrow: Attestation,
+    /// Demand bytes from `ArbitrationRequested`
+    pub demand: Bytes,
+    /// What the escrow holds; the splits must add up to exactly this
+    pub locked: L,
+}
+
+/// Request to an ERC20, ERC1155 or native-token splitter oracle
+pub type AmountSplitRequest = SplitRequest<U256>;
+/// Request to a token-bundle splitter oracle
+pub type BundleSplitRequest = SplitRequest<TokenBundleEscrowData>;
+
+/// A split decision recorded on-chain
+#[derive(Debug, Clone)]
+pub struct SplitDecision<L, S> {
+    pub request: SplitRequest<L>,
+    pub splits: Vec<S>,
+    pub receipt: TransactionReceipt,
+}
+
+pub type AmountSplitDecision = SplitDecision<U256, AmountSplit>;
+pub type BundleSplitDecision = SplitDecision<TokenBundleEscrowData, BundleSplit>;
+
+/// Result from a splitter client's `arbitrate_many*`
+pub struct SplitArbitrateManyResult<L, S> {
+    /// Decisions made for past requests (empty for `Future` mode)
+    pub past_decisions: Vec<SplitDecision<L, S>>,
+    /// Handle to the future-event listener (None for `Past`/`PastUnarbitrated` modes)
+    pub subscription: Option<SubscriptionHandle>,
+}
+
+pub type AmountArbitrateManyResult = SplitArbitrateManyResult<U256, AmountSplit>;
+pub type BundleArbitrateManyResult = SplitArbitrateManyResult<TokenBundleEscrowData, BundleSplit>;
+
+/// How one splitter family reads escrows and records splits.
+pub(super) trait SplitterFamily: Clone + Send + Sync + 'static {
+    type Locked: Clone + Send + Sync + 'static;
+    type Split: Clone + Send + Sync + 'static;
+
+    fn address(&self) -> Address;
+
+    /// What `escrow` holds, decoded from its obligation data
+    fn locked(&self, escrow: &Attestation) -> eyre::Result<Self::Locked>;
+
+    fn validate(splits: &[Self::Split], locked: &Self::Locked) -> eyre::Result<()>;
+
+    fn submit(
+        &self,
+        provider: &SharedWalletProvider,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+        splits: Vec<Self::Split>,
+        nonce: Option<u64>,
+    ) -> impl Future<Output = eyre::Result<TransactionReceipt>> + Send;
+}
+
+#[derive(Clone)]
+pub(super) struct AmountFamily {
+    pub(super) address: Address,
+    pub(super) kind: AmountSplitterKind,
+}
+
+impl SplitterFamily for AmountFamily {
+    type Locked = U256;
+    type Split = AmountSplit;
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn locked(&self, escrow: &Attestation) -> eyre::Result<U256> {
+        decode_escrow_amount(self.kind, &escrow.data)
+    }
+
+    fn validate(splits: &[AmountSplit], locked: &U256) -> eyre::Result<()> {
+        validate_amount_splits(splits, *locked)
+    }
+
+    async fn submit(
+        &self,
+        provider: &SharedWalletProvider,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+        splits: Vec<AmountSplit>,
+        nonce: Option<u64>,
+    ) -> eyre::Result<TransactionReceipt> {
+        let splitter = AnySplitter::new(self.address, provider);
+        let mut call = splitter.arbitrate(fulfillment, escrow, splits);
+        if let Some(nonce) = nonce {
+            call = call.nonce(nonce);
+        }
+        Ok(call.send().await?.get_receipt().await?)
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct BundleFamily {
+    pub(super) address: Address,
+}
+
+impl SplitterFamily for BundleFamily {
+    type Locked = TokenBundleEscrowData;
+    type Split = BundleSplit;
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn locked(&self, escrow: &Attestation) -> eyre::Result<TokenBundleEscrowData> {
+        use alloy::sol_types::SolValue as _;
+        Ok(TokenBundleEscrowData::abi_decode(&escrow.data)?)
+    }
+
+    fn validate(splits: &[BundleSplit], locked: &TokenBundleEscrowData) -> eyre::Result<()> {
+        validate_bundle_splits(splits, locked)
+    }
+
+    async fn submit(
+        &self,
+        provider: &SharedWalletProvider,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+        splits: Vec<BundleSplit>,
+        nonce: Option<u64>,
+    ) -> eyre::Result<TransactionReceipt> {
+        let splitter = TokenBundleSplitter::new(self.address, provider);
+        let mut call = splitter.arbitrate(fulfillment, escrow, splits);
+        if let Some(nonce) = nonce {
+            call = call.nonce(nonce);
+        }
+        Ok(call.send().await?.get_receipt().await?)
+    }
+}
+
+/// Owned oracle state for one splitter, so the listener can be spawned.
+#[derive(Clone)]
+pub(super) struct SplitterOracle<F> {
+    family: F,
+    public_provider: SharedPublicProvider,
+    wallet_provider: SharedWalletProvider,
+    oracle: Address,
+    poll_interval: std::time::Duration,
+}
+
+impl<F: SplitterFamily> SplitterOracle<F> {
+    pub(super) fn new(module: &SplittersModule, family: F) -> Self {
+        Self {
+            family,
+            public_provider: module.public_provider.clone(),
+            wallet_provider: module.wallet_provider.clone(),
+            oracle: module.signer.address(),
+            poll_interval: module.poll_interval,
+        }
+    }
+
+    fn make_arbitration_requested_filter(&self) -> Filter {
+        // ArbitrationRequested(bytes32 indexed fulfillment, bytes32 indexed escrow, address indexed oracle, bytes demand)
+        Filter::new()
+            .address(self.family.address())
+            .event_signature(ArbitrationRequested::SIGNATURE_HASH)
+            .topic3(self.oracle)
+            .from_block(BlockNumberOrTag::Earliest)
+            .to_block(BlockNumberOrTag::Latest)
+    }
+
+    /// Load the attestations behind an arbitration request.
+    ///
+    /// Returns `None` for requests that can no longer be decided usefully:
+    /// already decided (when `skip_decided`), expired or revoked, or whose
+    /// escrow isn't one this splitter can divide.
+    async fn load_request(
+        &self,
+        eas: Address,
+        event: ArbitrationRequested,
+        skip_decided: bool,
+    ) -> eyre::Result<Option<SplitRequest<F::Locked>>> {
+        if skip_decided {
+            let key = SplittersModule::decision_key(event.fulfillment, event.escrow);
+            let decided = AnySplitter::new(self.family.address(), &self.wallet_provider)
+                .hasDecision(self.oracle, key)
+                .call()
+                .await?;
+            if decided {
+                return Ok(None);
+            }
+        }
+
+        let eas = IEAS::new(eas, &self.wallet_provider);
+        let fulfillment = eas.getAttestation(event.fulfillment).call().await?;
+        let escrow = eas.getAttestation(event.escrow).call().await?;
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let inactive = |a: &Attestation| {
+            (a.expirationTime != 0 && a.expirationTime < now)
+                || (a.revocationTime != 0 && a.revocationTime < now)
+        };
+        if inactive(&fulfillment) || inactive(&escrow) {
+            return Ok(None);
+        }
+
+        let locked = match self.family.locked(&escrow) {
+            Ok(locked) => locked,
+            Err(err) => {
+                tracing::warn!("Skipping split request for escrow {}: {}", escrow.uid, err);
+                return Ok(None);
+            }
+        };
+
+        Ok(Some(SplitRequest {
+            fulfillment,
+            escrow,
+            demand: event.demand,
+            locked,
+        }))
+    }
+
+    async fn get_past_requests(
+        &self,
+        eas: Address,
+        skip_decided: bool,
+    ) -> eyre::Result<Vec<SplitRequest<F::Locked>>> {
+        let events = self
+            .public_provider
+            .get_logs(&self.make_arbitration_requested_filter())
+            .await?
+            .into_iter()
+            .map(|log| log.log_decode::<ArbitrationRequested>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let requests = try_join_all(
+            events
+                .into_iter()
+                .map(|log| self.load_request(eas, log.inner.data, skip_decided)),
+        )
+        .await?;
+        Ok(requests.into_iter().flatten().collect())
+    }
+
+    async fn decide(
+        &self,
+        request: SplitRequest<F::Locked>,
+        splits: Vec<F::Split>,
+        nonce: Option<u64>,
+    ) -> eyre::Result<SplitDecision<F::Locked, F::Split>> {
+        F::validate(&splits, &request.locked)?;
+        let receipt = self
+            .family
+            .submit(
+                &self.wallet_provider,
+                request.fulfillment.uid,
+                request.escrow.uid,
+                splits.clone(),
+                nonce,
+            )
+            .await?;
+        Ok(SplitDecision {
+            request,
+            splits,
+            receipt,
+        })
+    }
+
+    /// Decide split requests according to `mode`.
+    ///
+    /// Past decisions are all checked before any is submitted, so one
+    /// invalid split list fails the call without sending anything. Invalid
+    /// splits for future requests are logged and skipped.
+    pub(super) async fn arbitrate_many<
+        ArbitrateFut: Future<Output = Option<Vec<F::Split>>> + Send + 'static,
+        Arbitrate: Fn(&SplitRequest<F::Locked>) -> ArbitrateFut + Clone + Send + Sync + 'static,
+        OnDecisionFut: Future<Output = ()> + Send + 'static,
+        OnDecision: Fn(&SplitDecision<F::Locked, F::Split>) -> OnDecisionFut + Clone + Send + Sync + 'static,
+    >(
+        &self,
+        arbitrate: Arbitrate,
+        on_decision: OnDecision,
+        mode: ArbitrationMode,
+    ) -> eyre::Result<SplitArbitrateManyResult<F::Locked, F::Split>> {
+        use ArbitrationMode::*;
+
+        let skip_decided = matches!(mode, PastUnarbitrated | AllUnarbitrated);
+        let include_past = matches!(mode, Past | PastUnarbitrated | All | AllUnarbitrated);
+        let include_future = matches!(mode, Future | All | AllUnarbitrated);
+
+        let eas = AnySplitter::new(self.family.address(), &self.wallet_provider)
+            .eas()
+            .call()
+            .await?;
+
+        let past_decisions = if include_past {
+            let requests = self.get_past_requests(eas, skip_decided).await?;
+            let splits = join_all(requests.iter().map(&arbitrate)).await;
+
+            let decided: Vec<_> = requests
+                .into_iter()
+                .zip(splits)
+                .filter_map(|(request, splits)| splits.map(|splits| (request, splits)))
+                .collect();
+            for (request, splits) in &decided {
+                F::validate(splits, &request.locked).map_err(|err| {
+                    eyre::eyre!("Invalid splits for {}: {err}", request.fulfillment.uid)
+                })?;
+            }
+
+            let mut decisions = Vec::with_capacity(decided.len());
+            for (request, splits) in decided {
+                decisions.push(self.decide(request, splits, None).await?);
+            }
+            decisions
+        } else {
+            Vec::new()
+        };
+
+        let subscription = if include_future {
+            let filter = self.make_arbitration_requested_filter();
+            let (stream, handle) =
+                open_log_stream(&self.public_provider, &filter, self.poll_interval).await?;
+            self.spawn_stream_handler(stream, eas, arbitrate, on_decision, skip_decided);
+            Some(handle)
+        } else {
+            None
+        };
+
+        Ok(SplitArbitrateManyResult {
+            past_decisions,
+            subscription,
+        })
+    }
+
+    fn spawn_stream_handler<
+        ArbitrateFut: Future<Output = Option<Vec<F::Split>>> + Send + 'static,
+        Arbitrate: Fn(&SplitRequest<F::Locked>) -> ArbitrateFut + Send + Sync + 'static,
+        OnDecisionFut: Future<Output = ()> + Send + 'static,
+        OnDecision: Fn(&SplitDecision<F::Locked, F::Split>) -> OnDecisionFut + Send + Sync + 'static,
+    >(
+        &self,
+        mut stream: BoxedLogStream,
+        eas: Address,
+        arbitrate: Arbitrate,
+        on_decision: OnDecision,
+        skip_decided: bool,
+    ) {
+        let oracle = self.clone();
+
+        tokio::spawn(async move {
+            while let Some(log) = stream.next().await {
+                let Ok(event) = log.log_decode::<ArbitrationRequested>() else {
+                    continue;
+                };
+                let fulfillment = event.inner.fulfillment;
+
+                let request = match oracle
+                    .load_request(eas, event.inner.data, skip_decided)
+                    .await
+                {
+                    Ok(Some(request)) => request,
+                    Ok(None) => continue,
+                    Err(err) => {
+                        tracing::error!(
+                            "Failed to load split request for {}: {}",
+                            fulfillment,
+                            err
+                        );
+                        continue;
+                    }
+                };
+
+                let Some(splits) = arbitrate(&request).await else {
+                    continue;
+                };
+
+                let Ok(nonce) = oracle
+                    .wallet_provider
+                    .get_transaction_count(oracle.oracle)
+                    .await
+                else {
+                    continue;
+                };
+
+                match oracle.decide(request, splits, Some(nonce)).await {
+                    Ok(decision) => {
+                        tokio::spawn(on_decision(&decision));
+                    }
+                    Err(err) => {
+                        tracing::error!("Split arbitration failed for {}: {}", fulfillment, err);
+                    }
+                }
+            }
+        });
+    }
+}
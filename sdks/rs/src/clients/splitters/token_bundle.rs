@@ -0,0 +1,477 @@
+//! Token-bundle splitter client
+//!
+//! `TokenBundleSplitter` and `TokenBundleSplitterUnvalidated` divide a
+//! `TokenBundleEscrowObligation` escrow between several recipients. Each
+//! split takes a share of the native amount, of every ERC20 and ERC1155
+//! entry, and a set of the escrow's ERC721 tokens by index. Both contracts
+//! share one ABI; the unvalidated one skips checking splits on-chain, so this
+//! client checks them before sending either way.
+
+use std::{collections::BTreeSet, future::Future};
+
+use alloy::{
+    primitives::{Address, Bytes, FixedBytes, U256},
+    rpc::types::TransactionReceipt,
+    sol_types::SolValue as _,
+};
+
+use super::{
+    BundleSplit, EXECUTOR_SENTINEL, SplittersModule,
+    amount::validate_split_count,
+    events::Distribution,
+    oracle::{
+        BundleArbitrateManyResult, BundleFamily, BundleSplitDecision, BundleSplitRequest,
+        SplitterOracle,
+    },
+};
+use crate::{
+    clients::arbiters::ArbitrationMode,
+    contracts::{
+        obligations::escrow::default_escrow::TokenBundleEscrowObligation,
+        utils::splitters::token_bundle::TokenBundleSplitter as TokenBundleSplitterContract,
+    },
+};
+
+/// Obligation data of the token-bundle escrows splitters can divide
+pub type TokenBundleEscrowData = TokenBundleEscrowObligation::ObligationData;
+
+/// Builder for one recipient's share of a token-bundle escrow
+///
+/// Starts with nothing assigned, sized to the escrow's token lists.
+///
+/// # Example
+/// ```rust,ignore
+/// let alice_split = BundleSplitBuilder::new(alice, &escrow_data)
+///     .native(U256::from(100))
+///     .erc20(0, U256::from(50))
+///     .erc721(1)
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct BundleSplitBuilder {
+    split: BundleSplit,
+}
+
+impl BundleSplitBuilder {
+    /// Start an empty split paying `recipient`
+    pub fn new(recipient: Address, escrow: &TokenBundleEscrowData) -> Self {
+        Self {
+            split: BundleSplit {
+                recipient,
+                nativeAmount: U256::ZERO,
+                erc20Amounts: vec![U256::ZERO; escrow.erc20Tokens.len()],
+                erc721Indices: Vec::new(),
+                erc1155Amounts: vec![U256::ZERO; escrow.erc1155Tokens.len()],
+            },
+        }
+    }
+
+    /// Start an empty split paying whoever created the fulfillment through
+    /// the splitter
+    pub fn executor(escrow: &TokenBundleEscrowData) -> Self {
+        Self::new(EXECUTOR_SENTINEL, escrow)
+    }
+
+    /// Set the native-token amount
+    pub fn native(mut self, amount: U256) -> Self {
+        self.split.nativeAmount = amount;
+        self
+    }
+
+    /// Set the amount of the escrow's `index`th ERC20 entry
+    pub fn erc20(mut self, index: usize, amount: U256) -> Self {
+        set_amount(&mut self.split.erc20Amounts, index, amount);
+        self
+    }
+
+    /// Assign the escrow's `index`th ERC721 token
+    pub fn erc721(mut self, index: usize) -> Self {
+        self.split.erc721Indices.push(U256::from(index));
+        self
+    }
+
+    /// Set the amount of the escrow's `index`th ERC1155 entry
+    pub fn erc1155(mut self, index: usize, amount: U256) -> Self {
+        set_amount(&mut self.split.erc1155Amounts, index, amount);
+        self
+    }
+
+    pub fn build(self) -> BundleSplit {
+        self.split
+    }
+}
+
+impl From<BundleSplitBuilder> for BundleSplit {
+    fn from(builder: BundleSplitBuilder) -> Self {
+        builder.build()
+    }
+}
+
+// Out-of-range indices grow the list so validation reports the mismatch
+// instead of panicking here.
+fn set_amount(amounts: &mut Vec<U256>, index: usize, amount: U256) {
+    if index >= amounts.len() {
+        amounts.resize(index + 1, U256::ZERO);
+    }
+    amounts[index] = amount;
+}
+
+/// Check `splits` against `escrow` the way the validated splitter's
+/// `arbitrate` does: between one and [`super::MAX_SPLITS`] splits, native,
+/// ERC20 and ERC1155 shares adding up to exactly what the escrow holds, and
+/// every ERC721 token assigned exactly once.
+pub fn validate_bundle_splits(
+    splits: &[BundleSplit],
+    escrow: &TokenBundleEscrowData,
+) -> eyre::Result<()> {
+    validate_split_count(splits.len())?;
+
+    let native = checked_sum(splits.iter().map(|s| s.nativeAmount))?;
+    if native != escrow.nativeAmount {
+        return Err(eyre::eyre!(
+            "Native splits total {native} but the escrow holds {}",
+            escrow.nativeAmount
+        ));
+    }
+
+    validate_token_amounts(
+        "ERC20",
+        splits.iter().map(|s| &s.erc20Amounts),
+        &escrow.erc20Amounts,
+    )?;
+    validate_token_amounts(
+        "ERC1155",
+        splits.iter().map(|s| &s.erc1155Amounts),
+        &escrow.erc1155Amounts,
+    )?;
+
+    let erc721_count = escrow.erc721Tokens.len();
+    let mut assigned = BTreeSet::new();
+    for index in splits.iter().flat_map(|s| &s.erc721Indices) {
+        if *index >= U256::from(erc721_count) {
+            return Err(eyre::eyre!(
+                "ERC721 index {index} is out of range for {erc721_count} tokens"
+            ));
+        }
+        if !assigned.insert(*index) {
+            return Err(eyre::eyre!("ERC721 index {index} is assigned twice"));
+        }
+    }
+    if assigned.len() != erc721_count {
+        return Err(eyre::eyre!(
+            "Splits assign {} of the escrow's {erc721_count} ERC721 tokens",
+            assigned.len()
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_token_amounts<'s>(
+    kind: &str,
+    split_amounts: impl Iterator<Item = &'s Vec<U256>> + Clone,
+    escrow_amounts: &[U256],
+) -> eyre::Result<()> {
+    for amounts in split_amounts.clone() {
+        if amounts.len() != escrow_amounts.len() {
+            return Err(eyre::eyre!(
+                "Split has {} {kind} amounts but the escrow holds {} {kind} entries",
+                amounts.len(),
+                escrow_amounts.len()
+            ));
+        }
+    }
+    for (i, expected) in escrow_amounts.iter().enumerate() {
+        let total = checked_sum(split_amounts.clone().map(|amounts| amounts[i]))?;
+        if total != *expected {
+            return Err(eyre::eyre!(
+                "{kind} entry {i} splits total {total} but the escrow holds {expected}"
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn checked_sum(amounts: impl Iterator<Item = U256>) -> eyre::Result<U256> {
+    amounts
+        .into_iter()
+        .try_fold(U256::ZERO, |total, amount| total.checked_add(amount))
+        .ok_or_else(|| eyre::eyre!("Split amounts overflow uint256"))
+}
+
+/// Token-bundle splitter API
+pub struct TokenBundleSplitter<'a> {
+    module: &'a SplittersModule,
+    unvalidated: bool,
+}
+
+impl<'a> TokenBundleSplitter<'a> {
+    pub fn new(module: &'a SplittersModule, unvalidated: bool) -> Self {
+        Self {
+            module,
+            unvalidated,
+        }
+    }
+
+    /// Get the contract address
+    pub fn address(&self) -> Address {
+        if self.unvalidated {
+            self.module.addresses.token_bundle_splitter_unvalidated
+        } else {
+            self.module.addresses.token_bundle_splitter
+        }
+    }
+
+    /// Obligation data of `escrow`
+    pub async fn escrow_data(&self, escrow: FixedBytes<32>) -> eyre::Result<TokenBundleEscrowData> {
+        let attestation = self
+            .module
+            .escrow_attestation_on(self.address(), escrow)
+            .await?;
+        Ok(TokenBundleEscrowData::abi_decode(&attestation.data)?)
+    }
+
+    /// Ask `oracle` to decide splits for a fulfillment of `escrow`.
+    ///
+    /// Only the escrow's attester or recipient can request arbitration.
+    pub async fn request_arbitration(
+        &self,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+        oracle: Address,
+        demand: Bytes,
+    ) -> eyre::Result<TransactionReceipt> {
+        self.module
+            .request_arbitration_on(self.address(), fulfillment, escrow, oracle, demand)
+            .await
+    }
+
+    /// Record the signer's split decision as oracle.
+    ///
+    /// The splits are checked against the escrowed bundle before sending,
+    /// for the unvalidated splitter too.
+    pub async fn arbitrate(
+        &self,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+        splits: Vec<BundleSplit>,
+    ) -> eyre::Result<TransactionReceipt> {
+        validate_bundle_splits(&splits, &self.escrow_data(escrow).await?)?;
+
+        let receipt =
+            TokenBundleSplitterContract::new(self.address(), &self.module.wallet_provider)
+                .arbitrate(fulfillment, escrow, splits)
+                .send()
+                .await?
+                .get_receipt()
+                .await?;
+        Ok(receipt)
+    }
+
+    /// Splits `oracle` recorded for a fulfillment of `escrow`
+    pub async fn get_splits(
+        &self,
+        oracle: Address,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+    ) -> eyre::Result<Vec<BundleSplit>> {
+        let splits = TokenBundleSplitterContract::new(self.address(), &self.module.wallet_provider)
+            .getSplits(oracle, fulfillment, escrow)
+            .call()
+            .await?;
+        Ok(splits)
+    }
+
+    /// Check whether `oracle` has decided splits for a fulfillment of `escrow`
+    pub async fn has_decision(
+        &self,
+        oracle: Address,
+        fulfillment: FixedBytes<32>,
+        escrow: FixedBytes<32>,
+    ) -> eyre::Result<bool> {
+        self.module
+            .has_decision_on(self.address(), oracle, fulfillment, escrow)
+            .await
+    }
+
+    /// Create a fulfillment owned by the splitter, recording the signer as
+    /// its fulfiller. See [`super::amount::AmountSplitter::create_fulfillment`].
+    pub async fn create_fulfillment(
+        &self,
+        obligation_contract: Address,
+        data: Bytes,
+        expiration: u64,
+        ref_uid: FixedBytes<32>,
+        value: U256,
+    ) -> eyre::Result<TransactionReceipt> {
+        self.module
+            .create_fulfillment_on(
+                self.address(),
+                obligation_contract,
+                data,
+                expiration,
+                ref_uid,
+                value,
+            )
+            .await
+    }
+
+    /// Fulfiller recorded for a splitter-owned fulfillment, if any
+    pub async fn fulfiller(&self, fulfillment: FixedBytes<32>) -> eyre::Result<Option<Address>> {
+        self.module.fulfiller_on(self.address(), fulfillment).await
+    }
+
+    /// Collect `escrow` from `escrow_contract` and pay out the recorded
+    /// splits, reverting if any payout fails.
+    pub async fn collect_and_distribute(
+        &self,
+        escrow_contract: Address,
+        escrow: FixedBytes<32>,
+        fulfillment: FixedBytes<32>,
+    ) -> eyre::Result<TransactionReceipt> {
+        self.module
+            .collect_and_distribute_on(self.address(), escrow_contract, escrow, fulfillment, false)
+            .await
+    }
+
+    /// Collect `escrow` and pay out the recorded splits, skipping payouts
+    /// that fail. Skipped tokens stay in the splitter; use only when
+    /// [`Self::collect_and_distribute`] is permanently blocked.
+    pub async fn unsafe_partially_collect_and_distribute(
+        &self,
+        escrow_contract: Address,
+        escrow: FixedBytes<32>,
+        fulfillment: FixedBytes<32>,
+    ) -> eyre::Result<TransactionReceipt> {
+        self.module
+            .collect_and_distribute_on(self.address(), escrow_contract, escrow, fulfillment, true)
+            .await
+    }
+
+    /// Decode the distribution logged by this splitter in `receipt`
+    pub fn distribution(&self, receipt: &TransactionReceipt) -> eyre::Result<Distribution> {
+        Distribution::from_receipt(receipt, self.address())
+    }
+
+    /// Answer this splitter's arbitration requests as oracle, based on `mode`
+    ///
+    /// # Arguments
+    /// * `arbitrate` - Sync callback returning the splits for a request, or `None` to skip it
+    /// * `on_decision` - Callback invoked after each decision on a future request
+    /// * `mode` - Which requests to process (see `ArbitrationMode`)
+    ///
+    /// Splits are checked against what the escrow holds before being sent.
+    pub async fn arbitrate_many_sync<
+        Arbitrate: Fn(&BundleSplitRequest) -> Option<Vec<BundleSplit>> + Clone + Send + Sync + 'static,
+        OnDecisionFut: Future<Output = ()> + Send + 'static,
+        OnDecision: Fn(&BundleSplitDecision) -> OnDecisionFut + Clone + Send + Sync + 'static,
+    >(
+        &self,
+        arbitrate: Arbitrate,
+        on_decision: OnDecision,
+        mode: ArbitrationMode,
+    ) -> eyre::Result<BundleArbitrateManyResult> {
+        self.arbitrate_many_async(
+            move |request: &BundleSplitRequest| std::future::ready(arbitrate(request)),
+            on_decision,
+            mode,
+        )
+        .await
+    }
+
+    /// Answer this splitter's arbitration requests as oracle (async callback version)
+    ///
+    /// See [`Self::arbitrate_many_sync`].
+    pub async fn arbitrate_many_async<
+        ArbitrateFut: Future<Output = Option<Vec<BundleSplit>>> + Send + 'static,
+        Arbitrate: Fn(&BundleSplitRequest) -> ArbitrateFut + Clone + Send + Sync + 'static,
+        OnDecisionFut: Future<Output = ()> + Send + 'static,
+        OnDecision: Fn(&BundleSplitDecision) -> OnDecisionFut + Clone + Send + Sync + 'static,
+    >(
+        &self,
+        arbitrate: Arbitrate,
+        on_decision: OnDecision,
+        mode: ArbitrationMode,
+    ) -> eyre::Result<BundleArbitrateManyResult> {
+        SplitterOracle::new(
+            self.module,
+            BundleFamily {
+                address: self.address(),
+            },
+        )
+        .arbitrate_many(arbitrate, on_decision, mode)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escrow() -> TokenBundleEscrowData {
+        TokenBundleEscrowData {
+            arbiter: Address::ZERO,
+            demand: Bytes::new(),
+            nativeAmount: U256::from(10),
+            erc20Tokens: vec![Address::repeat_byte(0x20)],
+            erc20Amounts: vec![U256::from(100)],
+            erc721Tokens: vec![Address::repeat_byte(0x72), Address::repeat_byte(0x72)],
+            erc721TokenIds: vec![U256::from(1), U256::from(2)],
+            erc1155Tokens: vec![],
+            erc1155TokenIds: vec![],
+            erc1155Amounts: vec![],
+        }
+    }
+
+    #[test]
+    fn bundle_splits_cover_the_escrow_exactly() {
+        let escrow = escrow();
+        let alice = Address::repeat_byte(0x01);
+        let splits = vec![
+            BundleSplitBuilder::new(alice, &escrow)
+                .native(U256::from(4))
+                .erc20(0, U256::from(70))
+                .erc721(0)
+                .build(),
+            BundleSplitBuilder::executor(&escrow)
+                .native(U256::from(6))
+                .erc20(0, U256::from(30))
+                .erc721(1)
+                .build(),
+        ];
+        validate_bundle_splits(&splits, &escrow).unwrap();
+        assert_eq!(splits[1].recipient, EXECUTOR_SENTINEL);
+    }
+
+    #[test]
+    fn bundle_splits_reject_mismatches() {
+        let escrow = escrow();
+        let alice = Address::repeat_byte(0x01);
+        let full = || {
+            BundleSplitBuilder::new(alice, &escrow)
+                .native(U256::from(10))
+                .erc20(0, U256::from(100))
+        };
+
+        // Unassigned ERC721 tokens
+        assert!(validate_bundle_splits(&[full().erc721(0).build()], &escrow).is_err());
+        // Duplicate and out-of-range ERC721 indices
+        assert!(
+            validate_bundle_splits(&[full().erc721(0).erc721(0).erc721(1).build()], &escrow)
+                .is_err()
+        );
+        assert!(
+            validate_bundle_splits(&[full().erc721(0).erc721(1).erc721(2).build()], &escrow)
+                .is_err()
+        );
+        // Short native amount
+        let short = full().native(U256::from(9)).erc721(0).erc721(1).build();
+        assert!(validate_bundle_splits(&[short], &escrow).is_err());
+        // ERC20 entry that isn't in the escrow
+        let extra = full().erc20(1, U256::from(1)).erc721(0).erc721(1).build();
+        assert!(validate_bundle_splits(&[extra], &escrow).is_err());
+
+        assert!(validate_bundle_splits(&[full().erc721(0).erc721(1).build()], &escrow).is_ok());
+        assert!(validate_bundle_splits(&[], &escrow).is_err());
+    }
+}
@@ -25,6 +25,15 @@ sol!(
     "src/contracts/IEscrow.json"
 );
 
+// Generic arbiter interface, for calling `check` on any arbiter address
+sol!(
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug)]
+    BaseArbiter,
+    "src/contracts/BaseArbiter.json"
+);
+
 sol!(
     #[allow(missing_docs)]
     #[sol(rpc)]
@@ -147,6 +147,26 @@ pub struct BaseExtensions {
     pub oracle: OracleModule,
 }
 
+impl BaseExtensions {
+    /// Current addresses of every module, including custom arbiters
+    /// registered since the client was created.
+    pub fn config(&self) -> DefaultExtensionConfig {
+        DefaultExtensionConfig {
+            arbiters_addresses: self.arbiters.addresses.clone(),
+            erc20_addresses: self.erc20.addresses.clone(),
+            erc721_addresses: self.erc721.addresses.clone(),
+            erc1155_addresses: self.erc1155.addresses.clone(),
+            native_token_addresses: self.native_token.addresses.clone(),
+            token_bundle_addresses: self.token_bundle.addresses.clone(),
+            hook_based_addresses: self.hook_based.addresses.clone(),
+            splitters_addresses: self.splitters.addresses.clone(),
+            attestation_addresses: self.attestation.addresses.clone(),
+            string_obligation_addresses: self.string_obligation.addresses.clone(),
+            commit_reveal_obligation_addresses: self.commit_reveal.addresses.clone(),
+        }
+    }
+}
+
 impl AlkahestExtension for BaseExtensions {
     type Config = DefaultExtensionConfig;
 
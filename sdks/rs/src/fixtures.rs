@@ -40,3 +40,14 @@ sol!(
     MockERC1155,
     "src/fixtures/MockERC1155.json"
 );
+
+// Minimal ERC-8004 ValidationRegistry: stores requests and the validator's
+// latest response, without identity registry checks. Source:
+// contracts/test/utils/MockValidationRegistry.sol
+sol!(
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug)]
+    MockValidationRegistry,
+    "src/fixtures/MockValidationRegistry.json"
+);
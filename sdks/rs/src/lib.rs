@@ -7,10 +7,10 @@ use alloy::{
 };
 use extensions::{
     AlkahestExtension, BaseExtensions, HasArbiters, HasAttestation, HasCommitReveal, HasErc20,
-    HasErc721, HasErc1155, HasStringObligation, HasTokenBundle,
+    HasErc721, HasErc1155, HasOracle, HasStringObligation, HasTokenBundle,
 };
 use serde::{Deserialize, Serialize};
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 use types::EscrowClaimed;
 use types::{SharedPublicProvider, SharedWalletProvider};
 
@@ -36,7 +36,7 @@ pub mod types;
 pub mod utils;
 
 // Re-export contract types from client modules
-pub use address_index::ContractAddressInfo;
+pub use address_index::{AddressLabels, ContractAddressInfo};
 pub use clients::arbiters::ArbitersContract;
 pub use clients::attestation::AttestationContract;
 pub use clients::commit_reveal_obligation::CommitRevealObligationContract;
@@ -50,6 +50,10 @@ pub use clients::string_obligation::StringObligationContract;
 pub use clients::token_bundle::TokenBundleContract;
 pub use extensions::ContractModule;
 
+// Re-exported so `TypedPayload` types can derive `JsonSchema` with the same
+// schemars version the SDK hashes schema tags with.
+pub use schemars;
+
 /// Configuration struct containing all contract addresses for Alkahest protocol extensions.
 ///
 /// This struct holds the addresses for all the smart contracts used by different
@@ -214,6 +218,28 @@ impl AlkahestClient<BaseExtensions> {
     }
 }
 
+impl AlkahestClient<BaseExtensions> {
+    /// Current contract addresses, including custom arbiters deployed or
+    /// registered through [`ArbitersModule::deploy_arbiter`].
+    ///
+    /// [`ArbitersModule::deploy_arbiter`]: clients::arbiters::ArbitersModule::deploy_arbiter
+    pub fn config(&self) -> DefaultExtensionConfig {
+        self.extensions.config()
+    }
+
+    /// Index of the client's current contract addresses, see
+    /// [`DefaultExtensionConfig::address_index`].
+    pub fn address_index(&self) -> HashMap<Address, Vec<ContractAddressInfo>> {
+        self.config().address_index()
+    }
+
+    /// Resolve a contract name against the client's current addresses, see
+    /// [`DefaultExtensionConfig::resolve_name`].
+    pub fn resolve_name(&self, name: &str) -> Option<Address> {
+        self.config().resolve_name(name)
+    }
+}
+
 impl<Extensions: AlkahestExtension> AlkahestClient<Extensions> {
     /// Add an extension with a specific configuration
     pub async fn extend<NewExt: AlkahestExtension>(
@@ -526,6 +552,23 @@ impl<Extensions: AlkahestExtension> AlkahestClient<Extensions> {
         &self,
         escrow_attestation: &contracts::IEAS::Attestation,
     ) -> eyre::Result<DemandData::RustType> {
+        let arbiter_data = self.extract_arbiter_data(escrow_attestation)?;
+        DemandData::abi_decode(&arbiter_data.demand).map_err(Into::into)
+    }
+
+    /// Extract the arbiter address and raw demand bytes from an escrow attestation
+    ///
+    /// Escrow obligations encode `(address arbiter, bytes demand, ...)` as
+    /// their leading fields.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let ArbiterData { arbiter, demand } = client.extract_arbiter_data(&escrow_attestation)?;
+    /// ```
+    pub fn extract_arbiter_data(
+        &self,
+        escrow_attestation: &contracts::IEAS::Attestation,
+    ) -> eyre::Result<types::ArbiterData> {
         use alloy::sol;
         sol! {
             struct ArbiterDemand {
@@ -534,7 +577,10 @@ impl<Extensions: AlkahestExtension> AlkahestClient<Extensions> {
             }
         }
         let arbiter_demand = ArbiterDemand::abi_decode(&escrow_attestation.data)?;
-        DemandData::abi_decode(&arbiter_demand.demand).map_err(Into::into)
+        Ok(types::ArbiterData {
+            arbiter: arbiter_demand.oracle,
+            demand: arbiter_demand.demand,
+        })
     }
 
     /// Get escrow attestation and extract demand data in one call
@@ -556,6 +602,86 @@ impl<Extensions: AlkahestExtension> AlkahestClient<Extensions> {
         let demand = self.extract_demand_data::<DemandData>(&escrow)?;
         Ok((escrow, demand))
     }
+
+    /// Create a fulfillment for an escrow and request arbitration from every
+    /// trusted oracle in the escrow's demand.
+    ///
+    /// The escrow's demand tree is decoded with the arbiters module's codec
+    /// registry, so `TrustedOracleArbiter` leaves nested inside `AnyArbiter`
+    /// or `AllArbiter` demands are found too. `obligation_call` receives the
+    /// escrow UID to reference and must return the receipt of the transaction
+    /// that created the fulfillment attestation (a string, commit-reveal or
+    /// payment obligation). One `requestArbitration` is sent per distinct
+    /// (oracle, demand) leaf.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let result = client
+    ///     .fulfill_and_request_arbitration(escrow_uid, |escrow_uid| async move {
+    ///         client
+    ///             .string_obligation()
+    ///             .do_obligation("result".to_string(), None, Some(escrow_uid))
+    ///             .await
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn fulfill_and_request_arbitration<ObligationFut>(
+        &self,
+        escrow_uid: FixedBytes<32>,
+        obligation_call: impl FnOnce(FixedBytes<32>) -> ObligationFut,
+    ) -> eyre::Result<clients::arbiters::FulfillmentWithArbitration>
+    where
+        ObligationFut: std::future::Future<Output = eyre::Result<TransactionReceipt>>,
+        Extensions: extensions::HasAttestation + extensions::HasArbiters + extensions::HasOracle,
+    {
+        let eas = contracts::IEAS::new(self.attestation().addresses.eas, &self.wallet_provider);
+        let escrow = eas.getAttestation(escrow_uid).call().await?;
+        if escrow.uid != escrow_uid {
+            return Err(eyre::eyre!("Escrow attestation {} not found", escrow_uid));
+        }
+
+        // Resolve the oracles before creating the fulfillment so a demand
+        // without oracles fails without sending any transaction.
+        let arbiter_data = self.extract_arbiter_data(&escrow)?;
+        let decoded = self
+            .arbiters()
+            .decode_arbiter_demand(arbiter_data.arbiter, &arbiter_data.demand)?;
+        let mut leaves: Vec<(Address, alloy::primitives::Bytes)> = Vec::new();
+        for leaf in decoded.trusted_oracle_leaves() {
+            let leaf = (leaf.oracle, leaf.data.clone());
+            if !leaves.contains(&leaf) {
+                leaves.push(leaf);
+            }
+        }
+        if leaves.is_empty() {
+            return Err(eyre::eyre!(
+                "Escrow {} demand has no TrustedOracleArbiter leaves",
+                escrow_uid
+            ));
+        }
+
+        let fulfillment_receipt = obligation_call(escrow_uid).await?;
+        let fulfillment_uid = Self::get_attested_event(fulfillment_receipt.clone())?.uid;
+
+        let mut requests = Vec::with_capacity(leaves.len());
+        for (oracle, demand) in leaves {
+            let receipt = self
+                .oracle()
+                .request_arbitration(fulfillment_uid, oracle, demand.clone())
+                .await?;
+            requests.push(clients::arbiters::ArbitrationRequest {
+                oracle,
+                demand,
+                receipt,
+            });
+        }
+
+        Ok(clients::arbiters::FulfillmentWithArbitration {
+            fulfillment_uid,
+            fulfillment_receipt,
+            requests,
+        })
+    }
 }
 
 #[cfg(test)]
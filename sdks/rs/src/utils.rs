@@ -75,7 +75,9 @@ use crate::{
             },
         },
     },
-    fixtures::{EAS, MockERC20Permit, MockERC721, MockERC1155, SchemaRegistry},
+    fixtures::{
+        EAS, MockERC20Permit, MockERC721, MockERC1155, MockValidationRegistry, SchemaRegistry,
+    },
     types::{PublicProvider, WalletProvider},
 };
 
@@ -221,6 +223,55 @@ pub async fn wait_for_first_log(
     ))
 }
 
+/// Wait until the chain has mined a block numbered strictly above `block`,
+/// polling `eth_blockNumber` every `poll_interval`.
+///
+/// Dev chains that only mine when a transaction arrives (anvil and hardhat
+/// automine) are asked to mine one with `evm_mine` rather than waited on.
+/// Fails if no such block appears within `timeout`. Returns the first block
+/// number seen above `block`.
+pub async fn wait_for_block_after(
+    provider: &PublicProvider,
+    block: u64,
+    poll_interval: Duration,
+    timeout: Duration,
+) -> eyre::Result<u64> {
+    use alloy::providers::{Provider as _, ext::AnvilApi as _};
+
+    let current = provider.get_block_number().await?;
+    if current > block {
+        return Ok(current);
+    }
+
+    // Other nodes don't implement the method, which reads as not automining.
+    if provider.anvil_get_auto_mine().await.unwrap_or(false) {
+        provider.evm_mine(None).await?;
+    }
+
+    tokio::time::timeout(timeout, async {
+        loop {
+            let current = provider.get_block_number().await?;
+            if current > block {
+                return Ok(current);
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    })
+    .await
+    .map_err(|_| eyre::eyre!("No block after {block} was mined within {timeout:?}"))?
+}
+
+/// Generates a random 32-byte salt from the operating system's CSPRNG.
+///
+/// Used for commit-reveal commitments and EAS off-chain attestations.
+pub fn generate_salt() -> alloy::primitives::FixedBytes<32> {
+    use aes_gcm::aead::{OsRng, rand_core::RngCore as _};
+
+    let mut salt = alloy::primitives::FixedBytes::<32>::ZERO;
+    OsRng.fill_bytes(salt.as_mut_slice());
+    salt
+}
+
 /// True when the test suite was started with `ALKAHEST_TEST_TRANSPORT=http`.
 ///
 /// Used by test functions that exercise nonce-management interactions that
@@ -404,6 +455,7 @@ async fn build_shared_env() -> eyre::Result<SharedTestEnv> {
     let mock_erc721_b = MockERC721::deploy(&god_provider).await?;
     let mock_erc1155_a = MockERC1155::deploy(&god_provider).await?;
     let mock_erc1155_b = MockERC1155::deploy(&god_provider).await?;
+    let mock_validation_registry = MockValidationRegistry::deploy(&god_provider).await?;
 
     // Deploy core arbiters
     let trivial_arbiter = TrivialArbiter::deploy(&god_provider).await?;
@@ -585,6 +637,7 @@ async fn build_shared_env() -> eyre::Result<SharedTestEnv> {
                 nonexclusive_unrevocable_confirmation_arbiter
                     .address()
                     .clone(),
+            custom_arbiters: Default::default(),
         },
         string_obligation_addresses: StringObligationAddresses {
             eas: eas.address().clone(),
@@ -681,6 +734,7 @@ async fn build_shared_env() -> eyre::Result<SharedTestEnv> {
         erc721_b: mock_erc721_b.address().clone(),
         erc1155_a: mock_erc1155_a.address().clone(),
         erc1155_b: mock_erc1155_b.address().clone(),
+        validation_registry: *mock_validation_registry.address(),
     };
 
     // Capture the post-deploy state so per-test reverts can return here.
@@ -729,4 +783,5 @@ pub struct MockAddresses {
     pub erc721_b: Address,
     pub erc1155_a: Address,
     pub erc1155_b: Address,
+    pub validation_registry: Address,
 }
@@ -1,4 +1,5 @@
-use alkahest_rs::addresses::BASE_SEPOLIA_ADDRESSES;
+use alkahest_rs::{AddressLabels, addresses::BASE_SEPOLIA_ADDRESSES};
+use alloy::primitives::address;
 
 #[test]
 fn lookup_address_returns_all_matching_slots() {
@@ -34,3 +35,45 @@ fn lookup_address_includes_escrow_kind_for_escrow_obligations() {
         Some("erc20_escrow_obligation_default")
     );
 }
+
+#[test]
+fn custom_arbiters_are_indexed_and_resolvable_by_name() {
+    let custom = address!("0x00000000000000000000000000000000000c0001");
+    let mut config = BASE_SEPOLIA_ADDRESSES;
+    config
+        .arbiters_addresses
+        .custom_arbiters
+        .insert("score_arbiter".to_string(), custom);
+
+    let matches = config.lookup_address(custom);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].section, "arbiters_addresses");
+    assert_eq!(matches[0].field, "score_arbiter");
+
+    assert_eq!(config.resolve_name("score_arbiter"), Some(custom));
+    assert_eq!(
+        config.resolve_name("arbiters_addresses.score_arbiter"),
+        Some(custom)
+    );
+    assert_eq!(
+        AddressLabels::new(&config).get(&custom),
+        Some("score_arbiter")
+    );
+}
+
+#[test]
+fn resolve_name_requires_an_unambiguous_match() {
+    let config = BASE_SEPOLIA_ADDRESSES;
+
+    assert_eq!(
+        config.resolve_name("trusted_oracle_arbiter"),
+        Some(config.arbiters_addresses.trusted_oracle_arbiter)
+    );
+    assert_eq!(
+        config.resolve_name("erc20_addresses.escrow_obligation_default"),
+        Some(config.erc20_addresses.escrow_obligation_default)
+    );
+    // Every payment section has an escrow_obligation_default.
+    assert_eq!(config.resolve_name("escrow_obligation_default"), None);
+    assert_eq!(config.resolve_name("no_such_contract"), None);
+}
@@ -0,0 +1,154 @@
+use alkahest_rs::{
+    clients::arbiters::{
+        ArbiterDemand, ArbitersAddresses, DecodedCompositeDemand, DecodedDemand,
+        default_demand_codecs,
+    },
+    contracts::arbiters::TrustedOracleArbiter,
+};
+use alloy::{
+    primitives::{Address, Bytes, U256, address},
+    sol,
+};
+
+const SCORE_ARBITER: Address = address!("0x00000000000000000000000000000000000d0001");
+const THRESHOLD_ARBITER: Address = address!("0x00000000000000000000000000000000000d0002");
+
+sol! {
+    #[derive(Debug, PartialEq, ArbiterDemand)]
+    struct ScoreDemand {
+        address oracle;
+        uint8 minScore;
+    }
+
+    #[derive(Debug, PartialEq, ArbiterDemand)]
+    #[arbiter_demand(children)]
+    struct ThresholdDemand {
+        address[] arbiters;
+        bytes[] demands;
+        uint256 threshold;
+    }
+
+    #[derive(Debug, PartialEq, ArbiterDemand)]
+    #[arbiter_demand(children(arbiters = "targets", demands = "payloads"))]
+    struct RenamedChildrenDemand {
+        address[] targets;
+        bytes[] payloads;
+    }
+}
+
+fn score(min_score: u8) -> ScoreDemand {
+    ScoreDemand {
+        oracle: address!("0x1111111111111111111111111111111111111111"),
+        minScore: min_score,
+    }
+}
+
+fn registry(
+    addresses: &ArbitersAddresses,
+) -> alkahest_rs::clients::arbiters::ArbiterDemandCodecRegistry {
+    let mut registry = default_demand_codecs(addresses);
+    ScoreDemand::register_into(&mut registry, SCORE_ARBITER);
+    ThresholdDemand::register_into(&mut registry, THRESHOLD_ARBITER);
+    registry
+}
+
+#[test]
+fn derived_leaf_codec_roundtrips() -> eyre::Result<()> {
+    let addresses = ArbitersAddresses::default();
+    let registry = registry(&addresses);
+
+    let bytes: Bytes = score(70).into();
+    assert_eq!(ScoreDemand::try_from(&bytes)?, score(70));
+
+    let decoded = registry.decode(SCORE_ARBITER, &bytes)?;
+    let DecodedDemand::Extension(extension) = &decoded else {
+        panic!("expected extension demand, got {decoded:?}");
+    };
+    assert_eq!(extension.downcast_ref::<ScoreDemand>(), Some(&score(70)));
+
+    let direct = ScoreDemand::decode_demand(&registry, SCORE_ARBITER, bytes.clone())?;
+    assert!(matches!(direct, DecodedDemand::Extension(_)));
+
+    let encoded = decoded.encode_with_registry(&addresses, &registry)?;
+    assert_eq!(encoded.arbiter, SCORE_ARBITER);
+    assert_eq!(encoded.demand, bytes);
+    Ok(())
+}
+
+#[test]
+fn derived_composite_codec_decodes_children() -> eyre::Result<()> {
+    let addresses = ArbitersAddresses::default();
+    let registry = registry(&addresses);
+
+    let oracle_demand = TrustedOracleArbiter::DemandData {
+        oracle: address!("0x2222222222222222222222222222222222222222"),
+        data: Bytes::from_static(b"check"),
+    };
+    let threshold = ThresholdDemand {
+        arbiters: vec![addresses.trusted_oracle_arbiter, SCORE_ARBITER],
+        demands: vec![oracle_demand.clone().into(), score(50).into()],
+        threshold: U256::from(1),
+    };
+    let bytes: Bytes = threshold.clone().into();
+
+    let decoded = registry.decode(THRESHOLD_ARBITER, &bytes)?;
+    let DecodedDemand::Extension(extension) = &decoded else {
+        panic!("expected extension demand, got {decoded:?}");
+    };
+    let composite = extension
+        .downcast_ref::<DecodedCompositeDemand<ThresholdDemand>>()
+        .expect("composite demand");
+    assert_eq!(composite.data, threshold);
+    assert!(matches!(
+        &composite.children[0],
+        DecodedDemand::TrustedOracle(d) if d.oracle == oracle_demand.oracle
+    ));
+    let DecodedDemand::Extension(child) = &composite.children[1] else {
+        panic!("expected extension child");
+    };
+    assert_eq!(child.downcast_ref::<ScoreDemand>(), Some(&score(50)));
+
+    // Editing a decoded child is reflected when the tree is re-encoded.
+    let mut edited = composite.clone();
+    edited.children[1] = DecodedDemand::extension(SCORE_ARBITER, Bytes::new(), score(90));
+    let edited = DecodedDemand::extension(THRESHOLD_ARBITER, bytes.clone(), edited);
+    let encoded = edited.encode_with_registry(&addresses, &registry)?;
+
+    let reencoded = ThresholdDemand::try_from(&encoded.demand)?;
+    assert_eq!(reencoded.arbiters, threshold.arbiters);
+    assert_eq!(ScoreDemand::try_from(&reencoded.demands[1])?, score(90));
+    assert_eq!(reencoded.threshold, U256::from(1));
+    Ok(())
+}
+
+#[test]
+fn derived_composite_codec_honors_renamed_fields() -> eyre::Result<()> {
+    let addresses = ArbitersAddresses::default();
+    let mut registry = registry(&addresses);
+    let renamed_arbiter = address!("0x00000000000000000000000000000000000d0003");
+    RenamedChildrenDemand::register_into(&mut registry, renamed_arbiter);
+
+    let demand = RenamedChildrenDemand {
+        targets: vec![SCORE_ARBITER],
+        payloads: vec![score(10).into()],
+    };
+    let decoded = registry.decode(renamed_arbiter, &demand.clone().into())?;
+    let DecodedDemand::Extension(extension) = &decoded else {
+        panic!("expected extension demand, got {decoded:?}");
+    };
+    let composite = extension
+        .downcast_ref::<DecodedCompositeDemand<RenamedChildrenDemand>>()
+        .expect("composite demand");
+    assert_eq!(composite.children.len(), 1);
+
+    let mismatched = RenamedChildrenDemand {
+        targets: vec![SCORE_ARBITER, SCORE_ARBITER],
+        payloads: vec![score(10).into()],
+    };
+    assert!(
+        registry
+            .decode(renamed_arbiter, &mismatched.into())
+            .is_err()
+    );
+    Ok(())
+}
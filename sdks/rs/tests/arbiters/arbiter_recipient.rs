@@ -1,5 +1,6 @@
 use alkahest_rs::{
     contracts::{self, arbiters::attestation_properties::RecipientArbiter},
+    extensions::HasArbiters,
     utils::setup_test_environment,
 };
 use alloy::primitives::{Address, Bytes, FixedBytes};
@@ -163,3 +164,39 @@ async fn test_recipient_arbiter_trait_based_encoding() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_recipient_arbiter_via_attestation_properties_api() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let recipient_api = test
+        .alice_client
+        .arbiters()
+        .attestation_properties()
+        .recipient();
+
+    let attestation = contracts::IEAS::Attestation {
+        uid: FixedBytes::<32>::default(),
+        schema: FixedBytes::<32>::default(),
+        time: 0,
+        expirationTime: 0,
+        revocationTime: 0,
+        refUID: FixedBytes::<32>::default(),
+        recipient: test.bob.address(),
+        attester: Address::default(),
+        revocable: true,
+        data: Bytes::default(),
+    };
+
+    let matching = recipient_api.demand(test.bob.address());
+    let mismatched = recipient_api.demand(test.alice.address());
+
+    assert!(recipient_api.evaluate(&matching, &attestation));
+    assert!(!recipient_api.evaluate(&mismatched, &attestation));
+    assert!(recipient_api.check(&attestation, &matching).await?);
+    assert!(!recipient_api.check(&attestation, &mismatched).await?);
+
+    let decoded = recipient_api.decode(&recipient_api.encode(&matching))?;
+    assert_eq!(decoded.recipient, test.bob.address());
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use alkahest_rs::{
+    DefaultAlkahestClient,
+    clients::arbiters::{
+        ConfirmationArbiterType, ConfirmationCapabilityError, DecodedAllArbiterDemandData,
+        DecodedDemand,
+    },
+    extensions::{HasArbiters, HasErc20, HasStringObligation},
+    fixtures::MockERC20Permit,
+    types::{ArbiterData, Erc20Data},
+    utils::{TestContext, setup_test_environment},
+};
+use alloy::primitives::{Address, Bytes, FixedBytes};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+async fn create_escrow(test: &TestContext, arbiter: Address) -> eyre::Result<FixedBytes<32>> {
+    let mock_erc20 = MockERC20Permit::new(test.mock_addresses.erc20_a, &test.god_provider);
+    mock_erc20
+        .transfer(test.alice.address(), 100u64.try_into()?)
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+
+    let price = Erc20Data {
+        address: test.mock_addresses.erc20_a,
+        value: 100u64.try_into()?,
+    };
+    let item = ArbiterData {
+        arbiter,
+        demand: Bytes::new(),
+    };
+    let expiration = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 3600;
+
+    let receipt = test
+        .alice_client
+        .erc20()
+        .escrow()
+        .default()
+        .permit_and_create(&price, &item, expiration)
+        .await?;
+    Ok(DefaultAlkahestClient::get_attested_event(receipt)?.uid)
+}
+
+#[tokio::test]
+async fn test_unified_client_confirm_and_revoke() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let arbiter_type = ConfirmationArbiterType::NonexclusiveRevocable;
+    let escrow = create_escrow(
+        &test,
+        test.alice_client
+            .arbiters()
+            .confirmation_arbiter_address(arbiter_type),
+    )
+    .await?;
+
+    let receipt = test
+        .bob_client
+        .string_obligation()
+        .do_obligation("done".to_string(), None, Some(escrow))
+        .await?;
+    let fulfillment = DefaultAlkahestClient::get_attested_event(receipt)?.uid;
+
+    let arbiters = test.alice_client.arbiters();
+    let client = arbiters.confirmation().client(arbiter_type);
+    assert_eq!(
+        client.address(),
+        arbiters.confirmation().nonexclusive_revocable().address()
+    );
+
+    client.confirm(fulfillment, escrow).await?;
+    assert!(client.is_confirmed(fulfillment, escrow).await?);
+    assert!(!client.is_escrow_taken(escrow).await?);
+    client
+        .wait_for_confirmation(fulfillment, escrow, None)
+        .await?;
+
+    client.revoke(fulfillment, escrow).await?;
+    assert!(!client.is_confirmed(fulfillment, escrow).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unified_client_rejects_revoke_on_unrevocable() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let arbiters = test.alice_client.arbiters();
+
+    for arbiter_type in [
+        ConfirmationArbiterType::ExclusiveUnrevocable,
+        ConfirmationArbiterType::NonexclusiveUnrevocable,
+    ] {
+        let err = arbiters
+            .confirmation()
+            .client(arbiter_type)
+            .revoke(FixedBytes::ZERO, FixedBytes::ZERO)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ConfirmationCapabilityError>(),
+            Some(&ConfirmationCapabilityError::NotRevocable(arbiter_type))
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unified_client_detects_type() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let arbiters = test.alice_client.arbiters();
+    let addresses = &test.addresses.arbiters_addresses;
+
+    for arbiter_type in ConfirmationArbiterType::ALL {
+        let address = arbiters.confirmation_arbiter_address(arbiter_type);
+        let client = arbiters.confirmation().for_address(address)?;
+        assert_eq!(client.arbiter_type(), arbiter_type);
+    }
+    assert!(
+        arbiters
+            .confirmation()
+            .for_address(addresses.trivial_arbiter)
+            .is_err()
+    );
+
+    // Confirmation arbiters carry no demand data and decode as unknown leaves.
+    let confirmation_leaf = DecodedDemand::Unknown {
+        arbiter: addresses.exclusive_unrevocable_confirmation_arbiter,
+        raw_data: Bytes::new(),
+    };
+    let demand = DecodedDemand::AllArbiter(DecodedAllArbiterDemandData {
+        arbiters: vec![
+            addresses.trivial_arbiter,
+            addresses.exclusive_unrevocable_confirmation_arbiter,
+        ],
+        demands: vec![DecodedDemand::TrivialArbiter, confirmation_leaf],
+    });
+    let client = arbiters
+        .confirmation()
+        .for_demand(addresses.all_arbiter, &demand)?;
+    assert_eq!(
+        client.arbiter_type(),
+        ConfirmationArbiterType::ExclusiveUnrevocable
+    );
+
+    assert!(
+        arbiters
+            .confirmation()
+            .for_demand(addresses.trivial_arbiter, &DecodedDemand::TrivialArbiter)
+            .is_err()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use alkahest_rs::{
+    clients::arbiters::{ArbiterDemand, ArbiterDemandDataCodec, ContractArtifact, DecodedDemand},
+    extensions::HasArbiters,
+    utils::setup_test_environment,
+};
+use alloy::{dyn_abi::DynSolValue, primitives::Bytes, providers::Provider as _, sol};
+
+sol! {
+    /// Same layout as TrustedOracleArbiter's demand
+    #[derive(Debug, PartialEq, ArbiterDemand)]
+    struct OracleDemand {
+        address oracle;
+        bytes data;
+    }
+}
+
+#[tokio::test]
+async fn test_deploy_and_register_custom_arbiter() -> eyre::Result<()> {
+    let mut test = setup_test_environment().await?;
+    let artifact = ContractArtifact::from_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/src/contracts/arbiters/TrustedOracleArbiter.json"
+    ))?;
+    let eas = test.addresses.arbiters_addresses.eas;
+
+    let deployed = test
+        .alice_client
+        .extensions
+        .arbiters
+        .deploy_arbiter(
+            "custom_oracle",
+            &artifact,
+            &[DynSolValue::Address(eas)],
+            ArbiterDemandDataCodec::<OracleDemand>::new(),
+        )
+        .await?;
+    assert!(
+        !test
+            .god_provider
+            .get_code_at(deployed.address)
+            .await?
+            .is_empty()
+    );
+
+    let arbiters = test.alice_client.arbiters();
+    assert_eq!(
+        arbiters.addresses.custom_arbiter("custom_oracle"),
+        Some(deployed.address)
+    );
+
+    // The registered codec decodes demands for the new address.
+    let demand = OracleDemand {
+        oracle: test.bob.address(),
+        data: Bytes::from_static(b"custom"),
+    };
+    let decoded = arbiters.decode_arbiter_demand(deployed.address, &demand.clone().into())?;
+    let DecodedDemand::Extension(extension) = decoded else {
+        panic!("expected extension demand");
+    };
+    assert_eq!(extension.downcast_ref::<OracleDemand>(), Some(&demand));
+
+    // The client's address index resolves the new name.
+    assert_eq!(
+        test.alice_client.resolve_name("custom_oracle"),
+        Some(deployed.address)
+    );
+
+    // Built-in names are refused before anything is deployed.
+    assert!(
+        test.alice_client
+            .extensions
+            .arbiters
+            .deploy_arbiter(
+                "trivial_arbiter",
+                &artifact,
+                &[DynSolValue::Address(eas)],
+                ArbiterDemandDataCodec::<OracleDemand>::new(),
+            )
+            .await
+            .is_err()
+    );
+
+    Ok(())
+}
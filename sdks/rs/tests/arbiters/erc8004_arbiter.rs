@@ -0,0 +1,116 @@
+use crate::arbiters::common::create_test_attestation;
+use alkahest_rs::{
+    clients::arbiters::Erc8004CheckFailure, contracts::arbiters::ERC8004Arbiter,
+    extensions::HasArbiters, utils::setup_test_environment,
+};
+use alloy::primitives::{Bytes, FixedBytes, U256};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_erc8004_validation_flow() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let registry = test.mock_addresses.validation_registry;
+    let escrow_uid = FixedBytes::<32>::from_slice(&[3u8; 32]);
+    let fulfillment_uid = FixedBytes::<32>::from_slice(&[4u8; 32]);
+
+    let mut fulfillment = create_test_attestation(Some(fulfillment_uid), None);
+    fulfillment.refUID = escrow_uid;
+
+    let alice_erc8004 = test.alice_client.arbiters().erc8004();
+    let demand = alice_erc8004.demand(
+        registry,
+        test.bob.address(),
+        60,
+        Bytes::from_static(b"ipfs://validation-request"),
+    )?;
+    assert!(
+        alice_erc8004
+            .demand(registry, test.bob.address(), 0, Bytes::new())
+            .is_err()
+    );
+
+    // Nothing requested yet.
+    assert_eq!(
+        alice_erc8004
+            .explain_check(&fulfillment, &demand, escrow_uid)
+            .await?,
+        Some(Erc8004CheckFailure::ValidationNotFound)
+    );
+    assert!(matches!(
+        alice_erc8004
+            .explain_check(&fulfillment, &demand, FixedBytes::ZERO)
+            .await?,
+        Some(Erc8004CheckFailure::FulfillmentMustReferenceEscrow { .. })
+    ));
+
+    alice_erc8004
+        .request_validation(
+            &demand,
+            fulfillment_uid,
+            U256::from(1),
+            "ipfs://validation-request".into(),
+        )
+        .await?;
+
+    let request_hash = alice_erc8004.request_hash(fulfillment_uid, &demand);
+    let bob_erc8004 = test.bob_client.arbiters().erc8004();
+    bob_erc8004
+        .respond(
+            registry,
+            request_hash,
+            40,
+            String::new(),
+            FixedBytes::ZERO,
+            String::new(),
+        )
+        .await?;
+
+    assert_eq!(
+        alice_erc8004
+            .explain_check(&fulfillment, &demand, escrow_uid)
+            .await?,
+        Some(Erc8004CheckFailure::ResponseBelowMinimum {
+            response: 40,
+            min_response: 60,
+        })
+    );
+    assert!(
+        alice_erc8004
+            .wait_for_response(&demand, fulfillment_uid, Some(Duration::from_millis(200)))
+            .await
+            .is_err()
+    );
+
+    bob_erc8004
+        .respond(
+            registry,
+            request_hash,
+            85,
+            String::new(),
+            FixedBytes::ZERO,
+            String::new(),
+        )
+        .await?;
+
+    let status = alice_erc8004
+        .wait_for_response(&demand, fulfillment_uid, Some(Duration::from_secs(10)))
+        .await?;
+    assert_eq!(status.response, 85);
+    assert_eq!(status.validator, test.bob.address());
+    assert_eq!(
+        alice_erc8004
+            .explain_check(&fulfillment, &demand, escrow_uid)
+            .await?,
+        None
+    );
+
+    // The deployed arbiter agrees.
+    let arbiter = ERC8004Arbiter::new(alice_erc8004.address(), &test.alice_client.public_provider);
+    let passed = arbiter
+        .check(fulfillment.into(), demand.into(), escrow_uid)
+        .call()
+        .await?;
+    assert!(passed);
+
+    Ok(())
+}
@@ -3,6 +3,9 @@
 
 pub mod arbiter_recipient;
 pub mod arbiter_uid;
+pub mod confirmation_arbiters;
+pub mod custom_arbiter_deploy;
+pub mod erc8004_arbiter;
 pub mod logical_api;
 
 // Core arbiter tests split from arbiters_main.rs
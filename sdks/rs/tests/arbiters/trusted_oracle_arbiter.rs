@@ -1,6 +1,8 @@
 use crate::arbiters::common::create_test_attestation;
 use alkahest_rs::{
-    contracts::arbiters::TrustedOracleArbiter, extensions::HasArbiters,
+    clients::arbiters::{Demand, EvaluationSource, Verdict},
+    contracts::arbiters::TrustedOracleArbiter,
+    extensions::HasArbiters,
     utils::setup_test_environment,
 };
 use alloy::{
@@ -334,3 +336,43 @@ async fn test_trusted_oracle_arbiter_trait_based_encoding() -> eyre::Result<()>
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_evaluate_demand_resolves_oracle_leaf_on_chain() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let arbiters = test.alice_client.arbiters();
+    let addresses = &test.addresses.arbiters_addresses;
+
+    let obligation_uid = FixedBytes::<32>::from_slice(&[2u8; 32]);
+    let attestation = create_test_attestation(Some(obligation_uid), None);
+
+    let item = arbiters.encode_demand(&Demand::all([
+        Demand::Intrinsics,
+        Demand::trusted_oracle(test.bob.address(), Bytes::default()),
+    ]));
+    let decoded = arbiters.decode_arbiter_demand(item.arbiter, &item.demand)?;
+    assert_eq!(item.arbiter, addresses.all_arbiter);
+
+    // Intrinsics passes locally; the oracle has not decided yet.
+    let trace = arbiters
+        .evaluate_demand(item.arbiter, &decoded, &attestation, FixedBytes::ZERO)
+        .await?;
+    assert_eq!(trace.children[0].verdict, Verdict::Pass);
+    assert_eq!(trace.children[0].source, EvaluationSource::Local);
+    assert_eq!(trace.children[1].source, EvaluationSource::OnChain);
+    assert_eq!(trace.verdict, Verdict::Fail);
+
+    test.bob_client
+        .arbiters()
+        .trusted_oracle()
+        .arbitrate(obligation_uid, Bytes::default(), true)
+        .await?;
+
+    let trace = arbiters
+        .evaluate_demand(item.arbiter, &decoded, &attestation, FixedBytes::ZERO)
+        .await?;
+    assert_eq!(trace.children[1].verdict, Verdict::Pass);
+    assert_eq!(trace.verdict, Verdict::Pass);
+
+    Ok(())
+}
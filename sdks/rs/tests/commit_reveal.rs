@@ -1,17 +1,20 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use alloy::{
     primitives::{Bytes, FixedBytes, U256},
-    providers::ext::AnvilApi as _,
+    providers::{Provider as _, ext::AnvilApi as _},
 };
 
 use alkahest_rs::{
     DefaultAlkahestClient,
-    clients::commit_reveal_obligation::CommitRevealObligationModule,
+    clients::commit_reveal_obligation::{
+        CommitRevealObligationModule, FileRevealStore, MemoryRevealStore, PendingReveal,
+        RevealStage, RevealStateStore as _, SaltVault,
+    },
     contracts::obligations::CommitRevealObligation,
     extensions::{HasCommitReveal, HasNativeToken},
     types::{ArbiterData, NativeTokenData},
-    utils::setup_test_environment,
+    utils::{TestContext, setup_test_environment, wait_for_block_after},
 };
 
 /// Full lifecycle: Alice escrows native tokens → Bob commits → reveals → collects escrow → reclaims bond.
@@ -206,3 +209,321 @@ async fn test_commit_reveal_bond_slash() -> eyre::Result<()> {
 
     Ok(())
 }
+
+/// Alice escrows 2 ETH behind a commit-reveal demand of a 0.01 ETH bond and a
+/// one-hour reveal window. Returns the escrow UID.
+async fn create_commit_reveal_escrow(test: &TestContext) -> eyre::Result<FixedBytes<32>> {
+    let expiration = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 3600;
+    let demand = CommitRevealObligation::DemandData {
+        bondAmount: U256::from(10_000_000_000_000_000u64),
+        commitDeadline: U256::from(3600u64),
+    };
+    let item = ArbiterData {
+        arbiter: test.addresses.commit_reveal_obligation_addresses.obligation,
+        demand: CommitRevealObligationModule::encode_demand(&demand),
+    };
+
+    let escrow_receipt = test
+        .alice_client
+        .native_token()
+        .escrow()
+        .default()
+        .create(
+            &NativeTokenData {
+                value: U256::from(2_000_000_000_000_000_000u128),
+            },
+            &item,
+            expiration,
+        )
+        .await?;
+    Ok(DefaultAlkahestClient::get_attested_event(escrow_receipt)?.uid)
+}
+
+/// `commit_then_reveal` runs the whole lifecycle and clears its saved state.
+#[tokio::test]
+async fn test_commit_then_reveal_flow() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let escrow_uid = create_commit_reveal_escrow(&test).await?;
+
+    let data = CommitRevealObligation::ObligationData {
+        payload: Bytes::from(b"orchestrated".to_vec()),
+        salt: FixedBytes::<32>::from([0xcc; 32]),
+        schema: FixedBytes::<32>::default(),
+    };
+    let store = MemoryRevealStore::new();
+    let outcome = test
+        .bob_client
+        .commit_reveal()
+        .flow(&store)
+        .commit_then_reveal(escrow_uid, data.clone())
+        .await?;
+
+    assert_eq!(
+        outcome.commitment,
+        CommitRevealObligationModule::commitment_hash(escrow_uid, test.bob.address(), &data)
+    );
+    assert!(outcome.commit_receipt.is_some());
+    assert_eq!(
+        outcome.bond_reclaimed,
+        U256::from(10_000_000_000_000_000u64)
+    );
+    assert!(
+        test.bob_client
+            .commit_reveal()
+            .is_commitment_claimed(outcome.commitment)
+            .await?
+    );
+    assert!(store.load(escrow_uid)?.is_none());
+
+    let fulfillment = test
+        .bob_client
+        .commit_reveal()
+        .get_obligation(outcome.fulfillment_uid)
+        .await?;
+    assert_eq!(fulfillment.attestation.refUID, escrow_uid);
+    assert_eq!(fulfillment.data.payload, data.payload);
+    Ok(())
+}
+
+/// A process that committed and then crashed finishes the reveal from the
+/// state file without committing again.
+#[tokio::test]
+async fn test_commit_then_reveal_resumes_from_file() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let escrow_uid = create_commit_reveal_escrow(&test).await?;
+
+    let data = CommitRevealObligation::ObligationData {
+        payload: Bytes::from(b"resumed".to_vec()),
+        salt: FixedBytes::<32>::from([0xdd; 32]),
+        schema: FixedBytes::<32>::default(),
+    };
+    let commitment =
+        CommitRevealObligationModule::commitment_hash(escrow_uid, test.bob.address(), &data);
+    let bond_amount = U256::from(10_000_000_000_000_000u64);
+    let commit_deadline = U256::from(3600u64);
+
+    // Simulate the first run: state saved, commit landed, then a crash.
+    let dir = tempfile::tempdir()?;
+    let store = FileRevealStore::new(dir.path())?;
+    store.save(&PendingReveal {
+        escrow_uid,
+        escrow_contract: test
+            .addresses
+            .native_token_addresses
+            .escrow_obligation_default,
+        commitment,
+        claimer: test.bob.address(),
+        payload: data.payload.clone(),
+        salt: data.salt,
+        schema: data.schema,
+        bond_amount,
+        commit_deadline,
+        stage: RevealStage::Committed,
+    })?;
+    test.bob_client
+        .commit_reveal()
+        .commit(commitment, bond_amount, commit_deadline)
+        .await?;
+
+    let restarted = FileRevealStore::new(dir.path())?;
+    let outcome = test
+        .bob_client
+        .commit_reveal()
+        .flow(&restarted)
+        .resume(escrow_uid)
+        .await?
+        .expect("pending reveal should be found");
+    assert_eq!(outcome.commitment, commitment);
+    assert!(outcome.commit_receipt.is_none());
+    assert!(restarted.load(escrow_uid)?.is_none());
+    Ok(())
+}
+
+/// Without automine the flow waits for the next interval-mined block before
+/// revealing.
+#[tokio::test]
+async fn test_commit_then_reveal_with_interval_mining() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let escrow_uid = create_commit_reveal_escrow(&test).await?;
+
+    let data = CommitRevealObligation::ObligationData {
+        payload: Bytes::from(b"interval".to_vec()),
+        salt: FixedBytes::<32>::from([0xee; 32]),
+        schema: FixedBytes::<32>::default(),
+    };
+    let store = MemoryRevealStore::new();
+    test.god_provider.anvil_set_interval_mining(1).await?;
+    let outcome = test
+        .bob_client
+        .commit_reveal()
+        .flow(&store)
+        .commit_then_reveal(escrow_uid, data)
+        .await;
+    // Mining mode survives snapshot reverts, so restore it before asserting.
+    test.god_provider.anvil_set_auto_mine(true).await?;
+
+    let outcome = outcome?;
+    let commit_block = outcome.commit_receipt.and_then(|r| r.block_number);
+    assert!(outcome.reveal_receipt.block_number > commit_block);
+    Ok(())
+}
+
+/// `wait_for_block_after` mines a block itself on automine, and times out
+/// when nothing mines.
+#[tokio::test]
+async fn test_wait_for_block_after() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let provider = test.bob_client.public_provider.clone();
+    let poll = Duration::from_millis(50);
+
+    let current = provider.get_block_number().await?;
+    let next = wait_for_block_after(&provider, current, poll, Duration::from_secs(5)).await?;
+    assert!(next > current);
+
+    test.god_provider.anvil_set_auto_mine(false).await?;
+    let stalled = wait_for_block_after(&provider, next, poll, Duration::from_millis(500)).await;
+    test.god_provider.anvil_set_auto_mine(true).await?;
+    assert!(stalled.is_err());
+    Ok(())
+}
+
+/// The slasher reports an expired commitment in dry-run mode, then slashes
+/// it and pays `slashedBondRecipient`.
+#[tokio::test]
+async fn test_bond_slasher_dry_run_then_slash() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let escrow_uid = create_commit_reveal_escrow(&test).await?;
+
+    let data = CommitRevealObligation::ObligationData {
+        payload: Bytes::from(b"never revealed".to_vec()),
+        salt: FixedBytes::<32>::from([0xee; 32]),
+        schema: FixedBytes::<32>::default(),
+    };
+    let commitment =
+        CommitRevealObligationModule::commitment_hash(escrow_uid, test.bob.address(), &data);
+    let bond_amount = U256::from(10_000_000_000_000_000u64);
+    test.bob_client
+        .commit_reveal()
+        .commit(commitment, bond_amount, U256::from(3600u64))
+        .await?;
+
+    let slasher = test.alice_client.commit_reveal().slasher();
+    let index = slasher.index().await?;
+    assert_eq!(
+        index.get(commitment).map(|t| t.bond_amount),
+        Some(bond_amount)
+    );
+
+    // Nothing is slashable before the deadline.
+    assert!(slasher.clone().dry_run(true).run_once().await?.is_empty());
+
+    test.god_provider.anvil_increase_time(3601).await?;
+    test.god_provider.anvil_mine(Some(1), None).await?;
+
+    let recipient = test
+        .alice_client
+        .commit_reveal()
+        .slashed_bond_recipient()
+        .await?;
+    // Two dry-run ticks over the same index report the commitment once.
+    let dry_run = slasher.clone().dry_run(true);
+    let mut index = dry_run.index().await?;
+    let reports = dry_run.slash_expired(&mut index).await?;
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].commitment, commitment);
+    assert_eq!(reports[0].recipient, recipient);
+    assert!(reports[0].receipt.is_none());
+    assert!(dry_run.slash_expired(&mut index).await?.is_empty());
+    assert!(index.get(commitment).is_some());
+    assert!(
+        !test
+            .alice_client
+            .commit_reveal()
+            .is_commitment_claimed(commitment)
+            .await?
+    );
+
+    let reports = slasher.run_once().await?;
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].amount, bond_amount);
+    assert!(reports[0].receipt.is_some());
+    assert!(
+        test.alice_client
+            .commit_reveal()
+            .is_commitment_claimed(commitment)
+            .await?
+    );
+
+    // The slash event drops the commitment from a fresh index.
+    assert!(slasher.index().await?.get(commitment).is_none());
+    Ok(())
+}
+
+/// Salts committed through the vault survive a reopen, can't be reused, and
+/// recover the exact data needed to reveal.
+#[tokio::test]
+async fn test_commit_with_salt_vault() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let escrow_uid = create_commit_reveal_escrow(&test).await?;
+    let bond_amount = U256::from(10_000_000_000_000_000u64);
+    let commit_deadline = U256::from(3600u64);
+
+    let dir = tempfile::tempdir()?;
+    let vault_path = dir.path().join("salts.vault");
+    let mut vault = SaltVault::open(&vault_path, "passphrase")?;
+
+    let data = CommitRevealObligationModule::sealed_data(
+        Bytes::from(b"vaulted".to_vec()),
+        FixedBytes::<32>::default(),
+    );
+    let (commitment, receipt) = test
+        .bob_client
+        .commit_reveal()
+        .commit_with_vault(
+            &mut vault,
+            escrow_uid,
+            data.clone(),
+            bond_amount,
+            commit_deadline,
+        )
+        .await?;
+    assert!(receipt.status());
+
+    // Reusing the salt for a different payload is refused before sending.
+    let reused = CommitRevealObligation::ObligationData {
+        payload: Bytes::from(b"other".to_vec()),
+        ..data.clone()
+    };
+    assert!(
+        test.bob_client
+            .commit_reveal()
+            .commit_with_vault(&mut vault, escrow_uid, reused, bond_amount, commit_deadline)
+            .await
+            .is_err()
+    );
+
+    let reopened = SaltVault::open(&vault_path, "passphrase")?;
+    let recovered = reopened.recover(commitment)?;
+    assert_eq!(recovered.salt, data.salt);
+
+    let reveal_receipt = test
+        .bob_client
+        .commit_reveal()
+        .reveal_and_collect(
+            recovered,
+            test.bob.address(),
+            test.addresses
+                .native_token_addresses
+                .escrow_obligation_default,
+            escrow_uid,
+        )
+        .await?;
+    assert!(reveal_receipt.status());
+    assert!(
+        test.bob_client
+            .commit_reveal()
+            .is_commitment_claimed(commitment)
+            .await?
+    );
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use alkahest_rs::{
+    DefaultAlkahestClient,
+    clients::arbiters::{ConfirmationArbiterType, InboxOutcome},
+    contracts::obligations::StringObligation,
+    extensions::{HasArbiters, HasErc20, HasStringObligation},
+    fixtures::MockERC20Permit,
+    types::{ArbiterData, Erc20Data},
+    utils::{TestContext, setup_test_environment},
+};
+use alloy::primitives::{Address, Bytes, FixedBytes};
+use futures::StreamExt as _;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+async fn create_escrow(test: &TestContext, arbiter: Address) -> eyre::Result<FixedBytes<32>> {
+    let mock_erc20 = MockERC20Permit::new(test.mock_addresses.erc20_a, &test.god_provider);
+    mock_erc20
+        .transfer(test.alice.address(), 100u64.try_into()?)
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+
+    let price = Erc20Data {
+        address: test.mock_addresses.erc20_a,
+        value: 100u64.try_into()?,
+    };
+    let item = ArbiterData {
+        arbiter,
+        demand: Bytes::new(),
+    };
+    let expiration = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 3600;
+
+    let receipt = test
+        .alice_client
+        .erc20()
+        .escrow()
+        .default()
+        .permit_and_create(&price, &item, expiration)
+        .await?;
+    Ok(DefaultAlkahestClient::get_attested_event(receipt)?.uid)
+}
+
+async fn fulfill(
+    test: &TestContext,
+    statement: &str,
+    escrow_uid: FixedBytes<32>,
+) -> eyre::Result<FixedBytes<32>> {
+    let receipt = test
+        .bob_client
+        .string_obligation()
+        .do_obligation(statement.to_string(), None, Some(escrow_uid))
+        .await?;
+    Ok(DefaultAlkahestClient::get_attested_event(receipt)?.uid)
+}
+
+#[tokio::test]
+async fn test_inbox_lists_and_bulk_confirms() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let arbiters = &test.addresses.arbiters_addresses;
+
+    let exclusive_escrow =
+        create_escrow(&test, arbiters.exclusive_revocable_confirmation_arbiter).await?;
+    let nonexclusive_escrow =
+        create_escrow(&test, arbiters.nonexclusive_revocable_confirmation_arbiter).await?;
+
+    let first = fulfill(&test, "first", exclusive_escrow).await?;
+    let second = fulfill(&test, "second", nonexclusive_escrow).await?;
+
+    let bob_confirmation = test.bob_client.arbiters().confirmation();
+    bob_confirmation
+        .exclusive_revocable()
+        .request_confirmation(first, exclusive_escrow)
+        .await?;
+    bob_confirmation
+        .nonexclusive_revocable()
+        .request_confirmation(second, nonexclusive_escrow)
+        .await?;
+    // A repeated request is listed once.
+    bob_confirmation
+        .nonexclusive_revocable()
+        .request_confirmation(second, nonexclusive_escrow)
+        .await?;
+
+    // Bob is not the escrow recipient, so his inbox is empty.
+    assert!(
+        test.bob_client
+            .arbiters()
+            .confirmation()
+            .inbox()
+            .list_pending(None)
+            .await?
+            .is_empty()
+    );
+
+    let alice_arbiters = test.alice_client.arbiters();
+    let inbox = alice_arbiters.confirmation().inbox();
+    let pending = inbox.list_pending(None).await?;
+    assert_eq!(pending.len(), 2);
+    assert_eq!(
+        pending[0].arbiter_type,
+        ConfirmationArbiterType::ExclusiveRevocable
+    );
+    assert_eq!(pending[0].fulfillment_uid, first);
+    assert_eq!(
+        pending[1]
+            .obligation_data::<StringObligation::ObligationData>()?
+            .item,
+        "second"
+    );
+
+    let outcomes = inbox
+        .respond(&[(&pending[0], true), (&pending[1], false)])
+        .await;
+    assert!(matches!(outcomes[0], InboxOutcome::Confirmed(_)));
+    assert!(matches!(outcomes[1], InboxOutcome::Rejected));
+
+    let remaining = inbox.list_pending(None).await?;
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].fulfillment_uid, second);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_inbox_streams_new_requests() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let arbiter = test
+        .addresses
+        .arbiters_addresses
+        .nonexclusive_unrevocable_confirmation_arbiter;
+
+    let escrow = create_escrow(&test, arbiter).await?;
+    let fulfillment = fulfill(&test, "streamed", escrow).await?;
+
+    let alice_arbiters = test.alice_client.arbiters();
+    let inbox = alice_arbiters.confirmation().inbox();
+    let mut requests = inbox.stream().await?;
+
+    test.bob_client
+        .arbiters()
+        .confirmation()
+        .nonexclusive_unrevocable()
+        .request_confirmation(fulfillment, escrow)
+        .await?;
+
+    let request = tokio::time::timeout(Duration::from_secs(10), requests.next())
+        .await?
+        .ok_or_else(|| eyre::eyre!("stream ended"))??;
+    assert_eq!(request.fulfillment_uid, fulfillment);
+    assert_eq!(request.escrow_uid, escrow);
+    assert_eq!(
+        request.arbiter_type,
+        ConfirmationArbiterType::NonexclusiveUnrevocable
+    );
+
+    requests.close().await?;
+    Ok(())
+}
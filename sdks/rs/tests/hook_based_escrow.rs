@@ -0,0 +1,234 @@
+use alkahest_rs::{
+    DefaultAlkahestClient,
+    clients::hook_based::hook_data::HookAsset,
+    extensions::{HasHookBased, HasStringObligation},
+    fixtures::{MockERC20Permit, MockERC721},
+    types::{ArbiterData, Erc20Data, Erc721Data, NativeTokenData},
+    utils::setup_test_environment,
+};
+use alloy::{
+    primitives::{Bytes, U256},
+    providers::{Provider as _, ext::AnvilApi as _},
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[tokio::test]
+async fn test_hooks_escrow_erc20_and_erc721_collect() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+
+    let mock_erc20 = MockERC20Permit::new(test.mock_addresses.erc20_a, &test.god_provider);
+    mock_erc20
+        .transfer(test.alice.address(), U256::from(100))
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+    let mock_erc721 = MockERC721::new(test.mock_addresses.erc721_a, &test.god_provider);
+    mock_erc721
+        .mint(test.alice.address())
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+
+    let assets = [
+        HookAsset::erc20(test.mock_addresses.erc20_a, U256::from(100)),
+        HookAsset::erc721(test.mock_addresses.erc721_a, U256::from(1)),
+    ];
+    let item = ArbiterData {
+        arbiter: test.addresses.arbiters_addresses.trivial_arbiter,
+        demand: Bytes::new(),
+    };
+    let expiration = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 3600;
+
+    let hooks_escrow = test.alice_client.hook_based().escrow().hooks();
+    let receipt = hooks_escrow.create(&assets, &item, expiration).await?;
+    let escrow_uid = DefaultAlkahestClient::get_attested_event(receipt)?.uid;
+
+    let hook_addresses = &test.addresses.hook_based_addresses;
+    assert_eq!(
+        mock_erc20
+            .balanceOf(hook_addresses.erc20_escrow_hook)
+            .call()
+            .await?,
+        U256::from(100)
+    );
+    assert_eq!(
+        mock_erc721.ownerOf(U256::from(1)).call().await?,
+        hook_addresses.erc721_escrow_hook
+    );
+
+    let obligation = hooks_escrow.get_obligation(escrow_uid).await?;
+    assert_eq!(obligation.data.hooks.len(), 2);
+    for (i, asset) in assets.iter().enumerate() {
+        let decoded = HookAsset::decode(
+            hook_addresses,
+            obligation.data.hooks[i],
+            &obligation.data.hookDatas[i],
+        )?;
+        assert_eq!(decoded.hook_data(), asset.hook_data());
+    }
+
+    let fulfillment = test
+        .bob_client
+        .string_obligation()
+        .do_obligation("done".to_string(), None, Some(escrow_uid))
+        .await?;
+    let fulfillment_uid = DefaultAlkahestClient::get_attested_event(fulfillment)?.uid;
+
+    test.bob_client
+        .hook_based()
+        .escrow()
+        .hooks()
+        .collect(escrow_uid, fulfillment_uid)
+        .await?;
+
+    assert_eq!(
+        mock_erc20.balanceOf(test.bob.address()).call().await?,
+        U256::from(100)
+    );
+    assert_eq!(
+        mock_erc721.ownerOf(U256::from(1)).call().await?,
+        test.bob.address()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_hook_escrow_approvals_are_skipped_once_made() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let hook_based = test.alice_client.hook_based();
+    let escrow = hook_based.escrow().hook().address();
+    let assets = [
+        HookAsset::erc20(test.mock_addresses.erc20_a, U256::from(60)),
+        HookAsset::erc20(test.mock_addresses.erc20_a, U256::from(40)),
+    ];
+
+    // One hook approval and one allowance covering both amounts
+    let receipts = hook_based.util().approve_assets(&assets, escrow).await?;
+    assert_eq!(receipts.len(), 2);
+    assert!(
+        hook_based
+            .util()
+            .is_escrow_approved(
+                test.addresses.hook_based_addresses.erc20_escrow_hook,
+                escrow
+            )
+            .await?
+    );
+
+    let receipts = hook_based.util().approve_assets(&assets, escrow).await?;
+    assert!(receipts.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_hook_escrow_native_create_for_and_reclaim() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let amount = U256::from(1_000_000_000_000_000u64);
+    let asset = HookAsset::native_token(amount);
+    let item = ArbiterData {
+        arbiter: test.addresses.arbiters_addresses.trivial_arbiter,
+        demand: Bytes::new(),
+    };
+    let expiration = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 10;
+
+    let hook_escrow = test.alice_client.hook_based().escrow().hook();
+    let receipt = hook_escrow
+        .create_for(&asset, &item, expiration, test.bob.address())
+        .await?;
+    let escrow_uid = DefaultAlkahestClient::get_attested_event(receipt)?.uid;
+
+    let obligation = hook_escrow.get_obligation(escrow_uid).await?;
+    assert_eq!(obligation.attestation.recipient, test.bob.address());
+    assert_eq!(
+        obligation.data.hook,
+        test.addresses.hook_based_addresses.native_token_escrow_hook
+    );
+
+    test.god_provider.anvil_increase_time(20).await?;
+
+    let bob_before = test.god_provider.get_balance(test.bob.address()).await?;
+    hook_escrow.reclaim_expired(escrow_uid).await?;
+    let bob_after = test.god_provider.get_balance(test.bob.address()).await?;
+    assert_eq!(bob_after - bob_before, amount);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_hooks_escrow_builder_mixes_tokens_and_native_value() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+
+    let mock_erc20 = MockERC20Permit::new(test.mock_addresses.erc20_a, &test.god_provider);
+    mock_erc20
+        .transfer(test.alice.address(), U256::from(10))
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+    let mock_erc721 = MockERC721::new(test.mock_addresses.erc721_a, &test.god_provider);
+    mock_erc721
+        .mint(test.alice.address())
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+
+    let item = ArbiterData {
+        arbiter: test.addresses.arbiters_addresses.trivial_arbiter,
+        demand: Bytes::new(),
+    };
+    let expiration = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 3600;
+    let native = U256::from(100_000_000_000_000_000u64);
+
+    let builder = test
+        .alice_client
+        .hook_based()
+        .escrow()
+        .hooks()
+        .builder(item, expiration)
+        .erc20(Erc20Data {
+            address: test.mock_addresses.erc20_a,
+            value: U256::from(10),
+        })
+        .erc721(Erc721Data {
+            address: test.mock_addresses.erc721_a,
+            id: U256::from(1),
+        })
+        .native_token(NativeTokenData { value: native });
+    assert_eq!(builder.total_value(), native);
+    let data = builder.obligation_data()?;
+    assert_eq!(data.values, vec![U256::ZERO, U256::ZERO, native]);
+
+    let receipt = builder.create().await?;
+    let escrow_uid = DefaultAlkahestClient::get_attested_event(receipt)?.uid;
+
+    let fulfillment = test
+        .bob_client
+        .string_obligation()
+        .do_obligation("report".to_string(), None, Some(escrow_uid))
+        .await?;
+    let fulfillment_uid = DefaultAlkahestClient::get_attested_event(fulfillment)?.uid;
+
+    let bob_before = test.god_provider.get_balance(test.bob.address()).await?;
+    // Alice collects on Bob's behalf so Bob's native balance only changes by
+    // the escrowed amount.
+    test.alice_client
+        .hook_based()
+        .escrow()
+        .hooks()
+        .collect(escrow_uid, fulfillment_uid)
+        .await?;
+    let bob_after = test.god_provider.get_balance(test.bob.address()).await?;
+
+    assert_eq!(bob_after - bob_before, native);
+    assert_eq!(
+        mock_erc20.balanceOf(test.bob.address()).call().await?,
+        U256::from(10)
+    );
+    assert_eq!(
+        mock_erc721.ownerOf(U256::from(1)).call().await?,
+        test.bob.address()
+    );
+    Ok(())
+}
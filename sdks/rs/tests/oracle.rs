@@ -2,14 +2,20 @@
 mod tests {
     use alkahest_rs::{
         DefaultAlkahestClient,
-        clients::oracle::ArbitrationMode,
+        clients::{
+            arbiters::{OracleQuorum, QuorumOutcome},
+            oracle::{
+                ArbitrationMode, PrometheusOracleMetrics, RevisionMode,
+                read_decision_records_jsonl, verify_decision_record,
+            },
+        },
         contracts::{self, obligations::StringObligation},
-        extensions::{HasErc20, HasOracle, HasStringObligation},
+        extensions::{HasArbiters, HasErc20, HasOracle, HasStringObligation},
         fixtures::MockERC20Permit,
         types::{ArbiterData, Erc20Data},
         utils::TestContext,
     };
-    use alloy::primitives::{Bytes, FixedBytes, bytes};
+    use alloy::primitives::{Address, Bytes, FixedBytes, bytes};
     use std::{
         sync::Arc,
         time::{Duration, SystemTime, UNIX_EPOCH},
@@ -267,6 +273,67 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_arbitrate_past_audited() -> eyre::Result<()> {
+        let test = setup_test_environment().await?;
+        let (_, _, escrow_uid) = setup_escrow(&test).await?;
+
+        let fulfillment_uid = make_fulfillment(&test, "good", escrow_uid).await?;
+
+        test.bob_client
+            .oracle()
+            .request_arbitration(fulfillment_uid, test.bob.address(), Bytes::default())
+            .await?;
+
+        let audit_log = test
+            .bob_client
+            .oracle()
+            .decision_audit_log(test.bob.clone())
+            .await?;
+
+        let bob_client = Arc::new(test.bob_client.clone());
+        let result = test
+            .bob_client
+            .oracle()
+            .arbitrate_many_audited(
+                move |awd| {
+                    let client = bob_client.clone();
+                    let attestation = awd.attestation.clone();
+                    async move {
+                        let obligation = client
+                            .extract_obligation_data::<StringObligation::ObligationData>(
+                                &attestation,
+                            )
+                            .ok()?;
+                        let evidence =
+                            Bytes::from(format!("item={}", obligation.item).into_bytes());
+                        Some((obligation.item == "good", evidence))
+                    }
+                },
+                |_| async {},
+                &audit_log,
+                ArbitrationMode::Past,
+            )
+            .await?;
+
+        assert_eq!(result.past_decisions.len(), 1);
+
+        let records = audit_log.records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].record.fulfillment_uid, fulfillment_uid);
+        assert_eq!(records[0].record.escrow_uid, escrow_uid);
+        assert_eq!(records[0].record.evidence, Bytes::from("item=good"));
+        assert_eq!(
+            records[0].record.tx_hash,
+            result.past_decisions[0].receipt.transaction_hash
+        );
+
+        let exported = read_decision_records_jsonl(audit_log.to_jsonl()?.as_bytes())?;
+        assert!(verify_decision_record(&exported[0])?);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_trivial_arbitrate_all() -> eyre::Result<()> {
         let test = setup_test_environment().await?;
@@ -559,4 +626,216 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_oracle_quorum_two_of_three() -> eyre::Result<()> {
+        let test = setup_test_environment().await?;
+
+        let mock_erc20 = MockERC20Permit::new(test.mock_addresses.erc20_a, &test.god_provider);
+        mock_erc20
+            .transfer(test.alice.address(), 100u64.try_into()?)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+        let price = Erc20Data {
+            address: test.mock_addresses.erc20_a,
+            value: 100u64.try_into()?,
+        };
+
+        // Third oracle never votes; two approvals are enough
+        let quorum = OracleQuorum::new(
+            [
+                test.alice.address(),
+                test.bob.address(),
+                Address::repeat_byte(0x77),
+            ],
+            2,
+        )?;
+        let item = test.alice_client.arbiters().quorum().demand(&quorum)?;
+        let expiration = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 3600;
+        let escrow_receipt = test
+            .alice_client
+            .erc20()
+            .escrow()
+            .default()
+            .permit_and_create(&price, &item, expiration)
+            .await?;
+        let escrow_uid = DefaultAlkahestClient::get_attested_event(escrow_receipt)?.uid;
+
+        let fulfillment_uid = make_fulfillment(&test, "good", escrow_uid).await?;
+
+        test.alice_client
+            .oracle()
+            .arbitrate(fulfillment_uid, quorum.data().clone(), true)
+            .await?;
+
+        let mut updates = Vec::new();
+        let wait_quorum = quorum.clone();
+        let watcher = test.bob_client.clone();
+        let waiting = tokio::spawn(async move {
+            watcher
+                .arbiters()
+                .quorum()
+                .wait_for_quorum(&wait_quorum, fulfillment_uid, None, |p| {
+                    updates.push(p.approvals())
+                })
+                .await
+                .map(|progress| (progress, updates))
+        });
+
+        test.bob_client
+            .oracle()
+            .arbitrate(fulfillment_uid, quorum.data().clone(), true)
+            .await?;
+
+        let (progress, updates) = waiting.await??;
+        assert_eq!(progress.outcome(), Some(QuorumOutcome::Approved));
+        assert_eq!(progress.pending(), 1);
+        assert_eq!(updates.last(), Some(&2));
+
+        test.bob_client
+            .erc20()
+            .escrow()
+            .default()
+            .collect(escrow_uid, fulfillment_uid)
+            .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fulfill_and_request_arbitration() -> eyre::Result<()> {
+        let test = setup_test_environment().await?;
+        let (_, _, escrow_uid) = setup_escrow(&test).await?;
+
+        let bob_client = test.bob_client.clone();
+        let result = test
+            .bob_client
+            .fulfill_and_request_arbitration(escrow_uid, |escrow_uid| async move {
+                bob_client
+                    .string_obligation()
+                    .do_obligation("good".to_string(), None, Some(escrow_uid))
+                    .await
+            })
+            .await?;
+
+        assert_eq!(result.requests.len(), 1);
+        assert_eq!(result.requests[0].oracle, test.bob.address());
+        assert_eq!(result.requests[0].demand, Bytes::default());
+
+        // The oracle listener picks the request up without any manual call
+        let oracle_client = test.bob_client.oracle().clone();
+        let arbitration = test
+            .bob_client
+            .oracle()
+            .arbitrate_many_sync(
+                move |awd| {
+                    let obligation = oracle_client
+                        .extract_obligation_data::<StringObligation::ObligationData>(
+                            &awd.attestation,
+                        )
+                        .ok()?;
+                    Some(obligation.item == "good")
+                },
+                |_| async {},
+                ArbitrationMode::Past,
+            )
+            .await?;
+        assert_eq!(arbitration.past_decisions.len(), 1);
+
+        test.bob_client
+            .erc20()
+            .escrow()
+            .default()
+            .collect(escrow_uid, result.fulfillment_uid)
+            .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_revise_decisions() -> eyre::Result<()> {
+        let test = setup_test_environment().await?;
+        let (_, _, escrow_uid) = setup_escrow(&test).await?;
+
+        let fulfillment_uid = make_fulfillment(&test, "good", escrow_uid).await?;
+        test.bob_client
+            .oracle()
+            .request_arbitration(fulfillment_uid, test.bob.address(), Bytes::default())
+            .await?;
+        test.bob_client
+            .oracle()
+            .arbitrate_many_sync(|_| Some(true), |_| async {}, ArbitrationMode::Past)
+            .await?;
+
+        let decisions = test.bob_client.oracle().list_decisions().await?;
+        assert_eq!(decisions.len(), 1);
+        assert_eq!(decisions[0].fulfillment_uid, fulfillment_uid);
+        assert!(decisions[0].decision);
+
+        // Dry run reports the change without submitting it
+        let report = test
+            .bob_client
+            .oracle()
+            .revise_decisions(|_| Some(false), RevisionMode::DryRun)
+            .await?;
+        assert_eq!(report.revisions.len(), 1);
+        assert!(report.revisions[0].previous);
+        assert!(!report.revisions[0].revised);
+        assert!(report.submitted.is_empty());
+        assert!(test.bob_client.oracle().list_decisions().await?[0].decision);
+
+        let report = test
+            .bob_client
+            .oracle()
+            .revise_decisions(|_| Some(false), RevisionMode::Apply)
+            .await?;
+        assert_eq!(report.submitted.len(), 1);
+        assert!(!test.bob_client.oracle().list_decisions().await?[0].decision);
+
+        // Re-running with the same handler is a no-op
+        let report = test
+            .bob_client
+            .oracle()
+            .revise_decisions(|_| Some(false), RevisionMode::Apply)
+            .await?;
+        assert!(report.revisions.is_empty());
+        assert_eq!(report.unchanged, 1);
+        assert!(report.submitted.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_oracle_metrics() -> eyre::Result<()> {
+        let test = setup_test_environment().await?;
+        let (_, _, escrow_uid) = setup_escrow(&test).await?;
+
+        let fulfillment_uid = make_fulfillment(&test, "good", escrow_uid).await?;
+        test.bob_client
+            .oracle()
+            .request_arbitration(fulfillment_uid, test.bob.address(), Bytes::default())
+            .await?;
+
+        let metrics = Arc::new(PrometheusOracleMetrics::new());
+        let oracle = test
+            .bob_client
+            .oracle()
+            .clone()
+            .with_metrics(metrics.clone());
+        oracle
+            .arbitrate_many_sync(|_| Some(true), |_| async {}, ArbitrationMode::Past)
+            .await?;
+
+        assert_eq!(metrics.requests_seen(), 1);
+        assert_eq!(metrics.decisions(true), 1);
+        assert_eq!(metrics.decisions(false), 0);
+        assert_eq!(metrics.failures(), 0);
+        assert_eq!(metrics.queue_depth(), 0);
+        assert!(metrics.last_block() > 0);
+        assert!(metrics.balance() > alloy::primitives::U256::ZERO);
+
+        Ok(())
+    }
 }
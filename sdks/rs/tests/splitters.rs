@@ -0,0 +1,199 @@
+use alkahest_rs::{
+    DefaultAlkahestClient,
+    clients::{
+        arbiters::ArbitrationMode,
+        splitters::{
+            AmountSplit, EXECUTOR_SENTINEL, SplitterDemandData, SplittersModule,
+            amount::AmountSplits,
+            events::fulfillment_created,
+            oracle::{AmountSplitDecision, AmountSplitRequest},
+        },
+        string_obligation::StringObligationModule,
+    },
+    contracts::obligations::StringObligation,
+    extensions::{HasErc20, HasSplitters},
+    fixtures::MockERC20Permit,
+    types::{ArbiterData, Erc20Data},
+    utils::{TestContext, setup_test_environment},
+};
+use alloy::primitives::{Address, Bytes, FixedBytes, U256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Alice escrows 100 `erc20_a` tokens behind the ERC20 splitter with
+/// herself as oracle, and Bob fulfills through the splitter so he is paid
+/// as the executor. Returns the escrow and fulfillment UIDs.
+async fn escrow_and_fulfill(test: &TestContext) -> eyre::Result<(FixedBytes<32>, FixedBytes<32>)> {
+    let mock_erc20 = MockERC20Permit::new(test.mock_addresses.erc20_a, &test.god_provider);
+    mock_erc20
+        .transfer(test.alice.address(), U256::from(100))
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+
+    // Alice escrows 100 tokens and acts as the splitter's oracle.
+    let price = Erc20Data {
+        address: test.mock_addresses.erc20_a,
+        value: U256::from(100),
+    };
+    let item = ArbiterData {
+        arbiter: test.addresses.splitters_addresses.erc20_splitter,
+        demand: SplittersModule::encode_demand(&SplitterDemandData {
+            oracle: test.alice.address(),
+            data: Bytes::new(),
+        }),
+    };
+    let expiration = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 3600;
+    let (_, escrow) = test
+        .alice_client
+        .erc20()
+        .escrow()
+        .default()
+        .approve_and_create(&price, &item, expiration)
+        .await?;
+    let escrow_uid = DefaultAlkahestClient::get_attested_event(escrow)?.uid;
+
+    let bob_splitter = test.bob_client.splitters().erc20();
+    let fulfillment = bob_splitter
+        .create_fulfillment(
+            test.addresses.string_obligation_addresses.obligation,
+            StringObligationModule::encode(&StringObligation::ObligationData {
+                item: "done".to_string(),
+                schema: FixedBytes::ZERO,
+            }),
+            0,
+            escrow_uid,
+            U256::ZERO,
+        )
+        .await?;
+    let fulfillment_uid = fulfillment_created(&fulfillment)?.fulfillmentUid;
+    assert_eq!(
+        bob_splitter.fulfiller(fulfillment_uid).await?,
+        Some(test.bob.address())
+    );
+
+    Ok((escrow_uid, fulfillment_uid))
+}
+
+#[tokio::test]
+async fn test_erc20_splitter_arbitrate_collect_and_distribute() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let (escrow_uid, fulfillment_uid) = escrow_and_fulfill(&test).await?;
+    let mock_erc20 = MockERC20Permit::new(test.mock_addresses.erc20_a, &test.god_provider);
+    let bob_splitter = test.bob_client.splitters().erc20();
+
+    let alice_splitter = test.alice_client.splitters().erc20();
+    assert_eq!(
+        alice_splitter.escrow_amount(escrow_uid).await?,
+        U256::from(100)
+    );
+
+    // Splits that don't cover the escrow are rejected before sending.
+    let charlie = Address::repeat_byte(0xc4);
+    let short = AmountSplits::new().pay(charlie, U256::from(60));
+    assert!(
+        alice_splitter
+            .arbitrate(fulfillment_uid, escrow_uid, short)
+            .await
+            .is_err()
+    );
+
+    let splits = AmountSplits::new()
+        .pay(charlie, U256::from(60))
+        .pay_executor(U256::from(40));
+    alice_splitter
+        .arbitrate(fulfillment_uid, escrow_uid, splits)
+        .await?;
+    assert!(
+        alice_splitter
+            .has_decision(test.alice.address(), fulfillment_uid, escrow_uid)
+            .await?
+    );
+
+    let receipt = bob_splitter
+        .collect_and_distribute(
+            test.addresses.erc20_addresses.escrow_obligation_default,
+            escrow_uid,
+            fulfillment_uid,
+        )
+        .await?;
+    let distribution = bob_splitter.distribution(&receipt)?;
+    assert_eq!(distribution.escrow, escrow_uid);
+    assert_eq!(distribution.fulfiller, test.bob.address());
+    assert_eq!(distribution.splits[1].recipient, EXECUTOR_SENTINEL);
+    assert!(distribution.failed.is_empty());
+
+    assert_eq!(mock_erc20.balanceOf(charlie).call().await?, U256::from(60));
+    assert_eq!(
+        mock_erc20.balanceOf(test.bob.address()).call().await?,
+        U256::from(40)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_erc20_splitter_oracle_decides_past_requests() -> eyre::Result<()> {
+    let test = setup_test_environment().await?;
+    let (escrow_uid, fulfillment_uid) = escrow_and_fulfill(&test).await?;
+
+    let alice_splitter = test.alice_client.splitters().erc20();
+    alice_splitter
+        .request_arbitration(
+            fulfillment_uid,
+            escrow_uid,
+            test.alice.address(),
+            Bytes::new(),
+        )
+        .await?;
+
+    // The executor takes everything the escrow holds.
+    let result = alice_splitter
+        .arbitrate_many_sync(
+            |request: &AmountSplitRequest| {
+                Some(vec![AmountSplit {
+                    recipient: EXECUTOR_SENTINEL,
+                    amount: request.locked,
+                }])
+            },
+            |_: &AmountSplitDecision| async {},
+            ArbitrationMode::PastUnarbitrated,
+        )
+        .await?;
+    assert_eq!(result.past_decisions.len(), 1);
+    assert!(result.subscription.is_none());
+    let decision = &result.past_decisions[0];
+    assert_eq!(decision.request.escrow.uid, escrow_uid);
+    assert_eq!(decision.request.locked, U256::from(100));
+
+    let splits = alice_splitter
+        .get_splits(test.alice.address(), fulfillment_uid, escrow_uid)
+        .await?;
+    assert_eq!(splits.len(), 1);
+    assert_eq!(splits[0].amount, U256::from(100));
+
+    // Decided requests are skipped on the next run.
+    let result = alice_splitter
+        .arbitrate_many_sync(
+            |_: &AmountSplitRequest| None,
+            |_: &AmountSplitDecision| async {},
+            ArbitrationMode::PastUnarbitrated,
+        )
+        .await?;
+    assert!(result.past_decisions.is_empty());
+
+    // Splits that don't cover the escrow fail the run before anything is sent.
+    let result = alice_splitter
+        .arbitrate_many_sync(
+            |_: &AmountSplitRequest| {
+                Some(vec![AmountSplit {
+                    recipient: EXECUTOR_SENTINEL,
+                    amount: U256::from(1),
+                }])
+            },
+            |_: &AmountSplitDecision| async {},
+            ArbitrationMode::Past,
+        )
+        .await;
+    assert!(result.is_err());
+    Ok(())
+}